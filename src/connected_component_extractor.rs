@@ -8,13 +8,16 @@
 extern crate clap;
 extern crate lib_dachshund;
 
-use std::io;
-
 use clap::{App, Arg, ArgMatches};
 
+use lib_dachshund::dachshund::bad_rows::BadRowPolicy;
 use lib_dachshund::dachshund::error::CLQResult;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::input_format::InputFormat;
+use lib_dachshund::dachshund::input_ordering::InputOrdering;
 use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::output_format::OutputFormat;
+use lib_dachshund::dachshund::seed::Seed;
 use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
 use lib_dachshund::dachshund::strongly_connected_components_transformer::StronglyConnectedComponentsTransformer;
 use lib_dachshund::dachshund::transformer_base::TransformerBase;
@@ -37,6 +40,13 @@ fn get_command_line_args() -> ArgMatches<'static> {
                 .short("d")
                 .help("Interpret input as directed graph and calculate strongly connected components."),
         )
+        .args(&BadRowPolicy::get_args())
+        .args(&InputFormat::get_args())
+        .args(&InputOrdering::get_args())
+        .args(&Input::get_args())
+        .args(&Output::get_args())
+        .args(&OutputFormat::get_args())
+        .args(&Seed::get_args())
         .get_matches();
     matches
 }
@@ -44,14 +54,26 @@ fn get_command_line_args() -> ArgMatches<'static> {
 fn main() -> CLQResult<()> {
 
     let matches: ArgMatches = get_command_line_args();
-    let stdio: io::Stdin = io::stdin();
-    let input: Input = Input::console(&stdio);
-    let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
-    if matches.is_present("directed") {
-        ConnectedComponentsTransformer::new().run(input, output)?;
+    let bad_row_policy = BadRowPolicy::from_argmatches(&matches)?;
+    let input_format = InputFormat::from_argmatches(&matches)?;
+    let input_ordering = InputOrdering::from_argmatches(&matches)?;
+    let output_format = OutputFormat::from_argmatches(&matches)?;
+    let input: Input = Input::from_argmatches(&matches)?
+        .with_format(input_format)
+        .with_ordering(input_ordering);
+    let output: Output = Output::from_argmatches(&matches)?.with_format(output_format);
+    // nothing here is randomized, but --seed is accepted (and validated) like in
+    // the other binaries.
+    Seed::from_argmatches(&matches)?;
+    let summary = if matches.is_present("directed") {
+        ConnectedComponentsTransformer::new().run_with_bad_row_policy(input, output, bad_row_policy)?
     } else {
-        StronglyConnectedComponentsTransformer::new().run(input, output)?;
+        StronglyConnectedComponentsTransformer::new().run_with_bad_row_policy(
+            input,
+            output,
+            bad_row_policy,
+        )?
     };
+    summary.report();
     Ok(())
 }
@@ -8,14 +8,17 @@
 extern crate clap;
 extern crate lib_dachshund;
 
-use std::io;
-
 use clap::{App, Arg, ArgMatches};
 
+use lib_dachshund::dachshund::bad_rows::BadRowPolicy;
 use lib_dachshund::dachshund::core_transformer::CoreTransformer;
 use lib_dachshund::dachshund::error::CLQResult;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::input_format::InputFormat;
+use lib_dachshund::dachshund::input_ordering::InputOrdering;
 use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::output_format::OutputFormat;
+use lib_dachshund::dachshund::seed::Seed;
 use lib_dachshund::dachshund::transformer_base::TransformerBase;
 use lib_dachshund::dachshund::weighted_core_transformer::WeightedCoreTransformer;
 use lib_dachshund::dachshund::kpeak_transformer::KPeakTransformer;
@@ -44,23 +47,38 @@ fn get_command_line_args() -> ArgMatches<'static> {
                 .long("kpeaks")
                 .help("Calculates k-peak values and mountain assignments in graphs from stdin."),
         )
+        .args(&BadRowPolicy::get_args())
+        .args(&InputFormat::get_args())
+        .args(&InputOrdering::get_args())
+        .args(&Input::get_args())
+        .args(&Output::get_args())
+        .args(&OutputFormat::get_args())
+        .args(&Seed::get_args())
         .get_matches();
     matches
 }
 
 fn main() -> CLQResult<()> {
     let matches: ArgMatches = get_command_line_args();
-    let stdio: io::Stdin = io::stdin();
-    let input: Input = Input::console(&stdio);
-    let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
+    let bad_row_policy = BadRowPolicy::from_argmatches(&matches)?;
+    let input_format = InputFormat::from_argmatches(&matches)?;
+    let input_ordering = InputOrdering::from_argmatches(&matches)?;
+    let output_format = OutputFormat::from_argmatches(&matches)?;
+    let input: Input = Input::from_argmatches(&matches)?
+        .with_format(input_format)
+        .with_ordering(input_ordering);
+    let output: Output = Output::from_argmatches(&matches)?.with_format(output_format);
+    // nothing here is randomized, but --seed is accepted (and validated) like in
+    // the other binaries.
+    Seed::from_argmatches(&matches)?;
     assert!(!(matches.is_present("weighted") && matches.is_present("kpeaks")), "Input arguments include kpeaks and weighted. Cannot run kpeaks on weighted graph.");
-    if matches.is_present("weighted") {
-        WeightedCoreTransformer::new().run(input, output)?;
+    let summary = if matches.is_present("weighted") {
+        WeightedCoreTransformer::new().run_with_bad_row_policy(input, output, bad_row_policy)?
     } else if matches.is_present("kpeaks") {
-        KPeakTransformer::new().run(input, output)?;
+        KPeakTransformer::new().run_with_bad_row_policy(input, output, bad_row_policy)?
     } else {
-        CoreTransformer::new().run(input, output)?;
+        CoreTransformer::new().run_with_bad_row_policy(input, output, bad_row_policy)?
     };
+    summary.report();
     Ok(())
 }
@@ -4,7 +4,8 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::algorithms::sparse_matrix::SparseGraphMatrix;
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase};
 use nalgebra::DMatrix;
@@ -12,6 +13,18 @@ use std::collections::HashMap;
 
 type GraphMatrix = DMatrix<f64>;
 pub trait AdjacencyMatrix: GraphBase {
+    /// the entry an edge contributes to the adjacency matrix: 1 unless the
+    /// graph's edges carry weights.
+    fn get_edge_weight(&self, _edge: &<Self::NodeType as NodeBase>::NodeEdgeType) -> f64 {
+        1.0
+    }
+    /// sum of the weights of a node's edges (its degree, for unweighted graphs).
+    fn get_weighted_degree(&self, node_id: NodeId) -> f64 {
+        self.get_node(node_id)
+            .get_edges()
+            .map(|e| self.get_edge_weight(e))
+            .sum()
+    }
     fn get_adjacency_matrix_given_node_ids(&self, node_ids: &[NodeId]) -> GraphMatrix {
         let num_nodes = node_ids.len();
         let mut data: Vec<f64> = vec![0.0; num_nodes * num_nodes];
@@ -26,7 +39,7 @@ pub trait AdjacencyMatrix: GraphBase {
             for e in self.get_node(*node_id).get_edges() {
                 let j = pos_map.get(&e.get_neighbor_id()).unwrap();
                 let pos = i * num_nodes + j;
-                data[pos] += 1.0;
+                data[pos] += self.get_edge_weight(e);
             }
         }
         GraphMatrix::from_vec(num_nodes, num_nodes, data)
@@ -38,4 +51,30 @@ pub trait AdjacencyMatrix: GraphBase {
             node_ids,
         )
     }
+    /// same as get_adjacency_matrix_given_node_ids, in sparse form.
+    fn get_sparse_adjacency_matrix_given_node_ids(&self, node_ids: &[NodeId]) -> SparseGraphMatrix {
+        let index = DenseIndex::new(node_ids.to_vec());
+        let rows = node_ids
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| {
+                        (
+                            index.get_index(e.get_neighbor_id()),
+                            self.get_edge_weight(e),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        SparseGraphMatrix::from_rows(rows)
+    }
+    fn get_sparse_adjacency_matrix(&self) -> (SparseGraphMatrix, Vec<NodeId>) {
+        let node_ids = self.get_ordered_node_ids();
+        (
+            self.get_sparse_adjacency_matrix_given_node_ids(&node_ids),
+            node_ids,
+        )
+    }
 }
@@ -4,18 +4,90 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
+use crate::dachshund::algorithms::convergence::IterativeResult;
+use crate::dachshund::algorithms::lanczos::{
+    get_dense_eigenpairs, LanczosSolver, Spectrum, SPARSE_SOLVER_MIN_NODES,
+};
 use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::algorithms::sparse_matrix::{LinearOperator, SparseGraphMatrix};
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use std::collections::HashMap;
 
 pub trait AlgebraicConnectivity: GraphBase + Laplacian {
     // Algebraic Connectivity, or the Fiedler Measure, is the second-smallest eigenvalue of the graph Laplacian.
     // The lower the value, the less decomposable the graph's adjacency matrix is. Thanks to the nalgebra
     // crate computing this is quite straightforward.
-    fn get_algebraic_connectivity(&self) -> f64 {
+    // Large graphs use a sparse Laplacian and the Lanczos solver.
+    // Graphs with fewer than two nodes have no second eigenvalue, and give an error.
+    fn get_algebraic_connectivity(&self) -> CLQResult<IterativeResult<f64>> {
+        if self.count_nodes() >= SPARSE_SOLVER_MIN_NODES {
+            return self.get_algebraic_connectivity_sparse(1e-8);
+        }
+        self.get_algebraic_connectivity_dense()
+    }
+    fn get_algebraic_connectivity_dense(&self) -> CLQResult<IterativeResult<f64>> {
+        check_num_nodes(self.count_nodes())?;
         let (laplacian, _ids) = self.get_laplacian_matrix();
         let eigen = laplacian.symmetric_eigen();
         let mut eigenvalues: Vec<f64> = eigen.eigenvalues.iter().cloned().collect();
-        eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        eigenvalues[1]
+        eigenvalues.sort_by(|a, b| a.total_cmp(b));
+        Ok(IterativeResult::exact(eigenvalues[1]))
+    }
+    // The constant vector spans the Laplacian's null space for connected graphs, so
+    // with it projected out, the smallest remaining eigenvalue is the second smallest
+    // overall (or 0 again, if the graph is disconnected).
+    fn get_algebraic_connectivity_sparse(&self, tolerance: f64) -> CLQResult<IterativeResult<f64>> {
+        check_num_nodes(self.count_nodes())?;
+        let (laplacian, ids) = self.get_sparse_laplacian_matrix();
+        let constant = vec![1.0; ids.len()];
+        let eigen =
+            LanczosSolver::new(tolerance).solve(&laplacian, 1, Spectrum::Smallest, &[constant]);
+        Ok(IterativeResult::new(
+            eigen.values[0].max(0.0),
+            eigen.convergence,
+        ))
+    }
+    /// The eigenvector belonging to the algebraic connectivity. Ordering nodes by
+    /// their entry places tightly knit nodes next to each other; its sign pattern
+    /// gives a spectral bisection of the graph.
+    fn get_fiedler_vector(&self) -> CLQResult<IterativeResult<HashMap<NodeId, f64>>> {
+        let (laplacian, ids) = self.get_sparse_laplacian_matrix();
+        let pair = get_fiedler_pair(&laplacian, 1e-8)?;
+        Ok(pair.map(|(_value, vector)| ids.into_iter().zip(vector).collect()))
+    }
+}
+
+/// algebraic connectivity and Fiedler vector for a Laplacian matrix with at
+/// least two rows.
+pub fn get_fiedler_pair(
+    laplacian: &SparseGraphMatrix,
+    tolerance: f64,
+) -> CLQResult<IterativeResult<(f64, Vec<f64>)>> {
+    let n = laplacian.dim();
+    check_num_nodes(n)?;
+    let mut eigen = if n >= SPARSE_SOLVER_MIN_NODES {
+        let constant = vec![1.0; n];
+        LanczosSolver::new(tolerance).solve(laplacian, 1, Spectrum::Smallest, &[constant])
+    } else {
+        let mut eigen = get_dense_eigenpairs(laplacian, 2, Spectrum::Smallest);
+        eigen.values.remove(0);
+        eigen.vectors.remove(0);
+        eigen
+    };
+    Ok(IterativeResult::new(
+        (eigen.values.remove(0).max(0.0), eigen.vectors.remove(0)),
+        eigen.convergence,
+    ))
+}
+
+fn check_num_nodes(num_nodes: usize) -> CLQResult<()> {
+    if num_nodes < 2 {
+        return Err(CLQError::from(format!(
+            "Algebraic connectivity needs at least 2 nodes, got {}",
+            num_nodes
+        )));
     }
+    Ok(())
 }
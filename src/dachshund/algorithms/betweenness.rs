@@ -6,20 +6,68 @@
  */
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
-use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
-use std::collections::HashMap;
+use ordered_float::NotNan;
+use rand::Rng;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// How Brandes betweenness is scaled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BetweennessOptions {
+    /// divide by the number of pairs of other nodes: (n - 1)(n - 2), or n(n - 1)
+    /// when counting endpoints.
+    pub normalized: bool,
+    /// count the endpoints of shortest paths as lying on them.
+    pub endpoints: bool,
+    /// the length of a path is the sum of the weights of its edges, which should
+    /// be positive, rather than its number of edges. Graphs without edge weights
+    /// count edges either way.
+    pub weighted: bool,
+}
+
+/// Betweenness estimated from a sample of shortest paths, on the same scale as
+/// the exact Brandes betweenness computed with the same options.
+#[derive(Clone, Debug)]
+pub struct ApproximateBetweenness {
+    pub values: HashMap<NodeId, f64>,
+    /// number of shortest paths sampled (0 if betweenness was computed exactly).
+    pub num_samples: usize,
+    /// with the requested probability, every estimate is within this of the exact
+    /// value.
+    pub max_error: f64,
+}
+
+/// Node and edge betweenness. Brandes betweenness works on directed graphs too,
+/// which override `get_dense_successors` and `follows_edge_directions`; the
+/// other measures are only defined for undirected graphs.
+pub trait Betweenness: GraphBase {
+    /// nodes reachable from each node through a single edge, as dense indices.
+    fn get_dense_successors(&self, index: &DenseIndex) -> Vec<Vec<usize>> {
+        self.get_dense_adjacency(index)
+    }
+    /// the weights of the edges to the nodes given by `get_dense_successors`, in
+    /// the same order, for graphs with weighted edges.
+    fn get_dense_successor_weights(&self, _index: &DenseIndex) -> Option<Vec<Vec<f64>>> {
+        None
+    }
+    /// whether a path from s to t is different from the path from t to s.
+    fn follows_edge_directions(&self) -> bool {
+        false
+    }
 
-pub trait Betweenness:
-    GraphBase + UndirectedGraph + Connectivity + ShortestPaths + ConnectivityUndirected
-{
     fn get_node_betweenness_starting_from_sources(
         &self,
         sources: &[NodeId],
         check_is_connected: bool,
         nodes_in_connected_component: Option<Vec<NodeId>>,
-    ) -> Result<HashMap<NodeId, f64>, &'static str> {
+    ) -> Result<HashMap<NodeId, f64>, &'static str>
+    where
+        Self: UndirectedGraph + Connectivity + ShortestPaths + ConnectivityUndirected,
+    {
         if self.count_nodes() == 0 {
             return Err("Graph is empty");
         }
@@ -46,49 +94,436 @@ pub trait Betweenness:
         Ok(path_counts)
     }
     // graph must be connected if you're calling this
-    fn get_node_betweenness(&self) -> Result<HashMap<NodeId, f64>, &'static str> {
+    fn get_node_betweenness(&self) -> Result<HashMap<NodeId, f64>, &'static str>
+    where
+        Self: UndirectedGraph + Connectivity + ShortestPaths + ConnectivityUndirected,
+    {
         let ids: Vec<NodeId> = self.get_ids_iter().cloned().collect();
         self.get_node_betweenness_starting_from_sources(&ids, true, None)
     }
 
     fn get_node_betweenness_brandes(&self) -> Result<HashMap<NodeId, f64>, &'static str> {
+        self.get_node_betweenness_brandes_with_options(BetweennessOptions::default())
+    }
+
+    /// Brandes betweenness, following edge directions in directed graphs. Pairs of
+    /// nodes without any path between them (e.g. in different connected
+    /// components) do not contribute, so the graph need not be connected.
+    fn get_node_betweenness_brandes_with_options(
+        &self,
+        options: BetweennessOptions,
+    ) -> Result<HashMap<NodeId, f64>, &'static str> {
         // Algorithm: Brandes, Ulrik. A Faster Algorithm For Betweeness Centrality.
         // https://www.eecs.wsu.edu/~assefaw/CptS580-06/papers/brandes01centrality.pdf
 
         if self.count_nodes() == 0 {
             return Err("Graph is empty");
         }
-        if !self.get_is_connected().unwrap() {
-            return Err("Graph should be connected to compute betweenness.");
+
+        let index = self.get_dense_index();
+        let neighbors = self.get_dense_successors(&index);
+        let lengths = get_dense_lengths(self, &index, options)?;
+        let num_nodes = index.len();
+        let mut betweenness: Vec<f64> = vec![0.0; num_nodes];
+        let mut state = BrandesState::new(num_nodes, options);
+        for source in 0..num_nodes {
+            state.accumulate(&neighbors, lengths.as_deref(), source, &mut betweenness);
         }
 
-        let mut betweenness: HashMap<NodeId, f64> = HashMap::new();
-        for node_id in self.get_ids_iter() {
-            betweenness.insert(*node_id, 0.0);
+        if let Some(scale) =
+            get_betweenness_scale(num_nodes, options, self.follows_edge_directions())
+        {
+            betweenness.iter_mut().for_each(|x| *x *= scale);
         }
+        Ok(index.to_node_map(betweenness))
+    }
 
-        for source in self.get_ids_iter() {
-            let (mut stack, shortest_path_counts, preds) = self.get_shortest_paths_bfs(*source);
+    /// same as get_node_betweenness_brandes_with_options, with sources spread
+    /// across the threads of the current rayon pool.
+    fn get_node_betweenness_brandes_parallel(
+        &self,
+        options: BetweennessOptions,
+    ) -> Result<HashMap<NodeId, f64>, &'static str>
+    where
+        Self: Sync,
+    {
+        let ids = self.get_ordered_node_ids();
+        self.get_node_betweenness_brandes_parallel_from_sources(&ids, options)
+    }
+    /// the raw contribution of shortest paths starting at `sources` to Brandes
+    /// betweenness: it is not extrapolated to all sources, and `options` scale it
+    /// by the number of nodes of the graph, as they would full betweenness. Each
+    /// thread of the current rayon pool accumulates dependencies from its share of
+    /// the sources, and these are summed up at the end.
+    fn get_node_betweenness_brandes_parallel_from_sources(
+        &self,
+        sources: &[NodeId],
+        options: BetweennessOptions,
+    ) -> Result<HashMap<NodeId, f64>, &'static str>
+    where
+        Self: Sync,
+    {
+        if self.count_nodes() == 0 {
+            return Err("Graph is empty");
+        }
+        let index = self.get_dense_index();
+        let sources: Vec<usize> = sources
+            .iter()
+            .map(|id| index.try_get_index(*id))
+            .collect::<Option<Vec<usize>>>()
+            .ok_or("Sources should be nodes of the graph.")?;
+        let neighbors = self.get_dense_successors(&index);
+        let lengths = get_dense_lengths(self, &index, options)?;
+        let num_nodes = index.len();
+        let mut betweenness: Vec<f64> = sources
+            .par_iter()
+            .fold(
+                || (BrandesState::new(num_nodes, options), vec![0.0; num_nodes]),
+                |(mut state, mut betweenness), source| {
+                    state.accumulate(&neighbors, lengths.as_deref(), *source, &mut betweenness);
+                    (state, betweenness)
+                },
+            )
+            .map(|(_state, betweenness)| betweenness)
+            .reduce(
+                || vec![0.0; num_nodes],
+                |mut total, betweenness| {
+                    total.iter_mut().zip(betweenness).for_each(|(x, y)| *x += y);
+                    total
+                },
+            );
+        if let Some(scale) =
+            get_betweenness_scale(num_nodes, options, self.follows_edge_directions())
+        {
+            betweenness.iter_mut().for_each(|x| *x *= scale);
+        }
+        Ok(index.to_node_map(betweenness))
+    }
+
+    /// Riondato & Kornaropoulos, Fast approximation of betweenness centrality
+    /// through sampling: estimates betweenness from shortest paths between random
+    /// pairs of nodes. With probability at least 1 - `delta`, every estimate of the
+    /// betweenness normalized by the number of ordered pairs, n(n - 1), is within
+    /// `epsilon` of the exact value; `max_error` is that bound on the scale of the
+    /// returned values. The number of samples depends on the vertex diameter of the
+    /// graph, not on its size; when it would exceed the number of nodes, exact
+    /// betweenness is cheaper and returned instead. Paths are weighted shortest
+    /// paths with `options.weighted`, in which case the vertex diameter is bounded
+    /// using the smallest edge weight.
+    fn get_approximate_node_betweenness<R: Rng>(
+        &self,
+        epsilon: f64,
+        delta: f64,
+        options: BetweennessOptions,
+        rng: &mut R,
+    ) -> Result<ApproximateBetweenness, &'static str>
+    where
+        Self: UndirectedGraph,
+    {
+        if !(epsilon > 0.0 && epsilon < 1.0 && delta > 0.0 && delta < 1.0) {
+            return Err("epsilon and delta should be between 0 and 1.");
+        }
+        if options.endpoints {
+            return Err("Approximate betweenness does not count endpoints.");
+        }
+        if self.count_nodes() == 0 {
+            return Err("Graph is empty");
+        }
+        let index = self.get_dense_index();
+        let neighbors = self.get_dense_successors(&index);
+        let lengths = get_dense_lengths(self, &index, options)?;
+        let lengths = lengths.as_deref();
+        let num_nodes = index.len();
+        let mut state = BrandesState::new(num_nodes, options);
 
-            let mut dependencies: HashMap<NodeId, f64> = HashMap::new();
-            for node_id in self.get_ids_iter() {
-                dependencies.insert(*node_id, 0.0);
+        let vertex_diameter = get_vertex_diameter_bound(&neighbors, lengths, &mut state);
+        let num_samples = ((0.5 / (epsilon * epsilon))
+            * ((vertex_diameter.saturating_sub(2).max(1) as f64)
+                .log2()
+                .floor()
+                + 1.0
+                + (1.0 / delta).ln()))
+        .ceil() as usize;
+        if num_nodes < 3 || num_samples >= num_nodes {
+            let mut betweenness: Vec<f64> = vec![0.0; num_nodes];
+            for source in 0..num_nodes {
+                state.accumulate(&neighbors, lengths, source, &mut betweenness);
             }
+            if let Some(scale) = get_betweenness_scale(num_nodes, options, false) {
+                betweenness.iter_mut().for_each(|x| *x *= scale);
+            }
+            return Ok(ApproximateBetweenness {
+                values: index.to_node_map(betweenness),
+                num_samples: 0,
+                max_error: 0.0,
+            });
+        }
 
-            // Process nodes in order of nonincreasing distance from source to leverage
-            // recurrence relation in accumulating pair dependencies.
-            while !stack.is_empty() {
-                let w = stack.pop().unwrap();
-                for pred in &preds[&w] {
-                    *dependencies.entry(*pred).or_insert(0.0) += (0.5 + dependencies[&w])
-                        * (shortest_path_counts[&pred] as f64 / shortest_path_counts[&w] as f64)
+        let mut counts: Vec<f64> = vec![0.0; num_nodes];
+        for _ in 0..num_samples {
+            let source = rng.gen_range(0, num_nodes);
+            let mut target = rng.gen_range(0, num_nodes - 1);
+            if target >= source {
+                target += 1;
+            }
+            state.search(&neighbors, lengths, source);
+            if state.dists[target] < 0.0 {
+                continue;
+            }
+            // walk back from the target, picking each predecessor with probability
+            // proportional to the number of shortest paths through it.
+            let mut w = target;
+            while w != source {
+                let mut choice = rng.gen_range(0.0, state.shortest_path_counts[w]);
+                let mut pred = *state.preds[w].last().unwrap();
+                for &p in &state.preds[w] {
+                    choice -= state.shortest_path_counts[p];
+                    if choice < 0.0 {
+                        pred = p;
+                        break;
+                    }
                 }
-                if w != *source {
-                    *betweenness.entry(w).or_insert(0.0) += dependencies[&w]
+                if pred != source {
+                    counts[pred] += 1.0;
                 }
+                w = pred;
             }
         }
+        // counts / num_samples estimates betweenness over n(n - 1) ordered pairs.
+        let num_pairs = (num_nodes * (num_nodes - 1)) as f64;
+        let scale = num_pairs * get_betweenness_scale(num_nodes, options, false).unwrap_or(1.0);
+        Ok(ApproximateBetweenness {
+            values: index.to_node_map(
+                counts
+                    .into_iter()
+                    .map(|x| x / num_samples as f64 * scale)
+                    .collect(),
+            ),
+            num_samples,
+            max_error: epsilon * scale,
+        })
+    }
 
-        Ok(betweenness)
+    /// Betweenness of each edge: the number of shortest paths between pairs of
+    /// nodes going through it, pairs with several shortest paths splitting their
+    /// count evenly between them. Edges are keyed with their smaller node id first.
+    /// Unlike node betweenness, this does not require the graph to be connected.
+    fn get_edge_betweenness(&self) -> Result<HashMap<(NodeId, NodeId), f64>, &'static str>
+    where
+        Self: UndirectedGraph,
+    {
+        if self.count_nodes() == 0 {
+            return Err("Graph is empty");
+        }
+        let index = self.get_dense_index();
+        let neighbors = self.get_dense_adjacency(&index);
+        let mut betweenness: HashMap<(usize, usize), f64> = HashMap::new();
+        accumulate_edge_betweenness(&neighbors, 0..index.len(), &mut betweenness);
+        Ok(betweenness
+            .into_iter()
+            .map(|((i, j), value)| ((index.get_id(i), index.get_id(j)), value))
+            .collect())
+    }
+}
+
+/// Scratch space for Brandes' algorithm, reused from one source to the next.
+struct BrandesState {
+    preds: Vec<Vec<usize>>,
+    shortest_path_counts: Vec<f64>,
+    // -1 for nodes not reached (yet).
+    dists: Vec<f64>,
+    dependencies: Vec<f64>,
+    stack: Vec<usize>,
+    queue: VecDeque<usize>,
+    heap: BinaryHeap<Reverse<(NotNan<f64>, usize)>>,
+    endpoint_weight: f64,
+}
+impl BrandesState {
+    fn new(num_nodes: usize, options: BetweennessOptions) -> Self {
+        Self {
+            preds: vec![Vec::new(); num_nodes],
+            shortest_path_counts: vec![0.0; num_nodes],
+            dists: vec![-1.0; num_nodes],
+            dependencies: vec![0.0; num_nodes],
+            stack: Vec::with_capacity(num_nodes),
+            queue: VecDeque::with_capacity(num_nodes),
+            heap: BinaryHeap::new(),
+            endpoint_weight: if options.endpoints { 1.0 } else { 0.0 },
+        }
+    }
+    /// counts shortest paths from `source` to each node, by breadth-first search, or
+    /// by Dijkstra's algorithm given the `lengths` of edges. Leaves the nodes
+    /// reached on the stack, in order of nondecreasing distance.
+    fn search(&mut self, neighbors: &[Vec<usize>], lengths: Option<&[Vec<f64>]>, source: usize) {
+        // only the nodes reached from the previous source need to be reset.
+        for v in self.stack.drain(..) {
+            self.preds[v].clear();
+            self.shortest_path_counts[v] = 0.0;
+            self.dists[v] = -1.0;
+            self.dependencies[v] = 0.0;
+        }
+        self.shortest_path_counts[source] = 1.0;
+        self.dists[source] = 0.0;
+        match lengths {
+            None => self.search_unweighted(neighbors, source),
+            Some(lengths) => self.search_weighted(neighbors, lengths, source),
+        }
+    }
+    fn search_unweighted(&mut self, neighbors: &[Vec<usize>], source: usize) {
+        self.queue.push_back(source);
+        while let Some(v) = self.queue.pop_front() {
+            self.stack.push(v);
+            for &w in &neighbors[v] {
+                if self.dists[w] < 0.0 {
+                    self.queue.push_back(w);
+                    self.dists[w] = self.dists[v] + 1.0;
+                }
+                if self.dists[w] == self.dists[v] + 1.0 {
+                    self.shortest_path_counts[w] += self.shortest_path_counts[v];
+                    self.preds[w].push(v);
+                }
+            }
+        }
+    }
+    fn search_weighted(&mut self, neighbors: &[Vec<usize>], lengths: &[Vec<f64>], source: usize) {
+        self.heap.push(Reverse((NotNan::new(0.0).unwrap(), source)));
+        while let Some(Reverse((dist, v))) = self.heap.pop() {
+            // a node is pushed again whenever a shorter path to it is found.
+            if dist.into_inner() > self.dists[v] {
+                continue;
+            }
+            self.stack.push(v);
+            for (&w, length) in neighbors[v].iter().zip(&lengths[v]) {
+                let dist = self.dists[v] + length;
+                if self.dists[w] < 0.0 || dist < self.dists[w] {
+                    self.dists[w] = dist;
+                    self.shortest_path_counts[w] = 0.0;
+                    self.preds[w].clear();
+                    self.heap.push(Reverse((NotNan::new(dist).unwrap(), w)));
+                }
+                if dist == self.dists[w] {
+                    self.shortest_path_counts[w] += self.shortest_path_counts[v];
+                    self.preds[w].push(v);
+                }
+            }
+        }
+    }
+    /// adds the (unscaled) dependencies of `source` on every node to `betweenness`.
+    fn accumulate(
+        &mut self,
+        neighbors: &[Vec<usize>],
+        lengths: Option<&[Vec<f64>]>,
+        source: usize,
+        betweenness: &mut [f64],
+    ) {
+        self.search(neighbors, lengths, source);
+        // the source is an endpoint of a path to every node it reaches.
+        betweenness[source] += self.endpoint_weight * (self.stack.len() - 1) as f64;
+
+        // Process nodes in order of nonincreasing distance from source to leverage
+        // recurrence relation in accumulating pair dependencies.
+        for &w in self.stack.iter().rev() {
+            for &pred in &self.preds[w] {
+                self.dependencies[pred] += (1.0 + self.dependencies[w])
+                    * (self.shortest_path_counts[pred] / self.shortest_path_counts[w]);
+            }
+            if w != source {
+                betweenness[w] += self.dependencies[w] + self.endpoint_weight;
+            }
+        }
+    }
+}
+
+/// edge weights to use as path lengths, if any; an error if some are not positive.
+fn get_dense_lengths<G: Betweenness>(
+    graph: &G,
+    index: &DenseIndex,
+    options: BetweennessOptions,
+) -> Result<Option<Vec<Vec<f64>>>, &'static str> {
+    if !options.weighted {
+        return Ok(None);
+    }
+    let lengths = graph.get_dense_successor_weights(index);
+    if let Some(lengths) = &lengths {
+        if !lengths.iter().flatten().all(|x| *x > 0.0 && x.is_finite()) {
+            return Err("Edge weights should be positive for weighted betweenness.");
+        }
+    }
+    Ok(lengths)
+}
+
+/// an upper bound on the number of nodes of any shortest path: from any node, a
+/// path through it to the two furthest nodes of its component is at least as long,
+/// and has at most (its length / the shortest edge length) edges.
+fn get_vertex_diameter_bound(
+    neighbors: &[Vec<usize>],
+    lengths: Option<&[Vec<f64>]>,
+    state: &mut BrandesState,
+) -> usize {
+    let min_length = lengths.map_or(1.0, |x| {
+        x.iter().flatten().cloned().fold(f64::INFINITY, f64::min)
+    });
+    let mut seen: Vec<bool> = vec![false; neighbors.len()];
+    let mut bound: usize = 0;
+    for source in 0..neighbors.len() {
+        if seen[source] {
+            continue;
+        }
+        state.search(neighbors, lengths, source);
+        let (mut first, mut second): (f64, f64) = (0.0, 0.0);
+        for &v in &state.stack {
+            seen[v] = true;
+            let dist = state.dists[v];
+            if dist > first {
+                second = first;
+                first = dist;
+            } else if dist > second {
+                second = dist;
+            }
+        }
+        let num_edges = ((first + second) / min_length).ceil() as usize;
+        bound = bound.max((num_edges + 1).min(state.stack.len()));
+    }
+    bound
+}
+
+/// the factor Brandes betweenness is multiplied by. Without normalization, paths
+/// in undirected graphs are counted from both ends, hence halved.
+fn get_betweenness_scale(
+    num_nodes: usize,
+    options: BetweennessOptions,
+    directed: bool,
+) -> Option<f64> {
+    let n = num_nodes as f64;
+    match (options.normalized, options.endpoints) {
+        (true, true) if num_nodes >= 2 => Some(1.0 / (n * (n - 1.0))),
+        (true, false) if num_nodes > 2 => Some(1.0 / ((n - 1.0) * (n - 2.0))),
+        (true, _) => None,
+        (false, _) if directed => None,
+        (false, _) => Some(0.5),
+    }
+}
+
+/// Brandes' algorithm, accumulating dependencies on edges rather than nodes: adds
+/// the contribution of shortest paths starting at each of `sources` to the
+/// betweenness of the edges (i, j), i < j, of an undirected graph given by its
+/// dense adjacency lists. Summing over all sources gives edge betweenness.
+pub fn accumulate_edge_betweenness<I: IntoIterator<Item = usize>>(
+    neighbors: &[Vec<usize>],
+    sources: I,
+    betweenness: &mut HashMap<(usize, usize), f64>,
+) {
+    let mut state = BrandesState::new(neighbors.len(), BetweennessOptions::default());
+    for source in sources {
+        state.search(neighbors, None, source);
+        for &w in state.stack.iter().rev() {
+            for &pred in &state.preds[w] {
+                let dependency = (state.shortest_path_counts[pred] / state.shortest_path_counts[w])
+                    * (1.0 + state.dependencies[w]);
+                // each pair of nodes is reached from both ends.
+                *betweenness.entry((pred.min(w), pred.max(w))).or_insert(0.0) += 0.5 * dependency;
+                state.dependencies[pred] += dependency;
+            }
+        }
     }
 }
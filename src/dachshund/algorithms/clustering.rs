@@ -45,7 +45,7 @@ pub trait Clustering: GraphBase {
     // Approximate Clustering - Randomly sample neighbors of nodes w/ degree at least 2.
     // k~=26,000 gives an approximation w/ <1% chance of an error of more than 1 percentage point.
     // See http://jgaa.info/accepted/2005/SchankWagner2005.9.2.pdf for approximation guarantees.
-    fn get_approx_avg_clustering(&self, samples: usize) -> f64 {
+    fn get_approx_avg_clustering<R: Rng>(&self, samples: usize, rng: &mut R) -> f64 {
         let ordered_nodes = self
             .get_nodes_iter()
             .filter(|node| node.degree() >= 2)
@@ -53,14 +53,13 @@ pub trait Clustering: GraphBase {
 
         let n = ordered_nodes.len();
         let mut successes = 0;
-        let mut rng = rand::thread_rng();
 
         for _i in 0..samples {
             // Pick a random node with degree at least 2.
             let v = &ordered_nodes[rng.gen_range(0, n)];
 
             // Choose 2 random nodes that are neighbors of j
-            let mut random_neighbors = v.get_edges().choose_multiple(&mut rng, 2).into_iter();
+            let mut random_neighbors = v.get_edges().choose_multiple(rng, 2).into_iter();
             let next_random_neighbor = random_neighbors.next();
             let u_id = next_random_neighbor.unwrap().get_neighbor_id();
             let w_id = random_neighbors.next().unwrap().get_neighbor_id();
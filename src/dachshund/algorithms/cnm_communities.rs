@@ -7,7 +7,7 @@
 extern crate nalgebra as na;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase, SimpleNode};
+use crate::dachshund::node::SimpleNode;
 use ordered_float::OrderedFloat;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
@@ -99,49 +99,33 @@ pub trait CNMCommunities: GraphBase<NodeType = SimpleNode> {
         // using the fact that tupled are compared in lexicographic order
         // first element holds delta_q, 2nd holds index
         let mut delta_q_maxheap: HashMap<usize, CNMCommunityMergeInstructionHeap> = HashMap::new();
-        let mut reverse_id_map: HashMap<NodeId, usize> = HashMap::new();
 
-        let mut num_edges: usize = 0;
-        let mut sorted_ids: Vec<NodeId> = Vec::with_capacity(self.count_nodes());
-        for id in self.get_ids_iter() {
-            sorted_ids.push(*id);
-        }
-        sorted_ids.sort();
-        for (i, id) in sorted_ids.into_iter().enumerate() {
+        // community i starts out as the node with dense index i.
+        let index = self.get_dense_index();
+        let neighbors = self.get_dense_adjacency(&index);
+        let degrees: Vec<usize> = neighbors.iter().map(|x| x.len()).collect();
+        let num_edges: usize = degrees.iter().sum::<usize>() / 2;
+        for (i, &id) in index.ids().iter().enumerate() {
             let mut community: Community = HashSet::new();
             community.insert(id);
             communities.insert(i, community);
-
-            let d = self.get_node(id).degree();
-
-            degree_map.insert(i, d);
-            reverse_id_map.insert(id, i);
-            num_edges += d;
+            degree_map.insert(i, degrees[i]);
             delta_q_maxheap.insert(i, BinaryHeap::new());
             delta_q_bmap.insert(i, HashMap::new());
         }
-        num_edges /= 2;
         let q0: f64 = 1.0 / (num_edges as f64);
-        for (_i, community) in communities.iter() {
-            for id in community {
-                for e in self.get_node(*id).get_edges() {
-                    let neighbor_id = e.get_neighbor_id();
-                    let i: &usize = reverse_id_map.get(&id).unwrap();
-                    let j: &usize = reverse_id_map.get(&neighbor_id).unwrap();
-                    let k_i: usize = degree_map[i];
-                    let k_j: usize = degree_map[j];
-                    let delta_qij: f64 =
-                        q0 - 2. * ((k_i * k_j) as f64) / (((2 * num_edges).pow(2)) as f64);
-                    delta_q_bmap.get_mut(i).unwrap().insert(*j, delta_qij);
-                    delta_q_maxheap
-                        .get_mut(i)
-                        .unwrap()
-                        .push(CNMCommunityMergeInstruction::new(
-                            OrderedFloat(delta_qij),
-                            *i,
-                            *j,
-                        ));
-                }
+        for (i, node_neighbors) in neighbors.iter().enumerate() {
+            let row_bmap = delta_q_bmap.get_mut(&i).unwrap();
+            let row_maxheap = delta_q_maxheap.get_mut(&i).unwrap();
+            for &j in node_neighbors {
+                let delta_qij: f64 = q0
+                    - 2. * ((degrees[i] * degrees[j]) as f64) / (((2 * num_edges).pow(2)) as f64);
+                row_bmap.insert(j, delta_qij);
+                row_maxheap.push(CNMCommunityMergeInstruction::new(
+                    OrderedFloat(delta_qij),
+                    i,
+                    j,
+                ));
             }
         }
         let maxh = self.get_max_maxheap(&delta_q_maxheap);
@@ -0,0 +1,86 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+
+/// How an iterative computation ended: whether it reached the requested
+/// tolerance, after how many iterations, and the residual it was compared
+/// against (what that is depends on the algorithm, e.g. the L1 change between
+/// successive iterates for power iteration).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Convergence {
+    pub converged: bool,
+    pub iterations: usize,
+    pub residual: f64,
+}
+impl Convergence {
+    /// for results computed directly, e.g. by a dense eigendecomposition.
+    pub fn exact() -> Self {
+        Self {
+            converged: true,
+            iterations: 0,
+            residual: 0.0,
+        }
+    }
+    /// for results combining several iterative computations: converged if all of
+    /// them did, with their iterations summed and the largest residual.
+    pub fn and(self, other: Convergence) -> Self {
+        Self {
+            converged: self.converged && other.converged,
+            iterations: self.iterations + other.iterations,
+            residual: self.residual.max(other.residual),
+        }
+    }
+}
+
+/// The value computed by an iterative (or spectral) algorithm, along with how
+/// the computation ended. Results are returned even if the iteration did not
+/// converge; use `into_converged` to treat that as an error.
+#[derive(Clone, Debug)]
+pub struct IterativeResult<T> {
+    pub value: T,
+    pub convergence: Convergence,
+}
+impl<T> IterativeResult<T> {
+    pub fn new(value: T, convergence: Convergence) -> Self {
+        Self { value, convergence }
+    }
+    pub fn exact(value: T) -> Self {
+        Self::new(value, Convergence::exact())
+    }
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> IterativeResult<U> {
+        IterativeResult::new(f(self.value), self.convergence)
+    }
+    /// the value, or an error if the iteration stopped before converging.
+    pub fn into_converged(self) -> CLQResult<T> {
+        if self.convergence.converged {
+            return Ok(self.value);
+        }
+        Err(CLQError::from(format!(
+            "Did not converge after {} iterations (residual: {})",
+            self.convergence.iterations, self.convergence.residual
+        )))
+    }
+}
+
+/// runs `step` (which returns the residual of the iteration) until the residual
+/// drops to `eps` or `max_iter` iterations have been done.
+pub fn iterate<F: FnMut() -> f64>(eps: f64, max_iter: usize, mut step: F) -> Convergence {
+    let mut convergence = Convergence {
+        converged: false,
+        iterations: 0,
+        residual: f64::INFINITY,
+    };
+    while convergence.iterations < max_iter {
+        convergence.residual = step();
+        convergence.iterations += 1;
+        if convergence.residual <= eps {
+            convergence.converged = true;
+            break;
+        }
+    }
+    convergence
+}
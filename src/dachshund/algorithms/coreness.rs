@@ -53,17 +53,12 @@ pub trait Coreness: GraphBase + ConnectedComponents {
         self._get_k_cores(k, &mut removed)
     }
 
-    fn _init_bin_starts(
-        &self,
-        ordered_nodes: &Vec<NodeId>,
-        degree: &HashMap<NodeId, usize>,
-    ) -> Vec<usize> {
-        // bin_boundaries[i] tracks the leftmost index in ordered_nodes
-        // such that degree of the node at that index >= i
+    fn _init_bin_starts(&self, ordered_degrees: &[usize]) -> Vec<usize> {
+        // bin_boundaries[i] tracks the leftmost index in ordered_degrees
+        // such that the degree at that index >= i
         let mut bin_boundaries = vec![0];
         let mut current_degree = 0;
-        for i in 0..ordered_nodes.len() {
-            let new_degree = degree[&ordered_nodes[i]];
+        for (i, &new_degree) in ordered_degrees.iter().enumerate() {
             if new_degree > current_degree {
                 // create one new bin for each possible degree value
                 for _ in current_degree + 1..=new_degree {
@@ -88,7 +83,8 @@ pub trait Coreness: GraphBase + ConnectedComponents {
         // coreness_bin_starts[i] = j means i core consists of nodes[j..]
         // so when computing connected components, we exclude
         // initial segments of nodes up to the starts of these bins.
-        let coreness_bin_starts = self._init_bin_starts(&nodes, &coreness);
+        let ordered_coreness: Vec<usize> = nodes.iter().map(|x| coreness[x]).collect();
+        let coreness_bin_starts = self._init_bin_starts(&ordered_coreness);
 
         let mut core_assignments: Vec<Vec<Vec<NodeId>>> = Vec::new();
         let mut removed: FxHashSet<NodeId>;
@@ -100,55 +96,62 @@ pub trait Coreness: GraphBase + ConnectedComponents {
     }
 
     fn get_coreness_values(&self) -> HashMap<NodeId, usize> {
-        // Traverse the nodes in increasing order of degree to calculate coreness.
-        // See: https://arxiv.org/abs/cs/0310049 for an explanation of the bookkeeping details.
+        let index = self.get_dense_index();
+        let mut neighbors = self.get_dense_adjacency(&index);
+        for node_nbrs in neighbors.iter_mut() {
+            node_nbrs.sort_unstable();
+            node_nbrs.dedup();
+        }
 
         // The initial value for the coreness of each node is its degree.
-        let mut coreness: HashMap<NodeId, usize> = self
-            .get_nodes_iter()
-            .map(|x| (x.get_id(), x.degree()))
+        let degrees: Vec<usize> = index
+            .ids()
+            .iter()
+            .map(|id| self.get_node(*id).degree())
             .collect();
+        let coreness = self._get_dense_coreness(degrees, |node| neighbors[node].iter().copied());
+        index.to_node_map(coreness)
+    }
 
+    /// coreness of nodes 0..n of a dense graph, given their degrees and their
+    /// (distinct) neighbors.
+    fn _get_dense_coreness<F, I>(&self, mut coreness: Vec<usize>, neighbors: F) -> Vec<usize>
+    where
+        F: Fn(usize) -> I,
+        I: Iterator<Item = usize>,
+    {
+        // Traverse the nodes in increasing order of degree to calculate coreness.
+        // See: https://arxiv.org/abs/cs/0310049 for an explanation of the bookkeeping details.
         // Nodes in increasing order of coreness. We process this in order
         // and keep in order as we delete edges.
-        let mut nodes: Vec<NodeId> = coreness.keys().cloned().collect();
-        nodes.sort_unstable_by_key(|node_id| coreness[node_id]);
-
-        let mut bin_starts = self._init_bin_starts(&nodes, &coreness);
+        let mut nodes: Vec<usize> = (0..coreness.len()).collect();
+        nodes.sort_unstable_by_key(|&node| coreness[node]);
+        let ordered_degrees: Vec<usize> = nodes.iter().map(|&x| coreness[x]).collect();
+        let mut bin_starts = self._init_bin_starts(&ordered_degrees);
 
-        let mut node_idx: HashMap<NodeId, usize> = HashMap::new();
+        // node_idx[v] is the position of v in nodes.
+        let mut node_idx: Vec<usize> = vec![0; nodes.len()];
         for (i, &node) in nodes.iter().enumerate() {
-            node_idx.insert(node, i);
-        }
-
-        let mut neighbors: HashMap<NodeId, FxHashSet<NodeId>> = HashMap::new();
-        for node in self.get_nodes_iter() {
-            neighbors.insert(
-                node.get_id(),
-                FxHashSet::<NodeId>::from_iter(node.get_edges().map(|edge| edge.get_neighbor_id())),
-            );
+            node_idx[node] = i;
         }
 
         for i in 0..nodes.len() {
-            let node_id = nodes[i];
-            let node_nbrs: Vec<NodeId> = neighbors.get(&node_id).unwrap().iter().cloned().collect();
-            for nbr_id in node_nbrs {
-                let nbr_coreness = coreness[&nbr_id];
-                if nbr_coreness > coreness[&node_id] {
-                    neighbors.get_mut(&nbr_id).unwrap().remove(&node_id);
-                    let nbr_idx = node_idx[&nbr_id];
+            let node = nodes[i];
+            // Neighbors processed earlier never have a higher coreness, so they
+            // are skipped here without having to be removed.
+            for nbr in neighbors(node) {
+                let nbr_coreness = coreness[nbr];
+                if nbr_coreness > coreness[node] {
+                    // move nbr to the start of its bin, then shrink the bin.
+                    let nbr_idx = node_idx[nbr];
                     let nbr_bin_start = bin_starts[nbr_coreness];
-
-                    let nbr_idx_ptr = node_idx.get_mut(&nbr_id).unwrap() as *mut usize;
-                    let bin_start_node_idx_ptr =
-                        node_idx.get_mut(&nodes[nbr_bin_start]).unwrap() as *mut usize;
-                    unsafe {
-                        std::ptr::swap(nbr_idx_ptr, bin_start_node_idx_ptr);
-                    }
+                    let bin_start_node = nodes[nbr_bin_start];
                     nodes.swap(nbr_idx, nbr_bin_start);
+                    node_idx[nbr] = nbr_bin_start;
+                    node_idx[bin_start_node] = nbr_idx;
 
                     bin_starts[nbr_coreness] += 1;
-                    *coreness.entry(nbr_id).or_default() -= 1;
+                    coreness[nbr] -= 1;
                 }
             }
         }
@@ -276,7 +279,7 @@ pub trait FractionalCoreness : GraphBase<NodeType=WeightedNode>
         // of that node from all edges where we haven't removed the other ends yet.
         // Use PriorityQueue instead of BinaryHeap because the workload uses change priority.
         // [TODO:Perf] Switch to hashbrown. Benchmark performance.
-        let mut pq = PriorityQueue::with_capacity(self.get_nodes_iter().len());
+        let mut pq = PriorityQueue::with_capacity(self.count_nodes());
 
         // Initially the priority of the of each node is the node weight (the total edge weight
         // of each incident edge.)
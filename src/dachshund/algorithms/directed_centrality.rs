@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::convergence::{iterate, IterativeResult};
+use crate::dachshund::algorithms::sparse_matrix::{LinearOperator, SparseGraphMatrix};
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{DirectedNodeBase, NodeEdgeBase};
+use std::collections::HashMap;
+
+/// HITS scores: good hubs point to good authorities, and good authorities are
+/// pointed to by good hubs.
+#[derive(Clone, Debug)]
+pub struct HubsAndAuthorities {
+    pub hubs: HashMap<NodeId, f64>,
+    pub authorities: HashMap<NodeId, f64>,
+}
+
+/// Centrality measures which take the direction of edges into account. All of
+/// them are computed by iterating sparse matrix-vector products, and stop once
+/// an iteration changes the scores by at most `eps` (in L1 norm), or after
+/// `max_iter` iterations, whichever comes first.
+pub trait DirectedCentrality: GraphBase
+where
+    Self: GraphBase,
+    <Self as GraphBase>::NodeType: DirectedNodeBase,
+{
+    /// the adjacency matrix A (A[i][j] = 1 for an edge i -> j), and its transpose.
+    fn get_directed_adjacency_matrices(
+        &self,
+        index: &DenseIndex,
+    ) -> (SparseGraphMatrix, SparseGraphMatrix) {
+        let get_rows = |in_edges: bool| {
+            index
+                .ids()
+                .iter()
+                .map(|id| {
+                    let node = self.get_node(*id);
+                    let neighbors = if in_edges {
+                        node.get_in_neighbors()
+                    } else {
+                        node.get_out_neighbors()
+                    };
+                    neighbors
+                        .map(|e| (index.get_index(e.get_neighbor_id()), 1.0))
+                        .collect()
+                })
+                .collect()
+        };
+        (
+            SparseGraphMatrix::from_rows(get_rows(false)),
+            SparseGraphMatrix::from_rows(get_rows(true)),
+        )
+    }
+
+    /// Katz centrality: x = alpha A^T x + beta, i.e. the number of walks ending at
+    /// each node, those of length k discounted by alpha^k. The iteration only
+    /// converges for alpha below 1 / (the largest eigenvalue of A).
+    fn get_katz_centrality(
+        &self,
+        alpha: f64,
+        beta: f64,
+        eps: f64,
+        max_iter: usize,
+    ) -> IterativeResult<HashMap<NodeId, f64>> {
+        let index = self.get_dense_index();
+        let (_, transpose) = self.get_directed_adjacency_matrices(&index);
+        let n = index.len();
+        let mut x: Vec<f64> = vec![beta; n];
+        let mut y: Vec<f64> = vec![0.0; n];
+        let convergence = iterate(eps, max_iter, || {
+            transpose.apply(&x, &mut y);
+            y.iter_mut().for_each(|v| *v = alpha * *v + beta);
+            let residual = l1_distance(&x, &y);
+            std::mem::swap(&mut x, &mut y);
+            residual
+        });
+        IterativeResult::new(index.to_node_map(x), convergence)
+    }
+
+    /// eigenvector centrality from incoming edges: a node is central if central
+    /// nodes point to it. Scaled so that the most central node has centrality 1.
+    fn get_in_eigenvector_centrality(
+        &self,
+        eps: f64,
+        max_iter: usize,
+    ) -> IterativeResult<HashMap<NodeId, f64>> {
+        let index = self.get_dense_index();
+        let (_, transpose) = self.get_directed_adjacency_matrices(&index);
+        get_leading_eigenvector(&index, &transpose, eps, max_iter)
+    }
+    /// eigenvector centrality from outgoing edges: a node is central if it points
+    /// to central nodes. Scaled so that the most central node has centrality 1.
+    fn get_out_eigenvector_centrality(
+        &self,
+        eps: f64,
+        max_iter: usize,
+    ) -> IterativeResult<HashMap<NodeId, f64>> {
+        let index = self.get_dense_index();
+        let (adjacency, _) = self.get_directed_adjacency_matrices(&index);
+        get_leading_eigenvector(&index, &adjacency, eps, max_iter)
+    }
+
+    /// Kleinberg's hubs and authorities (HITS): authorities a = A^T h and hubs
+    /// h = A a, both scaled so that their largest entry is 1.
+    fn get_hits_scores(&self, eps: f64, max_iter: usize) -> IterativeResult<HubsAndAuthorities> {
+        let index = self.get_dense_index();
+        let (adjacency, transpose) = self.get_directed_adjacency_matrices(&index);
+        let n = index.len();
+        let mut hubs: Vec<f64> = vec![1.0; n];
+        let mut authorities: Vec<f64> = vec![1.0; n];
+        let mut next_hubs: Vec<f64> = vec![0.0; n];
+        let mut next_authorities: Vec<f64> = vec![0.0; n];
+        let convergence = iterate(eps, max_iter, || {
+            transpose.apply(&hubs, &mut next_authorities);
+            scale_to_max(&mut next_authorities);
+            adjacency.apply(&next_authorities, &mut next_hubs);
+            scale_to_max(&mut next_hubs);
+            let residual =
+                l1_distance(&hubs, &next_hubs) + l1_distance(&authorities, &next_authorities);
+            std::mem::swap(&mut hubs, &mut next_hubs);
+            std::mem::swap(&mut authorities, &mut next_authorities);
+            residual
+        });
+        IterativeResult::new(
+            HubsAndAuthorities {
+                hubs: index.to_node_map(hubs),
+                authorities: index.to_node_map(authorities),
+            },
+            convergence,
+        )
+    }
+}
+
+/// power iteration on I + M, which has the same leading eigenvector as M but,
+/// unlike M, does not oscillate on periodic (e.g. bipartite) graphs.
+fn get_leading_eigenvector(
+    index: &DenseIndex,
+    matrix: &SparseGraphMatrix,
+    eps: f64,
+    max_iter: usize,
+) -> IterativeResult<HashMap<NodeId, f64>> {
+    let n = index.len();
+    let mut x: Vec<f64> = vec![1.0; n];
+    let mut y: Vec<f64> = vec![0.0; n];
+    let convergence = iterate(eps, max_iter, || {
+        matrix.apply(&x, &mut y);
+        y.iter_mut().zip(&x).for_each(|(v, x_i)| *v += x_i);
+        scale_to_max(&mut y);
+        let residual = l1_distance(&x, &y);
+        std::mem::swap(&mut x, &mut y);
+        residual
+    });
+    IterativeResult::new(index.to_node_map(x), convergence)
+}
+
+/// divides by the largest entry, unless all entries are 0.
+fn scale_to_max(x: &mut [f64]) {
+    let m = x.iter().cloned().fold(0.0, f64::max);
+    if m > 0.0 {
+        x.iter_mut().for_each(|v| *v /= m);
+    }
+}
+
+fn l1_distance(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| (a - b).abs()).sum()
+}
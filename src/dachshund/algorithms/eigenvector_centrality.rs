@@ -5,6 +5,9 @@
  * LICENSE file in the root directory of this source tree.
  */
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
+use crate::dachshund::algorithms::convergence::{iterate, IterativeResult};
+use crate::dachshund::algorithms::lanczos::{LanczosSolver, Spectrum, SPARSE_SOLVER_MIN_NODES};
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use nalgebra::DMatrix;
@@ -13,26 +16,79 @@ use std::collections::HashMap;
 type GraphMatrix = DMatrix<f64>;
 
 pub trait EigenvectorCentrality: GraphBase + AdjacencyMatrix {
-    fn get_eigenvector_centrality(&self, eps: f64, max_iter: usize) -> HashMap<NodeId, f64> {
+    /// centralities are scaled so that the most central node has centrality 1. Large
+    /// graphs use a sparse adjacency matrix and the Lanczos solver. Graphs without
+    /// edges have no meaningful eigenvector centrality, and give an error.
+    fn get_eigenvector_centrality(
+        &self,
+        eps: f64,
+        max_iter: usize,
+    ) -> CLQResult<IterativeResult<HashMap<NodeId, f64>>> {
+        if self.count_nodes() >= SPARSE_SOLVER_MIN_NODES {
+            return self.get_eigenvector_centrality_sparse(eps, max_iter);
+        }
+        self.get_eigenvector_centrality_dense(eps, max_iter)
+    }
+    /// power iteration, stopping once an iteration changes the centralities by
+    /// at most `eps` (in L1 norm).
+    fn get_eigenvector_centrality_dense(
+        &self,
+        eps: f64,
+        max_iter: usize,
+    ) -> CLQResult<IterativeResult<HashMap<NodeId, f64>>> {
+        check_has_edges(self)?;
         let (adj_mat, node_ids) = self.get_adjacency_matrix();
         // Power iteration adaptation from
         // https://www.sci.unich.it/~francesc/teaching/network/eigenvector.html
 
         let n = node_ids.len();
-        let mut x0: GraphMatrix = GraphMatrix::zeros(1, n);
-        let mut x1: GraphMatrix = GraphMatrix::repeat(1, n, 1.0 / n as f64);
-        let mut iter: usize = 0;
-        while (&x0 - &x1).abs().sum() > eps && iter < max_iter {
-            x0 = x1;
-            x1 = &x0 * &adj_mat;
-            let m = x1.max();
-            x1 /= m;
-            iter += 1;
-        }
+        let mut x: GraphMatrix = GraphMatrix::repeat(1, n, 1.0 / n as f64);
+        let convergence = iterate(eps, max_iter, || {
+            let mut next = &x * &adj_mat;
+            let m = next.max();
+            next /= m;
+            let residual = (&next - &x).abs().sum();
+            x = next;
+            residual
+        });
         let mut ev: HashMap<NodeId, f64> = HashMap::new();
         for i in 0..n {
-            ev.insert(node_ids[i], x1[i]);
+            ev.insert(node_ids[i], x[i]);
         }
-        ev
+        Ok(IterativeResult::new(ev, convergence))
+    }
+    /// the leading eigenvector of the adjacency matrix, by the Lanczos solver.
+    /// As for power iteration, `max_iter` bounds the number of matrix-vector
+    /// products (Lanczos steps), and `eps` the (relative) eigenvector residual.
+    fn get_eigenvector_centrality_sparse(
+        &self,
+        eps: f64,
+        max_iter: usize,
+    ) -> CLQResult<IterativeResult<HashMap<NodeId, f64>>> {
+        check_has_edges(self)?;
+        let (adj_mat, node_ids) = self.get_sparse_adjacency_matrix();
+        let eigen = LanczosSolver::new(eps).with_max_iter(max_iter).solve(
+            &adj_mat,
+            1,
+            Spectrum::Largest,
+            &[],
+        );
+        let vector = eigen.vectors.into_iter().next().unwrap();
+        let m = vector.iter().cloned().fold(f64::MIN, f64::max);
+        let ev = node_ids
+            .into_iter()
+            .zip(vector)
+            .map(|(id, x)| (id, x / m))
+            .collect();
+        Ok(IterativeResult::new(ev, eigen.convergence))
+    }
+}
+
+fn check_has_edges<T: GraphBase>(graph: &T) -> CLQResult<()> {
+    if graph.count_edges() == 0 {
+        return Err(CLQError::from(
+            "Eigenvector centrality is undefined for graphs without edges",
+        ));
     }
+    Ok(())
 }
@@ -0,0 +1,187 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::betweenness::{accumulate_edge_betweenness, Betweenness};
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
+use crate::dachshund::id_types::NodeId;
+use std::collections::{HashMap, VecDeque};
+
+/// One level of the Girvan–Newman dendrogram: the communities (connected
+/// components) left once `removed_edges` have been cut, and their modularity with
+/// respect to the original graph.
+#[derive(Clone, Debug)]
+pub struct GirvanNewmanLevel {
+    pub communities: Vec<Vec<NodeId>>,
+    pub modularity: f64,
+    /// edges cut since the previous level, in the order they were removed.
+    pub removed_edges: Vec<(NodeId, NodeId)>,
+}
+
+/// All levels of the dendrogram, from the connected components of the graph down
+/// to single nodes, each splitting one community of the previous level in two.
+#[derive(Clone, Debug)]
+pub struct GirvanNewmanDendrogram {
+    pub levels: Vec<GirvanNewmanLevel>,
+    /// the level of highest modularity (the first one, in case of ties).
+    pub best_level: usize,
+}
+impl GirvanNewmanDendrogram {
+    pub fn get_best_communities(&self) -> &Vec<Vec<NodeId>> {
+        &self.levels[self.best_level].communities
+    }
+    pub fn get_best_modularity(&self) -> f64 {
+        self.levels[self.best_level].modularity
+    }
+}
+
+pub trait GirvanNewman: GraphBase + Betweenness {
+    /// Girvan & Newman, Community structure in social and biological networks:
+    /// repeatedly removes the edge of highest betweenness, recomputing edge
+    /// betweenness in the component it was removed from. Communities are sorted
+    /// lists of node ids, ordered by their smallest id. Ties between edges are
+    /// broken in favor of the edge with the smallest ids.
+    fn get_girvan_newman_communities(&self) -> CLQResult<GirvanNewmanDendrogram> {
+        if self.count_nodes() == 0 {
+            return Err("Graph is empty".into());
+        }
+        let index = self.get_dense_index();
+        let mut neighbors = self.get_dense_adjacency(&index);
+        let degrees: Vec<usize> = neighbors.iter().map(Vec::len).collect();
+        let original_edges: Vec<(usize, usize)> = get_edges(&neighbors);
+
+        let mut betweenness: HashMap<(usize, usize), f64> = HashMap::new();
+        accumulate_edge_betweenness(&neighbors, 0..index.len(), &mut betweenness);
+
+        let mut labels = get_component_labels(&neighbors);
+        let mut levels: Vec<GirvanNewmanLevel> = vec![GirvanNewmanLevel {
+            communities: to_communities(&labels, &index),
+            modularity: get_modularity(&labels, &degrees, &original_edges),
+            removed_edges: Vec::new(),
+        }];
+        let mut removed_edges: Vec<(NodeId, NodeId)> = Vec::new();
+        while let Some((i, j)) = get_max_edge(&betweenness) {
+            neighbors[i].retain(|k| *k != j);
+            neighbors[j].retain(|k| *k != i);
+            removed_edges.push((index.get_id(i), index.get_id(j)));
+
+            // betweenness only changes within the component(s) of i and j.
+            let mut affected = get_reachable(&neighbors, i);
+            if !affected.contains(&j) {
+                affected.extend(get_reachable(&neighbors, j));
+                labels = get_component_labels(&neighbors);
+                levels.push(GirvanNewmanLevel {
+                    communities: to_communities(&labels, &index),
+                    modularity: get_modularity(&labels, &degrees, &original_edges),
+                    removed_edges: std::mem::take(&mut removed_edges),
+                });
+            }
+            for v in &affected {
+                for w in &neighbors[*v] {
+                    betweenness.remove(&(*v.min(w), *v.max(w)));
+                }
+            }
+            betweenness.remove(&(i, j));
+            accumulate_edge_betweenness(&neighbors, affected, &mut betweenness);
+        }
+
+        let mut best_level: usize = 0;
+        for (level, item) in levels.iter().enumerate() {
+            if item.modularity > levels[best_level].modularity {
+                best_level = level;
+            }
+        }
+        Ok(GirvanNewmanDendrogram { levels, best_level })
+    }
+}
+
+/// edges (i, j), i < j, of dense adjacency lists.
+fn get_edges(neighbors: &[Vec<usize>]) -> Vec<(usize, usize)> {
+    neighbors
+        .iter()
+        .enumerate()
+        .flat_map(|(i, adjacent)| {
+            adjacent
+                .iter()
+                .filter(move |j| i < **j)
+                .map(move |j| (i, *j))
+        })
+        .collect()
+}
+
+fn get_max_edge(betweenness: &HashMap<(usize, usize), f64>) -> Option<(usize, usize)> {
+    betweenness
+        .iter()
+        .max_by(|(e1, b1), (e2, b2)| b1.total_cmp(b2).then(e2.cmp(e1)))
+        .map(|(edge, _)| *edge)
+}
+
+fn get_reachable(neighbors: &[Vec<usize>], source: usize) -> Vec<usize> {
+    let mut seen: Vec<bool> = vec![false; neighbors.len()];
+    let mut reachable: Vec<usize> = vec![source];
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    seen[source] = true;
+    queue.push_back(source);
+    while let Some(v) = queue.pop_front() {
+        for &w in &neighbors[v] {
+            if !seen[w] {
+                seen[w] = true;
+                reachable.push(w);
+                queue.push_back(w);
+            }
+        }
+    }
+    reachable
+}
+
+/// the connected component of each node, numbered by their smallest node.
+fn get_component_labels(neighbors: &[Vec<usize>]) -> Vec<usize> {
+    let mut labels: Vec<usize> = vec![usize::MAX; neighbors.len()];
+    let mut num_components: usize = 0;
+    for v in 0..neighbors.len() {
+        if labels[v] == usize::MAX {
+            for w in get_reachable(neighbors, v) {
+                labels[w] = num_components;
+            }
+            num_components += 1;
+        }
+    }
+    labels
+}
+
+fn to_communities(labels: &[usize], index: &DenseIndex) -> Vec<Vec<NodeId>> {
+    let num_communities = labels.iter().max().map_or(0, |x| x + 1);
+    let mut communities: Vec<Vec<NodeId>> = vec![Vec::new(); num_communities];
+    for (i, label) in labels.iter().enumerate() {
+        communities[*label].push(index.get_id(i));
+    }
+    communities
+}
+
+/// Newman's modularity: the fraction of edges within communities, minus its
+/// expected value were edges rewired at random, keeping degrees.
+fn get_modularity(labels: &[usize], degrees: &[usize], edges: &[(usize, usize)]) -> f64 {
+    let num_edges = edges.len() as f64;
+    if num_edges == 0.0 {
+        return 0.0;
+    }
+    let num_communities = labels.iter().max().map_or(0, |x| x + 1);
+    let mut internal_edges: Vec<f64> = vec![0.0; num_communities];
+    let mut total_degrees: Vec<f64> = vec![0.0; num_communities];
+    for (i, j) in edges {
+        if labels[*i] == labels[*j] {
+            internal_edges[labels[*i]] += 1.0;
+        }
+    }
+    for (label, degree) in labels.iter().zip(degrees) {
+        total_degrees[*label] += *degree as f64;
+    }
+    internal_edges
+        .iter()
+        .zip(total_degrees)
+        .map(|(e, d)| e / num_edges - (d / (2.0 * num_edges)).powi(2))
+        .sum()
+}
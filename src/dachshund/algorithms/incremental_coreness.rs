@@ -0,0 +1,181 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use std::collections::{HashMap, HashSet};
+
+/// Keeps the coreness values of a `SimpleUndirectedGraph` up to date as edges
+/// are inserted and removed, rather than recomputing them from scratch.
+///
+/// Uses the traversal ("subcore") algorithm of Sariyuce et al., Streaming
+/// Algorithms for k-core Decomposition: https://arxiv.org/abs/1209.1327. An
+/// edge update changes core numbers by at most one, and only for nodes with
+/// the lower endpoint's core number K which are connected to that endpoint
+/// through other nodes of core number K.
+pub struct IncrementalCoreness {
+    graph: SimpleUndirectedGraph,
+    coreness: HashMap<NodeId, usize>,
+}
+impl IncrementalCoreness {
+    pub fn new(graph: SimpleUndirectedGraph) -> Self {
+        let coreness = graph.get_coreness_values();
+        Self { graph, coreness }
+    }
+    pub fn get_graph(&self) -> &SimpleUndirectedGraph {
+        &self.graph
+    }
+    pub fn get_coreness_values(&self) -> &HashMap<NodeId, usize> {
+        &self.coreness
+    }
+    pub fn get_coreness(&self, node_id: NodeId) -> usize {
+        self.coreness[&node_id]
+    }
+    pub fn into_graph(self) -> SimpleUndirectedGraph {
+        self.graph
+    }
+
+    /// inserts an edge, adding any missing endpoint. Returns the nodes whose
+    /// core number changed (including new nodes). Self-loops are ignored.
+    pub fn insert_edge(&mut self, id1: NodeId, id2: NodeId) -> HashSet<NodeId> {
+        let mut changed: HashSet<NodeId> = HashSet::new();
+        if id1 == id2 {
+            return changed;
+        }
+        for id in &[id1, id2] {
+            if self.graph.add_node(*id) {
+                self.coreness.insert(*id, 0);
+                changed.insert(*id);
+            }
+        }
+        if !self.graph.add_edge(id1, id2) {
+            return changed;
+        }
+        let k = std::cmp::min(self.coreness[&id1], self.coreness[&id2]);
+        let subcore = self.get_subcore(&[id1, id2], k);
+        let mut support = self.get_support(&subcore, k);
+
+        // Evict nodes which cannot be part of a (k+1)-core; the rest move up.
+        let mut evicted: HashSet<NodeId> = HashSet::new();
+        let mut stack: Vec<NodeId> = subcore
+            .iter()
+            .filter(|id| support[id] <= k)
+            .cloned()
+            .collect();
+        while let Some(id) = stack.pop() {
+            if !evicted.insert(id) {
+                continue;
+            }
+            for nid in &self.graph.nodes[&id].neighbors {
+                if subcore.contains(nid) && !evicted.contains(nid) {
+                    let nid_support = support.get_mut(nid).unwrap();
+                    *nid_support -= 1;
+                    if *nid_support == k {
+                        stack.push(*nid);
+                    }
+                }
+            }
+        }
+        for id in subcore.difference(&evicted) {
+            *self.coreness.get_mut(id).unwrap() += 1;
+            changed.insert(*id);
+        }
+        changed
+    }
+
+    /// removes an edge, keeping its endpoints. Returns the nodes whose core
+    /// number changed.
+    pub fn remove_edge(&mut self, id1: NodeId, id2: NodeId) -> HashSet<NodeId> {
+        let mut changed: HashSet<NodeId> = HashSet::new();
+        if id1 == id2 || !self.graph.remove_edge(id1, id2) {
+            return changed;
+        }
+        let k = std::cmp::min(self.coreness[&id1], self.coreness[&id2]);
+        let subcore = self.get_subcore(&[id1, id2], k);
+        let mut support = self.get_support(&subcore, k);
+
+        // Nodes left with fewer than k neighbors in the k-core drop out of it.
+        let mut stack: Vec<NodeId> = subcore
+            .iter()
+            .filter(|id| support[id] < k)
+            .cloned()
+            .collect();
+        while let Some(id) = stack.pop() {
+            if !changed.insert(id) {
+                continue;
+            }
+            for nid in &self.graph.nodes[&id].neighbors {
+                if subcore.contains(nid) && !changed.contains(nid) {
+                    let nid_support = support.get_mut(nid).unwrap();
+                    *nid_support -= 1;
+                    if *nid_support + 1 == k {
+                        stack.push(*nid);
+                    }
+                }
+            }
+        }
+        for id in &changed {
+            *self.coreness.get_mut(id).unwrap() -= 1;
+        }
+        changed
+    }
+
+    /// removes a node along with its edges. Returns the remaining nodes whose
+    /// core number changed.
+    pub fn remove_node(&mut self, id: NodeId) -> HashSet<NodeId> {
+        let mut changed: HashSet<NodeId> = HashSet::new();
+        if !self.graph.has_node(id) {
+            return changed;
+        }
+        let neighbors: Vec<NodeId> = self.graph.nodes[&id].neighbors.iter().cloned().collect();
+        for nid in neighbors {
+            changed.extend(self.remove_edge(id, nid));
+        }
+        self.graph.remove_node(id);
+        self.coreness.remove(&id);
+        changed.remove(&id);
+        changed
+    }
+
+    /// nodes with core number k reachable from the roots with core number k
+    /// through nodes with core number k.
+    fn get_subcore(&self, roots: &[NodeId], k: usize) -> HashSet<NodeId> {
+        let mut subcore: HashSet<NodeId> = HashSet::new();
+        let mut stack: Vec<NodeId> = roots
+            .iter()
+            .filter(|id| self.coreness[id] == k)
+            .cloned()
+            .collect();
+        while let Some(id) = stack.pop() {
+            if !subcore.insert(id) {
+                continue;
+            }
+            for nid in &self.graph.nodes[&id].neighbors {
+                if self.coreness[nid] == k && !subcore.contains(nid) {
+                    stack.push(*nid);
+                }
+            }
+        }
+        subcore
+    }
+
+    /// number of neighbors in the k-core, for each node of the subcore.
+    fn get_support(&self, subcore: &HashSet<NodeId>, k: usize) -> HashMap<NodeId, usize> {
+        subcore
+            .iter()
+            .map(|id| {
+                let count = self.graph.nodes[id]
+                    .neighbors
+                    .iter()
+                    .filter(|nid| self.coreness[nid] >= k)
+                    .count();
+                (*id, count)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,406 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate rand;
+
+use crate::dachshund::algorithms::convergence::Convergence;
+use crate::dachshund::algorithms::sparse_matrix::{LinearOperator, SparseGraphMatrix};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// graphs with at least this many nodes use sparse matrices and the Lanczos
+/// solver for spectral measures, rather than dense matrices.
+pub const SPARSE_SOLVER_MIN_NODES: usize = 500;
+
+/// Which end of the spectrum to compute eigenpairs for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Spectrum {
+    Smallest,
+    Largest,
+}
+
+/// Eigenvalues, from the requested end of the spectrum inwards, along with
+/// their unit eigenvectors. For the Lanczos solver, `convergence` counts the
+/// Lanczos steps (matrix-vector products) taken, and its residual is the largest
+/// relative residual |Av - λv| / max(|λ|, 1) of the returned pairs.
+#[derive(Clone, Debug)]
+pub struct EigenPairs {
+    pub values: Vec<f64>,
+    pub vectors: Vec<Vec<f64>>,
+    pub convergence: Convergence,
+}
+
+/// Computes a few extreme eigenpairs of a symmetric matrix, accessing it only
+/// through matrix-vector products.
+///
+/// This is the implicitly restarted Lanczos method (as in ARPACK) with full
+/// reorthogonalization: once the Krylov basis reaches `max_dim` vectors, it is
+/// compressed, by implicitly shifted QR steps on the tridiagonal projection
+/// with the unwanted Ritz values as shifts, down to a basis that keeps the
+/// wanted Ritz pairs, and grown again. Should the Krylov space become invariant
+/// early on, the iteration continues from a fresh random vector orthogonal to
+/// it. Memory use is O(n * max_dim).
+pub struct LanczosSolver {
+    pub tolerance: f64,
+    /// size of the Krylov basis at which the iteration restarts. Defaults to
+    /// min(n, 2 * num_pairs + 100).
+    pub max_dim: Option<usize>,
+    /// number of Lanczos steps after which the solver gives up, returning its
+    /// current Ritz pairs as not converged.
+    pub max_iter: usize,
+}
+impl LanczosSolver {
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            tolerance,
+            max_dim: None,
+            max_iter: 10000,
+        }
+    }
+    pub fn with_max_dim(mut self, max_dim: usize) -> Self {
+        self.max_dim = Some(max_dim);
+        self
+    }
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// computes `num_pairs` eigenpairs from the given end of the spectrum,
+    /// restricted to the orthogonal complement of `deflate` (e.g. the constant
+    /// vector, the known null space of a connected graph's Laplacian). Start
+    /// vectors are drawn from a fixed seed; see `solve_with_rng`.
+    pub fn solve<A: LinearOperator + ?Sized>(
+        &self,
+        op: &A,
+        num_pairs: usize,
+        which: Spectrum,
+        deflate: &[Vec<f64>],
+    ) -> EigenPairs {
+        self.solve_with_rng(op, num_pairs, which, deflate, &mut StdRng::seed_from_u64(0))
+    }
+    /// same as `solve`, with start vectors drawn from `rng`.
+    pub fn solve_with_rng<A: LinearOperator + ?Sized, R: Rng>(
+        &self,
+        op: &A,
+        num_pairs: usize,
+        which: Spectrum,
+        deflate: &[Vec<f64>],
+        rng: &mut R,
+    ) -> EigenPairs {
+        let n = op.dim();
+        let deflate = orthonormalize(deflate);
+        let space_dim = n.saturating_sub(deflate.len());
+        let num_pairs = std::cmp::min(num_pairs, space_dim);
+        if num_pairs == 0 {
+            return EigenPairs {
+                values: Vec::new(),
+                vectors: Vec::new(),
+                convergence: Convergence::exact(),
+            };
+        }
+        let max_dim =
+            std::cmp::min(space_dim, self.max_dim.unwrap_or(2 * num_pairs + 100)).max(num_pairs);
+        // Ritz pairs kept on restarts: the wanted ones, and some more to speed up
+        // convergence.
+        let num_kept = num_pairs + (max_dim - num_pairs) / 2;
+        let mut basis: Vec<Vec<f64>> = Vec::new();
+        let mut alphas: Vec<f64> = Vec::new();
+        // betas[j] couples basis vectors j and j + 1; 0 where the iteration restarted.
+        let mut betas: Vec<f64> = Vec::new();
+        let mut residual: Vec<f64> = vec![0.0; n];
+        let mut scale: f64 = 1.0;
+        let mut exhausted = false;
+        let mut iterations: usize = 0;
+        let mut w: Vec<f64> = vec![0.0; n];
+        loop {
+            while basis.len() < max_dim && iterations < self.max_iter {
+                let beta = norm(&residual);
+                let q: Vec<f64> = if !basis.is_empty() && beta > 1e-10 * scale {
+                    betas.push(beta);
+                    residual.iter().map(|x| x / beta).collect()
+                } else {
+                    // start (or restart, if the space found so far is invariant).
+                    let mut start: Vec<f64> = (0..n).map(|_| rng.gen_range(-1.0, 1.0)).collect();
+                    for _ in 0..2 {
+                        project_out(&mut start, &deflate);
+                        project_out(&mut start, &basis);
+                    }
+                    let start_norm = norm(&start);
+                    if start_norm <= 1e-10 {
+                        exhausted = true;
+                        break;
+                    }
+                    if !basis.is_empty() {
+                        betas.push(0.0);
+                    }
+                    start.iter().map(|x| x / start_norm).collect()
+                };
+                op.apply(&q, &mut w);
+                iterations += 1;
+                let alpha = dot(&w, &q);
+                scale = scale.max(alpha.abs());
+                basis.push(q);
+                alphas.push(alpha);
+                for _ in 0..2 {
+                    project_out(&mut w, &deflate);
+                    project_out(&mut w, &basis);
+                }
+                residual.copy_from_slice(&w);
+            }
+            let size = basis.len();
+            // a basis spanning the whole space is invariant, so its Ritz pairs are exact.
+            exhausted = exhausted || size == space_dim;
+            let (ritz_values, ritz_vectors) = get_tridiagonal_eigenpairs(&alphas, &betas);
+            let mut order: Vec<usize> = (0..size).collect();
+            order.sort_by(|a, b| {
+                let (x, y) = (ritz_values[*a], ritz_values[*b]);
+                match which {
+                    Spectrum::Smallest => x.total_cmp(&y),
+                    Spectrum::Largest => y.total_cmp(&x),
+                }
+            });
+            let wanted = &order[..std::cmp::min(num_pairs, size)];
+            let next_beta = norm(&residual);
+            let max_residual = wanted
+                .iter()
+                .map(|i| {
+                    (next_beta * ritz_vectors[size - 1][*i]).abs() / ritz_values[*i].abs().max(1.0)
+                })
+                .fold(0.0, f64::max);
+            let converged = max_residual <= self.tolerance;
+            if converged || exhausted || iterations >= self.max_iter || size <= num_kept {
+                let values = wanted.iter().map(|i| ritz_values[*i]).collect();
+                let vectors = wanted
+                    .iter()
+                    .map(|i| normalize_sign(combine(&basis, |j| ritz_vectors[j][*i])))
+                    .collect();
+                let convergence = Convergence {
+                    converged: converged || exhausted,
+                    iterations,
+                    residual: max_residual,
+                };
+                return EigenPairs {
+                    values,
+                    vectors,
+                    convergence,
+                };
+            }
+            // implicit restart: QR steps shifted by the unwanted Ritz values turn the
+            // first num_kept basis vectors into a Lanczos basis (with the same
+            // residual structure) for the space of the wanted Ritz vectors.
+            let mut t: Vec<Vec<f64>> = vec![vec![0.0; size]; size];
+            let mut q: Vec<Vec<f64>> = vec![vec![0.0; size]; size];
+            for j in 0..size {
+                t[j][j] = alphas[j];
+                q[j][j] = 1.0;
+                if j + 1 < size {
+                    t[j][j + 1] = betas[j];
+                    t[j + 1][j] = betas[j];
+                }
+            }
+            for i in &order[num_kept..] {
+                apply_shifted_qr_step(&mut t, &mut q, ritz_values[*i]);
+            }
+            let next_vector = combine(&basis, |j| q[j][num_kept]);
+            let (beta_kept, sigma) = (t[num_kept][num_kept - 1], q[size - 1][num_kept - 1]);
+            for (r, v) in residual.iter_mut().zip(next_vector) {
+                *r = beta_kept * v + sigma * *r;
+            }
+            basis = (0..num_kept)
+                .map(|k| combine(&basis, |j| q[j][k]))
+                .collect();
+            alphas = (0..num_kept).map(|j| t[j][j]).collect();
+            betas = (0..num_kept - 1).map(|j| t[j + 1][j]).collect();
+        }
+    }
+}
+
+/// eigenpairs of a symmetric matrix, using a dense eigendecomposition for
+/// matrices smaller than SPARSE_SOLVER_MIN_NODES and the Lanczos solver (drawing
+/// its start vectors from `rng`) otherwise.
+pub fn get_extreme_eigenpairs<R: Rng>(
+    matrix: &SparseGraphMatrix,
+    num_pairs: usize,
+    which: Spectrum,
+    tolerance: f64,
+    rng: &mut R,
+) -> EigenPairs {
+    if matrix.dim() >= SPARSE_SOLVER_MIN_NODES {
+        return LanczosSolver::new(tolerance).solve_with_rng(matrix, num_pairs, which, &[], rng);
+    }
+    get_dense_eigenpairs(matrix, num_pairs, which)
+}
+
+/// eigenpairs of a symmetric matrix, by a dense eigendecomposition.
+pub fn get_dense_eigenpairs(
+    matrix: &SparseGraphMatrix,
+    num_pairs: usize,
+    which: Spectrum,
+) -> EigenPairs {
+    let eigen = matrix.to_dense().symmetric_eigen();
+    let mut order: Vec<usize> = (0..matrix.dim()).collect();
+    order.sort_by(|a, b| {
+        let (x, y) = (eigen.eigenvalues[*a], eigen.eigenvalues[*b]);
+        match which {
+            Spectrum::Smallest => x.total_cmp(&y),
+            Spectrum::Largest => y.total_cmp(&x),
+        }
+    });
+    order.truncate(num_pairs);
+    EigenPairs {
+        values: order.iter().map(|i| eigen.eigenvalues[*i]).collect(),
+        vectors: order
+            .iter()
+            .map(|i| normalize_sign(eigen.eigenvectors.column(*i).iter().cloned().collect()))
+            .collect(),
+        convergence: Convergence::exact(),
+    }
+}
+
+/// the linear combination of `vectors` with the given coefficients.
+fn combine<F: Fn(usize) -> f64>(vectors: &[Vec<f64>], coefficient: F) -> Vec<f64> {
+    let mut result = vec![0.0; vectors.first().map_or(0, |v| v.len())];
+    for (j, v) in vectors.iter().enumerate() {
+        let c = coefficient(j);
+        for (r, v_k) in result.iter_mut().zip(v) {
+            *r += c * v_k;
+        }
+    }
+    result
+}
+
+/// eigenvalues and eigenvectors (as columns) of the symmetric tridiagonal matrix
+/// with the given diagonal and off-diagonal, by the implicit QL method.
+fn get_tridiagonal_eigenpairs(diagonal: &[f64], off_diagonal: &[f64]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = diagonal.len();
+    let mut d: Vec<f64> = diagonal.to_vec();
+    // e[i] couples i and i + 1.
+    let mut e: Vec<f64> = off_diagonal.to_vec();
+    e.resize(n, 0.0);
+    let mut z: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for l in 0..n {
+        for _ in 0..100 {
+            // look for a negligible off-diagonal element to split the matrix at.
+            let mut m = l;
+            while m + 1 < n && e[m].abs() > f64::EPSILON * (d[m].abs() + d[m + 1].abs()) {
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+            let mut g = (d[l + 1] - d[l]) / (2.0 * e[l]);
+            let mut r = g.hypot(1.0);
+            g = d[m] - d[l] + e[l] / (g + r.copysign(g));
+            let (mut s, mut c, mut p) = (1.0, 1.0, 0.0);
+            let mut underflow = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == 0.0 {
+                    d[i + 1] -= p;
+                    e[m] = 0.0;
+                    underflow = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + 2.0 * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+                for row in z.iter_mut() {
+                    let f = row[i + 1];
+                    row[i + 1] = s * row[i] + c * f;
+                    row[i] = c * row[i] - s * f;
+                }
+            }
+            if !underflow {
+                d[l] -= p;
+                e[l] = g;
+                e[m] = 0.0;
+            }
+        }
+    }
+    (d, z)
+}
+
+/// one implicitly shifted QR step on the symmetric tridiagonal matrix `t`
+/// (stored densely), accumulating the orthogonal transformation into `q`.
+fn apply_shifted_qr_step(t: &mut [Vec<f64>], q: &mut [Vec<f64>], shift: f64) {
+    let m = t.len();
+    let (mut x, mut z) = (t[0][0] - shift, t[1][0]);
+    for k in 0..m - 1 {
+        let r = x.hypot(z);
+        let (c, s) = if r == 0.0 { (1.0, 0.0) } else { (x / r, z / r) };
+        // the rotation only touches the band around k, and the bulge it chases.
+        let (lo, hi) = (k.saturating_sub(1), std::cmp::min(m, k + 3));
+        let (upper, lower) = t.split_at_mut(k + 1);
+        for (a, b) in upper[k][lo..hi].iter_mut().zip(&mut lower[0][lo..hi]) {
+            let (x_a, x_b) = (*a, *b);
+            *a = c * x_a + s * x_b;
+            *b = c * x_b - s * x_a;
+        }
+        for row in t[lo..hi].iter_mut().chain(q.iter_mut()) {
+            let (a, b) = (row[k], row[k + 1]);
+            row[k] = c * a + s * b;
+            row[k + 1] = c * b - s * a;
+        }
+        if k + 2 < m {
+            x = t[k + 1][k];
+            z = t[k + 2][k];
+        }
+    }
+}
+
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| a * b).sum()
+}
+
+fn norm(x: &[f64]) -> f64 {
+    dot(x, x).sqrt()
+}
+
+/// removes the components of x along each of the (orthonormal) vectors.
+fn project_out(x: &mut [f64], vectors: &[Vec<f64>]) {
+    for v in vectors {
+        let projection = dot(x, v);
+        for (x_i, v_i) in x.iter_mut().zip(v) {
+            *x_i -= projection * v_i;
+        }
+    }
+}
+
+fn orthonormalize(vectors: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut basis: Vec<Vec<f64>> = Vec::new();
+    for v in vectors {
+        let mut v = v.clone();
+        project_out(&mut v, &basis);
+        let v_norm = norm(&v);
+        if v_norm > 1e-10 {
+            basis.push(v.iter().map(|x| x / v_norm).collect());
+        }
+    }
+    basis
+}
+
+/// unit-normalizes, flipping the sign so that the largest entry is positive.
+fn normalize_sign(mut vector: Vec<f64>) -> Vec<f64> {
+    let largest = vector
+        .iter()
+        .cloned()
+        .fold(0.0, |acc: f64, x| if x.abs() > acc.abs() { x } else { acc });
+    let factor = norm(&vector) * largest.signum();
+    if factor != 0.0 {
+        vector.iter_mut().for_each(|x| *x /= factor);
+    }
+    vector
+}
@@ -5,18 +5,20 @@
  * LICENSE file in the root directory of this source tree.
  */
 use crate::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
+use crate::dachshund::algorithms::sparse_matrix::SparseGraphMatrix;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::NodeBase;
 use nalgebra::{DMatrix, DVector};
 
 type GraphMatrix = DMatrix<f64>;
+/// Laplacians of a graph. Degrees and adjacencies are taken from AdjacencyMatrix,
+/// so graphs with edge weights get weighted Laplacians.
 pub trait Laplacian: GraphBase + AdjacencyMatrix {
     fn get_degree_matrix(&self) -> (GraphMatrix, Vec<NodeId>) {
         let node_ids = self.get_ordered_node_ids();
         let diag: Vec<f64> = node_ids
             .iter()
-            .map(|x| self.get_node(*x).degree() as f64)
+            .map(|x| self.get_weighted_degree(*x))
             .collect();
         (
             GraphMatrix::from_diagonal(&DVector::from_row_slice(&diag)),
@@ -28,4 +30,51 @@ pub trait Laplacian: GraphBase + AdjacencyMatrix {
         let adj_mat = self.get_adjacency_matrix_given_node_ids(&node_ids);
         (deg_mat - adj_mat, node_ids)
     }
+    /// same as get_laplacian_matrix, in sparse form.
+    fn get_sparse_laplacian_matrix(&self) -> (SparseGraphMatrix, Vec<NodeId>) {
+        let (adjacency, node_ids) = self.get_sparse_adjacency_matrix();
+        (adjacency.to_laplacian(), node_ids)
+    }
+    /// the symmetric normalized Laplacian D^-1/2 (D - A) D^-1/2, whose spectrum lies
+    /// in [0, 2] whatever the degrees. Rows of isolated nodes are all 0.
+    fn get_normalized_laplacian_matrix(&self) -> (GraphMatrix, Vec<NodeId>) {
+        let (laplacian, node_ids) = self.get_sparse_normalized_laplacian_matrix();
+        (laplacian.to_dense(), node_ids)
+    }
+    fn get_sparse_normalized_laplacian_matrix(&self) -> (SparseGraphMatrix, Vec<NodeId>) {
+        let (laplacian, node_ids) = self.get_sparse_laplacian_matrix();
+        let inv_sqrt_degrees: Vec<f64> = self
+            .get_inverse_degrees(&node_ids)
+            .into_iter()
+            .map(f64::sqrt)
+            .collect();
+        (
+            laplacian.scale(&inv_sqrt_degrees, &inv_sqrt_degrees),
+            node_ids,
+        )
+    }
+    /// the random-walk Laplacian D^-1 (D - A) = I - P, where P holds the
+    /// transition probabilities of a random walk. It is not symmetric, but shares
+    /// its eigenvalues with the symmetric normalized Laplacian. Rows of isolated
+    /// nodes are all 0.
+    fn get_random_walk_laplacian_matrix(&self) -> (GraphMatrix, Vec<NodeId>) {
+        let (laplacian, node_ids) = self.get_sparse_random_walk_laplacian_matrix();
+        (laplacian.to_dense(), node_ids)
+    }
+    fn get_sparse_random_walk_laplacian_matrix(&self) -> (SparseGraphMatrix, Vec<NodeId>) {
+        let (laplacian, node_ids) = self.get_sparse_laplacian_matrix();
+        let inv_degrees = self.get_inverse_degrees(&node_ids);
+        let ones = vec![1.0; node_ids.len()];
+        (laplacian.scale(&inv_degrees, &ones), node_ids)
+    }
+    /// 1 / (weighted) degree of each node, or 0 for isolated nodes.
+    fn get_inverse_degrees(&self, node_ids: &[NodeId]) -> Vec<f64> {
+        node_ids
+            .iter()
+            .map(|id| match self.get_weighted_degree(*id) {
+                d if d > 0.0 => 1.0 / d,
+                _ => 0.0,
+            })
+            .collect()
+    }
 }
@@ -12,9 +12,16 @@ pub mod clustering;
 pub mod cnm_communities;
 pub mod connected_components;
 pub mod connectivity;
+pub mod convergence;
 pub mod coreness;
+pub mod directed_centrality;
 pub mod eigenvector_centrality;
+pub mod girvan_newman;
+pub mod incremental_coreness;
+pub mod lanczos;
 pub mod laplacian;
 pub mod shortest_paths;
+pub mod sparse_matrix;
+pub mod spectral_clustering;
 pub mod transitivity;
 pub mod k_peaks;
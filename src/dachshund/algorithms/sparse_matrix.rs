@@ -0,0 +1,140 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use nalgebra::DMatrix;
+
+/// Anything which can multiply a vector: the only access iterative solvers
+/// need to a matrix.
+pub trait LinearOperator {
+    fn dim(&self) -> usize;
+    /// computes y = Ax. `y` has the same length as `x`.
+    fn apply(&self, x: &[f64], y: &mut [f64]);
+}
+
+/// A square matrix in compressed sparse row form. Matrices derived from graphs
+/// (adjacency, Laplacian) have about as many non-zero entries per row as the
+/// node has neighbors, so this takes O(n + m) memory rather than O(n^2).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGraphMatrix {
+    row_starts: Vec<usize>,
+    columns: Vec<usize>,
+    values: Vec<f64>,
+}
+impl SparseGraphMatrix {
+    /// builds a matrix from the (column, value) entries of each row. Entries
+    /// for the same column are summed up.
+    pub fn from_rows(rows: Vec<Vec<(usize, f64)>>) -> Self {
+        let mut row_starts: Vec<usize> = Vec::with_capacity(rows.len() + 1);
+        let mut columns: Vec<usize> = Vec::new();
+        let mut values: Vec<f64> = Vec::new();
+        row_starts.push(0);
+        for mut row in rows {
+            row.sort_by_key(|(column, _)| *column);
+            let row_start = columns.len();
+            for (column, value) in row {
+                if columns.len() > row_start && *columns.last().unwrap() == column {
+                    *values.last_mut().unwrap() += value;
+                } else {
+                    columns.push(column);
+                    values.push(value);
+                }
+            }
+            row_starts.push(columns.len());
+        }
+        Self {
+            row_starts,
+            columns,
+            values,
+        }
+    }
+    pub fn num_nonzeros(&self) -> usize {
+        self.values.len()
+    }
+    /// (column, value) entries of a row, by increasing column.
+    pub fn get_row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_starts[i]..self.row_starts[i + 1];
+        self.columns[range.clone()]
+            .iter()
+            .cloned()
+            .zip(self.values[range].iter().cloned())
+    }
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let range = self.row_starts[i]..self.row_starts[i + 1];
+        match self.columns[range.clone()].binary_search(&j) {
+            Ok(pos) => self.values[range.start + pos],
+            Err(_) => 0.0,
+        }
+    }
+    /// sum of the entries in each row (node degrees, for an adjacency matrix).
+    pub fn get_row_sums(&self) -> Vec<f64> {
+        (0..self.dim())
+            .map(|i| self.get_row(i).map(|(_, value)| value).sum())
+            .collect()
+    }
+    /// the rows and columns given by `indices`, in that order (e.g. the
+    /// adjacency matrix of an induced subgraph).
+    pub fn get_submatrix(&self, indices: &[usize]) -> Self {
+        let mut positions: Vec<Option<usize>> = vec![None; self.dim()];
+        for (pos, i) in indices.iter().enumerate() {
+            positions[*i] = Some(pos);
+        }
+        let rows = indices
+            .iter()
+            .map(|i| {
+                self.get_row(*i)
+                    .filter_map(|(j, value)| positions[j].map(|pos| (pos, value)))
+                    .collect()
+            })
+            .collect();
+        Self::from_rows(rows)
+    }
+    /// treating self as an adjacency matrix, the Laplacian D - A, where D holds
+    /// the row sums on its diagonal.
+    pub fn to_laplacian(&self) -> Self {
+        let rows = self
+            .get_row_sums()
+            .into_iter()
+            .enumerate()
+            .map(|(i, degree)| {
+                let mut row: Vec<(usize, f64)> = vec![(i, degree)];
+                row.extend(self.get_row(i).map(|(j, value)| (j, -value)));
+                row
+            })
+            .collect();
+        Self::from_rows(rows)
+    }
+    /// diag(left) * self * diag(right).
+    pub fn scale(&self, left: &[f64], right: &[f64]) -> Self {
+        let rows = (0..self.dim())
+            .map(|i| {
+                self.get_row(i)
+                    .map(|(j, value)| (j, left[i] * value * right[j]))
+                    .collect()
+            })
+            .collect();
+        Self::from_rows(rows)
+    }
+    pub fn to_dense(&self) -> DMatrix<f64> {
+        let n = self.dim();
+        let mut matrix = DMatrix::zeros(n, n);
+        for i in 0..n {
+            for (j, value) in self.get_row(i) {
+                matrix[(i, j)] = value;
+            }
+        }
+        matrix
+    }
+}
+impl LinearOperator for SparseGraphMatrix {
+    fn dim(&self) -> usize {
+        self.row_starts.len() - 1
+    }
+    fn apply(&self, x: &[f64], y: &mut [f64]) {
+        for (i, y_i) in y.iter_mut().enumerate() {
+            *y_i = self.get_row(i).map(|(j, value)| value * x[j]).sum();
+        }
+    }
+}
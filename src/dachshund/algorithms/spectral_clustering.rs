@@ -0,0 +1,199 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate rand;
+
+use crate::dachshund::algorithms::algebraic_connectivity::get_fiedler_pair;
+use crate::dachshund::algorithms::convergence::{Convergence, IterativeResult};
+use crate::dachshund::algorithms::lanczos::{get_extreme_eigenpairs, Spectrum};
+use crate::dachshund::algorithms::laplacian::Laplacian;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::NodeId;
+use rand::Rng;
+
+const EIGEN_TOLERANCE: f64 = 1e-8;
+const MAX_KMEANS_ITERATIONS: usize = 100;
+
+/// Partitions a graph into clusters using eigenvectors of its Laplacian.
+/// Clusters are returned as sorted lists of node ids, ordered by their smallest id,
+/// along with the convergence of the eigen-solver(s) they were computed with.
+pub trait SpectralClustering: GraphBase + Laplacian {
+    /// Splits the graph into two halves (differing in size by at most one) by
+    /// the median entry of its Fiedler vector.
+    fn get_spectral_bisection(&self) -> CLQResult<IterativeResult<(Vec<NodeId>, Vec<NodeId>)>> {
+        Ok(self.get_spectral_partition(2)?.map(|mut parts| {
+            let second = if parts.len() > 1 {
+                parts.pop().unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            let first = parts.pop().unwrap_or_default();
+            (first, second)
+        }))
+    }
+
+    /// Recursive spectral bisection: splits the largest remaining part in two
+    /// until there are `num_parts` parts (or all parts are single nodes). Each
+    /// split uses the Fiedler vector of the subgraph induced by the part.
+    fn get_spectral_partition(
+        &self,
+        num_parts: usize,
+    ) -> CLQResult<IterativeResult<Vec<Vec<NodeId>>>> {
+        let (adjacency, ids) = self.get_sparse_adjacency_matrix();
+        let mut parts: Vec<Vec<usize>> = vec![(0..ids.len()).collect()];
+        let mut convergence = Convergence::exact();
+        while parts.len() < num_parts {
+            let largest = match parts
+                .iter()
+                .enumerate()
+                .max_by_key(|(i, part)| (part.len(), std::cmp::Reverse(*i)))
+            {
+                Some((largest, part)) if part.len() >= 2 => largest,
+                _ => break,
+            };
+            let part = parts.swap_remove(largest);
+            let laplacian = adjacency.get_submatrix(&part).to_laplacian();
+            let fiedler_pair = get_fiedler_pair(&laplacian, EIGEN_TOLERANCE)?;
+            convergence = convergence.and(fiedler_pair.convergence);
+            let (_value, fiedler) = fiedler_pair.value;
+            check_finite(&fiedler)?;
+            let mut order: Vec<usize> = (0..part.len()).collect();
+            order.sort_by(|a, b| fiedler[*a].total_cmp(&fiedler[*b]).then(a.cmp(b)));
+            let half = part.len().div_ceil(2);
+            parts.push(order[..half].iter().map(|i| part[*i]).collect());
+            parts.push(order[half..].iter().map(|i| part[*i]).collect());
+        }
+        Ok(IterativeResult::new(
+            to_node_id_clusters(parts, &ids),
+            convergence,
+        ))
+    }
+
+    /// k-way spectral clustering (Ng, Jordan & Weiss, On Spectral Clustering:
+    /// Analysis and an algorithm): nodes are embedded by the eigenvectors of the
+    /// `k` smallest eigenvalues of the normalized Laplacian, rows normalized to
+    /// unit length, then grouped by k-means. `rng` seeds k-means, and the
+    /// eigen-solver on large graphs.
+    fn get_spectral_clusters<R: Rng>(
+        &self,
+        k: usize,
+        rng: &mut R,
+    ) -> CLQResult<IterativeResult<Vec<Vec<NodeId>>>> {
+        let (laplacian, ids) = self.get_sparse_normalized_laplacian_matrix();
+        let n = ids.len();
+        let k = std::cmp::min(k, n);
+        if k == 0 {
+            return Ok(IterativeResult::exact(Vec::new()));
+        }
+        let eigen = get_extreme_eigenpairs(&laplacian, k, Spectrum::Smallest, EIGEN_TOLERANCE, rng);
+        for vector in &eigen.vectors {
+            check_finite(vector)?;
+        }
+        let points: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                let row: Vec<f64> = eigen.vectors.iter().map(|v| v[i]).collect();
+                let norm = row.iter().map(|x| x * x).sum::<f64>().sqrt();
+                match norm {
+                    norm if norm > 0.0 => row.iter().map(|x| x / norm).collect(),
+                    _ => row,
+                }
+            })
+            .collect();
+        let labels = get_kmeans_labels(&points, k, rng);
+        let mut clusters: Vec<Vec<usize>> = vec![Vec::new(); k];
+        for (i, label) in labels.into_iter().enumerate() {
+            clusters[label].push(i);
+        }
+        Ok(IterativeResult::new(
+            to_node_id_clusters(clusters, &ids),
+            eigen.convergence,
+        ))
+    }
+}
+
+fn check_finite(vector: &[f64]) -> CLQResult<()> {
+    if vector.iter().any(|x| !x.is_finite()) {
+        return Err(CLQError::from(
+            "Spectral clustering found a non-finite eigenvector entry",
+        ));
+    }
+    Ok(())
+}
+
+fn to_node_id_clusters(clusters: Vec<Vec<usize>>, ids: &[NodeId]) -> Vec<Vec<NodeId>> {
+    let mut clusters: Vec<Vec<NodeId>> = clusters
+        .into_iter()
+        .filter(|cluster| !cluster.is_empty())
+        .map(|cluster| {
+            let mut cluster: Vec<NodeId> = cluster.into_iter().map(|i| ids[i]).collect();
+            cluster.sort();
+            cluster
+        })
+        .collect();
+    clusters.sort();
+    clusters
+}
+
+fn squared_distance(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+/// Lloyd's algorithm with k-means++ seeding; returns the cluster of each point.
+fn get_kmeans_labels<R: Rng>(points: &[Vec<f64>], k: usize, rng: &mut R) -> Vec<usize> {
+    let nearest = |point: &Vec<f64>, centers: &[Vec<f64>]| -> (usize, f64) {
+        centers
+            .iter()
+            .map(|center| squared_distance(point, center))
+            .enumerate()
+            .fold(
+                (0, f64::MAX),
+                |best, (i, d)| if d < best.1 { (i, d) } else { best },
+            )
+    };
+    let mut centers: Vec<Vec<f64>> = vec![points[rng.gen_range(0, points.len())].clone()];
+    while centers.len() < k {
+        let distances: Vec<f64> = points.iter().map(|p| nearest(p, &centers).1).collect();
+        let total: f64 = distances.iter().sum();
+        let next = if total > 0.0 {
+            let mut target = rng.gen_range(0.0, total);
+            distances
+                .iter()
+                .position(|d| {
+                    target -= d;
+                    target < 0.0
+                })
+                .unwrap_or(points.len() - 1)
+        } else {
+            rng.gen_range(0, points.len())
+        };
+        centers.push(points[next].clone());
+    }
+    let mut labels: Vec<usize> = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_KMEANS_ITERATIONS {
+        let new_labels: Vec<usize> = points.iter().map(|p| nearest(p, &centers).0).collect();
+        if new_labels == labels {
+            break;
+        }
+        labels = new_labels;
+        let dim = points[0].len();
+        let mut sums: Vec<Vec<f64>> = vec![vec![0.0; dim]; k];
+        let mut counts: Vec<usize> = vec![0; k];
+        for (point, label) in points.iter().zip(&labels) {
+            counts[*label] += 1;
+            for (s, x) in sums[*label].iter_mut().zip(point) {
+                *s += x;
+            }
+        }
+        for (center, (sum, count)) in centers.iter_mut().zip(sums.into_iter().zip(counts)) {
+            // an empty cluster keeps its previous center.
+            if count > 0 {
+                *center = sum.into_iter().map(|s| s / count as f64).collect();
+            }
+        }
+    }
+    labels
+}
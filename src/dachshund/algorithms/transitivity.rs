@@ -49,7 +49,7 @@ pub trait Transitivity: GraphBase {
     // Approximate Transitivity
     // k~=26,000 gives an approximation w/ <1% chance of an error of more than 1 percentage point.
     // See http://jgaa.info/accepted/2005/SchankWagner2005.9.2.pdf for approximation guarantees.
-    fn get_approx_transitivity(&self, samples: usize) -> f64 {
+    fn get_approx_transitivity<R: Rng>(&self, samples: usize, rng: &mut R) -> f64 {
         let ordered_nodes = self
             .get_nodes_iter()
             .filter(|node| node.degree() >= 2)
@@ -63,13 +63,12 @@ pub trait Transitivity: GraphBase {
         let dist = WeightedIndex::new(triples_counts).unwrap();
 
         let mut successes = 0;
-        let mut rng = rand::thread_rng();
         for _i in 0..samples {
             // Choose a random node weighted by degree.
-            let v = &ordered_nodes[dist.sample(&mut rng)];
+            let v = &ordered_nodes[dist.sample(rng)];
 
             // Choose 2 random nodes that are neighbors of j
-            let mut random_neighbors = v.get_edges().choose_multiple(&mut rng, 2).into_iter();
+            let mut random_neighbors = v.get_edges().choose_multiple(rng, 2).into_iter();
             let next_random_neighbor = random_neighbors.next();
             let u_id = next_random_neighbor.unwrap().get_neighbor_id();
             let w_id = random_neighbors.next().unwrap().get_neighbor_id();
@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+
+use clap::{Arg, ArgMatches};
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// What to do when a line of input cannot be turned into a row.
+#[derive(Default)]
+pub enum BadRowPolicy {
+    /// stop processing, returning a `CLQError::MalformedRow`.
+    #[default]
+    Fail,
+    /// drop the line and keep going, counting it in the run summary.
+    Skip,
+    /// like `Skip`, but also write the offending line (verbatim) to a side file.
+    WriteRejects(String),
+}
+impl BadRowPolicy {
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![
+            Arg::with_name("bad_rows")
+                .long("bad_rows")
+                .takes_value(true)
+                .possible_values(&["fail", "skip", "reject"])
+                .help(
+                    "What to do with malformed input lines: fail (default), skip them, \
+                     or reject them (skip and write them to --rejects_file).",
+                ),
+            Arg::with_name("rejects_file")
+                .long("rejects_file")
+                .takes_value(true)
+                .help("File to which rejected input lines are written (with --bad_rows reject)."),
+        ]
+    }
+    /// constructs a policy from an ArgMatches object (to help with command line arguments).
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        match matches.value_of("bad_rows").unwrap_or("fail") {
+            "fail" => Ok(BadRowPolicy::Fail),
+            "skip" => Ok(BadRowPolicy::Skip),
+            "reject" => {
+                let path = matches.value_of("rejects_file").ok_or_else(|| {
+                    CLQError::from("--bad_rows reject requires --rejects_file")
+                })?;
+                Ok(BadRowPolicy::WriteRejects(path.to_owned()))
+            }
+            other => Err(format!("Unknown bad row policy: {}", other).into()),
+        }
+    }
+}
+
+/// Counts of rows seen over the course of a run, reported once input is exhausted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowSummary {
+    pub num_rows: usize,
+    pub num_rejected: usize,
+}
+impl RowSummary {
+    /// reports the summary on stderr, if any rows were rejected.
+    pub fn report(&self) {
+        if self.num_rejected > 0 {
+            eprintln!("{}", self);
+        }
+    }
+}
+impl fmt::Display for RowSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Processed {} rows, rejected {} malformed rows.",
+            self.num_rows, self.num_rejected
+        )
+    }
+}
+
+/// Applies a `BadRowPolicy` to lines the line processor could not handle,
+/// keeping track of the `RowSummary` as it goes.
+pub struct BadRowHandler {
+    policy: BadRowPolicy,
+    rejects: Option<BufWriter<File>>,
+    summary: RowSummary,
+}
+impl BadRowHandler {
+    pub fn new(policy: BadRowPolicy) -> CLQResult<Self> {
+        let rejects = match &policy {
+            BadRowPolicy::WriteRejects(path) => Some(BufWriter::new(File::create(path)?)),
+            _ => None,
+        };
+        Ok(Self {
+            policy,
+            rejects,
+            summary: RowSummary::default(),
+        })
+    }
+    pub fn record_good_row(&mut self) {
+        self.summary.num_rows += 1;
+    }
+    /// line numbers are 1-based.
+    pub fn record_bad_row(
+        &mut self,
+        line_number: usize,
+        line: &str,
+        error: CLQError,
+    ) -> CLQResult<()> {
+        let error = CLQError::MalformedRow {
+            line_number,
+            source: Box::new(error),
+        };
+        if let BadRowPolicy::Fail = self.policy {
+            return Err(error);
+        }
+        self.summary.num_rows += 1;
+        self.summary.num_rejected += 1;
+        if let Some(rejects) = self.rejects.as_mut() {
+            rejects.write_all(line.as_bytes())?;
+            rejects.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+    pub fn finish(mut self) -> CLQResult<RowSummary> {
+        if let Some(rejects) = self.rejects.as_mut() {
+            rejects.flush()?;
+        }
+        Ok(self.summary)
+    }
+}
@@ -6,9 +6,7 @@
  */
 extern crate rand;
 
-use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
-use std::hash::{Hash, Hasher};
 
 use rand::prelude::*;
 
@@ -20,6 +18,7 @@ use crate::dachshund::node::Node;
 use crate::dachshund::row::CliqueRow;
 use crate::dachshund::scorer::Scorer;
 use crate::dachshund::search_problem::SearchProblem;
+use crate::dachshund::seed::Seed;
 
 use std::rc::Rc;
 
@@ -96,6 +95,7 @@ impl<'a, TGraph: GraphBase<NodeType = Node>> Beam<'a, TGraph> {
         num_non_core_types: usize,
         search_problem: Rc<SearchProblem>,
         graph_id: GraphId,
+        seed: Seed,
     ) -> CLQResult<Beam<'a, TGraph>> {
         let core_ids: &Vec<NodeId> = &graph.get_core_ids();
         let non_core_ids: &Vec<NodeId> = &graph.get_non_core_ids().unwrap();
@@ -105,9 +105,7 @@ impl<'a, TGraph: GraphBase<NodeType = Node>> Beam<'a, TGraph> {
 
         // To ensure deterministic behaviour between two identically configured runs,
         // seed the pseudorandom sequence with the current cluster.
-        let mut seeder = DefaultHasher::new();
-        graph_id.hash(&mut seeder);
-        let mut rng = StdRng::seed_from_u64(seeder.finish());
+        let mut rng = seed.get_rng(graph_id);
 
         if !clique_rows.is_empty() {
             let init_clique = Candidate::from_clique_rows(clique_rows, graph, &scorer)?;
@@ -18,10 +18,11 @@ use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::{GraphId, NodeId, NodeTypeId};
 use crate::dachshund::node::{Node, NodeBase};
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::{Field, OutputFormat, OutputRecord};
 use crate::dachshund::row::CliqueRow;
 use crate::dachshund::scorer::Scorer;
 
-use std::sync::mpsc::Sender;
 
 /// This data structure represents a guarantee or promise about the local cliqueness
 /// for some core nodes. It should be interpreted as saying
@@ -276,6 +277,16 @@ where
 
     /// encodes self as tab-separated "wide" format
     pub fn to_printable_row(&self, target_types: &[String]) -> CLQResult<String> {
+        let record = self.add_output_fields(OutputRecord::new(), target_types)?;
+        Ok(record.render(OutputFormat::Tsv))
+    }
+
+    /// appends the "wide" format fields describing self to a record.
+    pub fn add_output_fields(
+        &self,
+        record: OutputRecord,
+        target_types: &[String],
+    ) -> CLQResult<OutputRecord> {
         let encode_err_handler = |e: json::EncoderError| Err(CLQError::from(e.to_string()));
 
         let cliqueness = self.get_cliqueness()?;
@@ -285,36 +296,38 @@ where
             .iter()
             .map(|x| x.value())
             .collect();
-
-        let mut s = String::new();
-        s.push_str(&core_ids.len().to_string());
-        s.push_str("\t");
-        s.push_str(&non_core_ids.len().to_string());
-        s.push_str("\t");
-
-        s.push_str(&json::encode(&core_ids).or_else(encode_err_handler)?);
-        s.push_str("\t");
-
-        s.push_str(&json::encode(&non_core_ids).or_else(encode_err_handler)?);
-        s.push_str("\t");
-
         let non_core_types_str: Vec<String> = self
             .non_core_ids
             .clone()
             .into_iter()
             .map(|id| target_types[self.get_node(id).non_core_type.unwrap().value() - 1].clone())
             .collect();
-        s.push_str(&json::encode(&non_core_types_str).or_else(encode_err_handler)?);
-        s.push_str("\t");
-        s.push_str(&cliqueness.to_string());
-        s.push_str("\t");
-        s.push_str(&json::encode(&self.get_core_densities()).or_else(encode_err_handler)?);
-        s.push_str("\t");
-        s.push_str(
-            &json::encode(&self.get_non_core_densities(target_types.len())?)
-                .or_else(encode_err_handler)?,
-        );
-        Ok(s)
+        let non_core_densities = self.get_non_core_densities(target_types.len())?;
+
+        Ok(record
+            .field("num_core_nodes", core_ids.len())
+            .field("num_non_core_nodes", non_core_ids.len())
+            .field(
+                "core_ids",
+                Field::Json(json::encode(&core_ids).or_else(encode_err_handler)?),
+            )
+            .field(
+                "non_core_ids",
+                Field::Json(json::encode(&non_core_ids).or_else(encode_err_handler)?),
+            )
+            .field(
+                "non_core_types",
+                Field::Json(json::encode(&non_core_types_str).or_else(encode_err_handler)?),
+            )
+            .field("cliqueness", cliqueness)
+            .field(
+                "core_densities",
+                Field::Json(json::encode(&self.get_core_densities()).or_else(encode_err_handler)?),
+            )
+            .field(
+                "non_core_densities",
+                Field::Json(json::encode(&non_core_densities).or_else(encode_err_handler)?),
+            ))
     }
 
     /// used for interaction with Transformer classes.
@@ -342,13 +355,15 @@ where
         Ok(out)
     }
 
-    /// convenience function, used for debugging and "long-format" printing
+    /// convenience function, used for debugging and "long-format" printing.
+    /// `original_id` is the graph key as it appeared in the input.
     pub fn print(
         &self,
         graph_id: GraphId,
+        original_id: &str,
         target_types: &[String],
         core_type: &str,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         for output_row in &self.get_output_rows(graph_id)? {
             let node_type: String = match output_row.target_type {
@@ -357,17 +372,11 @@ where
                 Some(t) => target_types[t.value() - 1].clone(),
                 None => core_type.to_string(),
             };
-            output
-                .send((
-                    Some(format!(
-                        "{}\t{}\t{}",
-                        graph_id.value(),
-                        output_row.node_id.value(),
-                        node_type
-                    )),
-                    false,
-                ))
-                .unwrap();
+            let record = OutputRecord::new()
+                .field("graph_id", original_id)
+                .field("node_id", output_row.node_id.value())
+                .field("node_type", node_type);
+            output.send_record(record)?;
         }
         Ok(())
     }
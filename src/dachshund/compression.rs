@@ -0,0 +1,129 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate flate2;
+extern crate zstd;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression applied to input or output streams.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+impl Compression {
+    pub fn parse(s: &str) -> CLQResult<Self> {
+        match s {
+            "none" => Ok(Compression::None),
+            "gzip" | "gz" => Ok(Compression::Gzip),
+            "zstd" | "zst" => Ok(Compression::Zstd),
+            _ => Err(CLQError::from(format!("Invalid compression: {}", s))),
+        }
+    }
+    /// guesses compression from a file name (".gz" or ".zst").
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".gz") {
+            Compression::Gzip
+        } else if path.ends_with(".zst") {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+    /// recognizes compressed streams by their leading bytes.
+    pub fn from_magic(prefix: &[u8]) -> Self {
+        if prefix.starts_with(GZIP_MAGIC) {
+            Compression::Gzip
+        } else if prefix.starts_with(ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    /// wraps a reader, decompressing it if its first bytes identify it as gzip
+    /// or zstd. Anything else is passed through untouched.
+    pub fn decode<'a, R: Read + 'a>(mut reader: R) -> io::Result<Box<dyn BufRead + 'a>> {
+        // a single read may return fewer bytes than the magic number (e.g. on
+        // a pipe), so keep reading until we have enough or hit end of input.
+        let mut prefix = [0u8; 4];
+        let mut len = 0;
+        while len < prefix.len() {
+            match reader.read(&mut prefix[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let stream = Cursor::new(prefix[..len].to_vec()).chain(reader);
+        Ok(match Compression::from_magic(&prefix[..len]) {
+            Compression::None => Box::new(BufReader::new(stream)),
+            Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(stream))),
+            Compression::Zstd => Box::new(BufReader::new(zstd::Decoder::new(stream)?)),
+        })
+    }
+
+    /// wraps a writer, compressing everything written to it.
+    pub fn encode<'a, W: Write + Send + 'a>(self, writer: W) -> io::Result<CompressedWriter<'a>> {
+        let writer: Box<dyn Write + Send + 'a> = Box::new(writer);
+        Ok(match self {
+            Compression::None => CompressedWriter::Plain(writer),
+            Compression::Gzip => {
+                CompressedWriter::Gzip(GzEncoder::new(writer, flate2::Compression::default()))
+            }
+            Compression::Zstd => CompressedWriter::Zstd(zstd::Encoder::new(writer, 0)?),
+        })
+    }
+}
+
+/// A writer which may be compressing its output. Compressed streams end with a
+/// trailer, so `finish` must be called once everything has been written.
+pub enum CompressedWriter<'a> {
+    Plain(Box<dyn Write + Send + 'a>),
+    Gzip(GzEncoder<Box<dyn Write + Send + 'a>>),
+    Zstd(zstd::Encoder<'static, Box<dyn Write + Send + 'a>>),
+}
+impl<'a> CompressedWriter<'a> {
+    /// writes out any trailer and flushes the underlying writer.
+    pub fn finish(&mut self) -> io::Result<()> {
+        match self {
+            CompressedWriter::Plain(writer) => writer.flush(),
+            CompressedWriter::Gzip(encoder) => {
+                encoder.try_finish()?;
+                encoder.get_mut().flush()
+            }
+            CompressedWriter::Zstd(encoder) => {
+                encoder.do_finish()?;
+                encoder.get_mut().flush()
+            }
+        }
+    }
+}
+impl<'a> Write for CompressedWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            CompressedWriter::Plain(writer) => writer.write(buf),
+            CompressedWriter::Gzip(encoder) => encoder.write(buf),
+            CompressedWriter::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            CompressedWriter::Plain(writer) => writer.flush(),
+            CompressedWriter::Gzip(encoder) => encoder.flush(),
+            CompressedWriter::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
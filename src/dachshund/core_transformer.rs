@@ -12,13 +12,14 @@ use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::OutputRecord;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_transformer::GraphStatsTransformerBase;
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
 use crate::dachshund::transformer_base::TransformerBase;
 use std::collections::HashMap;
-use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 pub struct CoreTransformer {
@@ -64,7 +65,7 @@ impl TransformerBase for CoreTransformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = SimpleUndirectedGraphBuilder {};
@@ -78,15 +79,13 @@ impl TransformerBase for CoreTransformer {
         for (node_id, node_coreness) in coreness {
             let degree = graph.get_node_degree(node_id);
             let anomaly = anomaly_map.get(&node_id).unwrap();
-            let line: String = format!(
-                "{}\t{}\t{}\t{}\t{}",
-                original_id,
-                node_id.value(),
-                node_coreness,
-                degree,
-                anomaly
-            );
-            output.send((Some(line), false)).unwrap();
+            let record = OutputRecord::new()
+                .field("graph_id", original_id.as_str())
+                .field("node_id", node_id.value())
+                .field("coreness", node_coreness)
+                .field("degree", degree)
+                .field("coreness_anomaly", *anomaly);
+            output.send_record(record)?;
         }
         Ok(())
     }
@@ -0,0 +1,502 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::algorithms::connected_components::{
+    ConnectedComponents, ConnectedComponentsUndirected,
+};
+use crate::dachshund::algorithms::coreness::Coreness;
+use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
+use crate::dachshund::graph_snapshot::{GraphSnapshot, ReadSnapshot, WriteSnapshot};
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::CsrNode;
+use crate::dachshund::simple_undirected_graph::{SimpleUndirectedGraph, UndirectedGraph};
+use fxhash::FxHashSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, OnceLock};
+
+/// Read access to the compressed sparse row arrays behind a `CsrGraph` and its
+/// nodes: sorted node ids, and for each of them a sorted neighbor list, stored
+/// as positions in those ids.
+pub trait CsrStorage: Sync + Sized {
+    fn get_ids(&self) -> &[NodeId];
+    fn get_neighbor_positions(&self, position: usize) -> &[u32];
+    fn count_arcs(&self) -> usize;
+    fn create_empty() -> Self;
+}
+
+/// CSR arrays owned by the graph, laid out as in a `GraphSnapshot`: offsets
+/// hold one more entry than there are nodes.
+pub struct CsrArrays {
+    pub ids: Vec<NodeId>,
+    offsets: Vec<u64>,
+    neighbors: Vec<u32>,
+}
+impl CsrStorage for CsrArrays {
+    fn get_ids(&self) -> &[NodeId] {
+        &self.ids
+    }
+    fn get_neighbor_positions(&self, position: usize) -> &[u32] {
+        let start = self.offsets[position] as usize;
+        let end = self.offsets[position + 1] as usize;
+        &self.neighbors[start..end]
+    }
+    fn count_arcs(&self) -> usize {
+        self.neighbors.len()
+    }
+    fn create_empty() -> Self {
+        CsrArrays {
+            ids: Vec::new(),
+            offsets: vec![0],
+            neighbors: Vec::new(),
+        }
+    }
+}
+
+/// Snapshots are read in place; their node ids are checked to be sorted when
+/// they are loaded.
+impl CsrStorage for &GraphSnapshot {
+    fn get_ids(&self) -> &[NodeId] {
+        GraphSnapshot::ids(self)
+    }
+    fn get_neighbor_positions(&self, position: usize) -> &[u32] {
+        self.neighbors(position)
+    }
+    fn count_arcs(&self) -> usize {
+        self.num_arcs()
+    }
+    fn create_empty() -> Self {
+        static EMPTY: OnceLock<GraphSnapshot> = OnceLock::new();
+        EMPTY.get_or_init(GraphSnapshot::create_empty)
+    }
+}
+
+/// Keeps track of a simple undirected graph in compressed sparse row form: all
+/// neighbor lists share one contiguous array of positions, and node ids are
+/// kept sorted so that a node's dense index is its position in `ids`.
+/// Algorithms work on positions; `CsrNode`s are only built, once, for callers
+/// of `get_node` and `get_nodes_iter`.
+pub struct CsrGraph<S: CsrStorage> {
+    storage: Arc<S>,
+    core_ids: OnceLock<Vec<NodeId>>,
+    nodes: OnceLock<Vec<CsrNode<S>>>,
+}
+/// A `CsrGraph` owning its arrays.
+pub type CsrUndirectedGraph = CsrGraph<CsrArrays>;
+/// A `CsrGraph` borrowing its neighbor lists from a `GraphSnapshot`.
+pub type CsrSnapshotGraph<'a> = CsrGraph<&'a GraphSnapshot>;
+
+impl<S: CsrStorage> GraphBase for CsrGraph<S> {
+    type NodeType = CsrNode<S>;
+    type IdsIter<'a>
+        = std::slice::Iter<'a, NodeId>
+    where
+        S: 'a;
+    type NodesIter<'a>
+        = std::slice::Iter<'a, CsrNode<S>>
+    where
+        S: 'a;
+    /// the node table, by position; neighbor lists stay read-only.
+    type NodeMap = [CsrNode<S>];
+
+    /// core and non-core IDs are the same for a `CsrGraph`.
+    fn get_core_ids(&self) -> &Vec<NodeId> {
+        self.core_ids.get_or_init(|| self.ids().to_vec())
+    }
+    /// core and non-core IDs are the same for a `CsrGraph`.
+    fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
+        Some(self.get_core_ids())
+    }
+    fn get_ids_iter(&self) -> std::slice::Iter<'_, NodeId> {
+        self.ids().iter()
+    }
+    fn get_nodes_iter(&self) -> std::slice::Iter<'_, CsrNode<S>> {
+        self.get_nodes().iter()
+    }
+    fn get_mut_nodes(&mut self) -> &mut [CsrNode<S>] {
+        self.get_nodes();
+        self.nodes.get_mut().unwrap()
+    }
+    fn has_node(&self, node_id: NodeId) -> bool {
+        self.get_index(node_id).is_some()
+    }
+    fn get_node(&self, node_id: NodeId) -> &CsrNode<S> {
+        &self.get_nodes()[self.get_index(node_id).unwrap()]
+    }
+    fn count_edges(&self) -> usize {
+        self.storage.count_arcs() / 2
+    }
+    fn count_nodes(&self) -> usize {
+        self.ids().len()
+    }
+    fn create_empty() -> Self {
+        Self::from_storage(S::create_empty())
+    }
+    fn get_ordered_node_ids(&self) -> Vec<NodeId> {
+        self.ids().to_vec()
+    }
+    /// neighbor lists are already dense when `index` follows `ids`.
+    fn get_dense_adjacency(&self, index: &DenseIndex) -> Vec<Vec<usize>> {
+        if index.ids() != self.ids() {
+            return index
+                .ids()
+                .iter()
+                .map(|id| {
+                    self.get_neighbors(*id)
+                        .map(|x| index.get_index(x))
+                        .collect()
+                })
+                .collect();
+        }
+        (0..self.count_nodes())
+            .map(|i| {
+                self.get_neighbor_positions(i)
+                    .iter()
+                    .map(|x| *x as usize)
+                    .collect()
+            })
+            .collect()
+    }
+}
+impl<S: CsrStorage> CsrGraph<S> {
+    fn from_storage(storage: S) -> Self {
+        assert!(
+            storage.get_ids().len() <= u32::MAX as usize,
+            "Too many nodes for a CsrGraph"
+        );
+        CsrGraph {
+            storage: Arc::new(storage),
+            core_ids: OnceLock::new(),
+            nodes: OnceLock::new(),
+        }
+    }
+    fn get_nodes(&self) -> &Vec<CsrNode<S>> {
+        self.nodes.get_or_init(|| {
+            self.ids()
+                .iter()
+                .enumerate()
+                .map(|(i, id)| CsrNode::new(*id, i, &*self.storage))
+                .collect()
+        })
+    }
+    /// node ids, sorted: a node's position here indexes the CSR arrays.
+    pub fn ids(&self) -> &[NodeId] {
+        self.storage.get_ids()
+    }
+    /// position of a node in `ids`; constant time when ids are contiguous.
+    pub fn get_index(&self, node_id: NodeId) -> Option<usize> {
+        let ids = self.ids();
+        let first = ids.first()?.value();
+        let last = ids.last()?.value();
+        let num_nodes = ids.len() as u64;
+        if last.wrapping_sub(first) as u64 == num_nodes - 1 {
+            let offset = node_id.value().wrapping_sub(first) as u64;
+            return if offset < num_nodes {
+                Some(offset as usize)
+            } else {
+                None
+            };
+        }
+        ids.binary_search(&node_id).ok()
+    }
+    /// neighbors of the node at `position`, as positions in `ids`.
+    pub fn get_neighbor_positions(&self, position: usize) -> &[u32] {
+        self.storage.get_neighbor_positions(position)
+    }
+    fn get_neighbors_at(&self, position: usize) -> impl Iterator<Item = usize> + '_ {
+        self.get_neighbor_positions(position)
+            .iter()
+            .map(|x| *x as usize)
+    }
+    pub fn get_neighbors(&self, node_id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let ids = self.ids();
+        let position = self.get_index(node_id).unwrap();
+        self.get_neighbors_at(position).map(move |x| ids[x])
+    }
+    pub fn get_node_degree(&self, id: NodeId) -> usize {
+        self.get_neighbor_positions(self.get_index(id).unwrap())
+            .len()
+    }
+    /// positions of `ids`, flagged in a vector indexed by position.
+    fn get_position_flags<'b, I>(&self, ids: I) -> Vec<bool>
+    where
+        I: Iterator<Item = &'b NodeId>,
+    {
+        let mut flags = vec![false; self.count_nodes()];
+        for id in ids {
+            if let Some(position) = self.get_index(*id) {
+                flags[position] = true;
+            }
+        }
+        flags
+    }
+    /// triangles through the node at `position`, counted by merging its sorted
+    /// neighbor list with those of its neighbors.
+    fn count_triangles_at(&self, position: usize) -> usize {
+        let neighbors = self.get_neighbor_positions(position);
+        let num_triangles: usize = neighbors
+            .iter()
+            .map(|x| count_common_positions(neighbors, self.get_neighbor_positions(*x as usize)))
+            .sum();
+        num_triangles / 2
+    }
+}
+fn count_common_positions(a: &[u32], b: &[u32]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    count
+}
+impl CsrUndirectedGraph {
+    /// builds the graph from sorted node ids, CSR offsets into `neighbors`, and
+    /// (sorted) neighbor lists given as positions in `ids`.
+    pub(crate) fn from_arrays(ids: Vec<NodeId>, offsets: Vec<u64>, neighbors: Vec<u32>) -> Self {
+        Self::from_storage(CsrArrays {
+            ids,
+            offsets,
+            neighbors,
+        })
+    }
+}
+impl<'a> CsrSnapshotGraph<'a> {
+    /// reads an undirected `GraphSnapshot`, e.g. one opened from disk, in place.
+    pub fn view(snapshot: &'a GraphSnapshot) -> CLQResult<Self> {
+        if snapshot.is_directed() {
+            return Err(CLQError::from(
+                "Cannot build an undirected graph from a directed snapshot",
+            ));
+        }
+        Ok(Self::from_storage(snapshot))
+    }
+}
+impl From<&SimpleUndirectedGraph> for CsrUndirectedGraph {
+    fn from(graph: &SimpleUndirectedGraph) -> Self {
+        let index = graph.get_dense_index();
+        let mut offsets: Vec<u64> = Vec::with_capacity(index.len() + 1);
+        let mut neighbors: Vec<u32> = Vec::with_capacity(2 * graph.count_edges());
+        offsets.push(0);
+        for id in index.ids() {
+            // ids and neighbor sets are both sorted, so positions come out sorted.
+            neighbors.extend(
+                graph.nodes[id]
+                    .neighbors
+                    .iter()
+                    .map(|x| index.get_index(*x) as u32),
+            );
+            offsets.push(neighbors.len() as u64);
+        }
+        Self::from_arrays(index.ids().to_vec(), offsets, neighbors)
+    }
+}
+impl<S: CsrStorage> UndirectedGraph for CsrGraph<S> {}
+
+impl<S: CsrStorage> ConnectedComponents for CsrGraph<S> {
+    /// the traversal of the default implementation, on positions; start nodes
+    /// only join a component through one of their edges there as well.
+    fn _get_connected_components_membership(
+        &self,
+        ignore_nodes: Option<&FxHashSet<NodeId>>,
+        ignore_edges: Option<&HashSet<(NodeId, NodeId)>>,
+    ) -> (HashMap<NodeId, usize>, usize) {
+        let ids = self.ids();
+        let ignored = self.get_position_flags(ignore_nodes.into_iter().flatten());
+        let keeps_edge = |u: usize, v: usize| {
+            ignore_edges.is_none_or(|edges| {
+                !edges.contains(&(ids[u], ids[v])) && !edges.contains(&(ids[v], ids[u]))
+            })
+        };
+        let mut membership: Vec<Option<usize>> = vec![None; ids.len()];
+        let mut num_components = 0;
+        let mut stack: Vec<usize> = Vec::new();
+        for start in 0..ids.len() {
+            if ignored[start] || membership[start].is_some() {
+                continue;
+            }
+            stack.extend(
+                self.get_neighbors_at(start)
+                    .filter(|x| keeps_edge(start, *x)),
+            );
+            while let Some(position) = stack.pop() {
+                if ignored[position] || membership[position].is_some() {
+                    continue;
+                }
+                membership[position] = Some(num_components);
+                stack.extend(
+                    self.get_neighbors_at(position)
+                        .filter(|x| membership[*x].is_none() && keeps_edge(position, *x)),
+                );
+            }
+            num_components += 1;
+        }
+        let components = membership
+            .iter()
+            .enumerate()
+            .filter_map(|(position, component)| component.map(|x| (ids[position], x)))
+            .collect();
+        (components, num_components)
+    }
+}
+impl<S: CsrStorage> ConnectedComponentsUndirected for CsrGraph<S> {}
+impl<S: CsrStorage> Coreness for CsrGraph<S> {
+    fn _get_k_cores(&self, k: usize, removed: &mut FxHashSet<NodeId>) -> Vec<Vec<NodeId>> {
+        let ids = self.ids();
+        let mut is_removed = self.get_position_flags(removed.iter());
+        let mut num_neighbors: Vec<usize> = (0..ids.len())
+            .map(|x| self.get_neighbor_positions(x).len())
+            .collect();
+        // positions follow id order, so nodes are visited as in the default.
+        let mut queue: BTreeSet<usize> = (0..ids.len()).collect();
+        while let Some(position) = queue.pop_first() {
+            if num_neighbors[position] < k {
+                is_removed[position] = true;
+                removed.insert(ids[position]);
+                for neighbor in self.get_neighbors_at(position) {
+                    if !is_removed[neighbor] {
+                        queue.insert(neighbor);
+                        num_neighbors[neighbor] -= 1;
+                    }
+                }
+            }
+        }
+        self._get_connected_components(Some(removed), None)
+    }
+    fn get_coreness_values(&self) -> HashMap<NodeId, usize> {
+        let degrees = (0..self.count_nodes())
+            .map(|x| self.get_neighbor_positions(x).len())
+            .collect();
+        let coreness = self._get_dense_coreness(degrees, |x| self.get_neighbors_at(x));
+        self.ids().iter().cloned().zip(coreness).collect()
+    }
+}
+impl<S: CsrStorage> ShortestPaths for CsrGraph<S> {
+    /// breadth-first, as the graph is unweighted; parents are all neighbors one
+    /// step closer to `source`.
+    fn get_shortest_paths(
+        &self,
+        source: NodeId,
+        nodes_in_connected_component: &Option<Vec<NodeId>>,
+    ) -> (
+        HashMap<NodeId, Option<usize>>,
+        HashMap<NodeId, HashSet<NodeId>>,
+    ) {
+        let ids = self.ids();
+        let is_target = match nodes_in_connected_component {
+            Some(targets) => self.get_position_flags(targets.iter()),
+            None => vec![true; ids.len()],
+        };
+        let mut dist: Vec<Option<usize>> = vec![None; ids.len()];
+        let mut parents: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+        let source_position = self.get_index(source).unwrap();
+        dist[source_position] = Some(0);
+        let mut queue: VecDeque<usize> = VecDeque::from(vec![source_position]);
+        while let Some(u) = queue.pop_front() {
+            let alt = dist[u].unwrap() + 1;
+            for v in self.get_neighbors_at(u).filter(|x| is_target[*x]) {
+                if dist[v].is_none() {
+                    dist[v] = Some(alt);
+                    queue.push_back(v);
+                }
+                if dist[v] == Some(alt) {
+                    parents[v].push(u);
+                }
+            }
+        }
+        parents[source_position].push(source_position);
+        (0..ids.len())
+            .filter(|x| is_target[*x])
+            .map(|x| {
+                let node_parents = parents[x].iter().map(|y| ids[*y]).collect();
+                ((ids[x], dist[x]), (ids[x], node_parents))
+            })
+            .unzip()
+    }
+    fn get_shortest_paths_bfs(
+        &self,
+        source: NodeId,
+    ) -> (
+        Vec<NodeId>,
+        HashMap<NodeId, u32>,
+        HashMap<NodeId, Vec<NodeId>>,
+    ) {
+        let ids = self.ids();
+        let mut preds: Vec<Vec<NodeId>> = vec![Vec::new(); ids.len()];
+        let mut shortest_path_counts: Vec<u32> = vec![0; ids.len()];
+        let mut dists: Vec<Option<u32>> = vec![None; ids.len()];
+        let source_position = self.get_index(source).unwrap();
+        shortest_path_counts[source_position] = 1;
+        dists[source_position] = Some(0);
+
+        let mut stack = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(source_position);
+        while let Some(v) = queue.pop_front() {
+            stack.push(ids[v]);
+            let dist = dists[v].unwrap() + 1;
+            for neighbor in self.get_neighbors_at(v) {
+                if dists[neighbor].is_none() {
+                    queue.push_back(neighbor);
+                    dists[neighbor] = Some(dist);
+                }
+                if dists[neighbor] == Some(dist) {
+                    shortest_path_counts[neighbor] += shortest_path_counts[v];
+                    preds[neighbor].push(ids[v]);
+                }
+            }
+        }
+        (
+            stack,
+            ids.iter().cloned().zip(shortest_path_counts).collect(),
+            ids.iter().cloned().zip(preds).collect(),
+        )
+    }
+}
+impl<S: CsrStorage> Transitivity for CsrGraph<S> {
+    fn triangle_count(&self, node_id: NodeId) -> usize {
+        self.count_triangles_at(self.get_index(node_id).unwrap())
+    }
+    fn triples_count(&self, node_id: NodeId) -> usize {
+        let num_neighbors = self.get_node_degree(node_id);
+        num_neighbors * num_neighbors.saturating_sub(1) / 2
+    }
+    fn get_transitivity(&self) -> f64 {
+        let positions = 0..self.count_nodes();
+        let num_triangles: usize = positions.clone().map(|x| self.count_triangles_at(x)).sum();
+        let num_triples: usize = positions
+            .map(|x| {
+                let num_neighbors = self.get_neighbor_positions(x).len();
+                num_neighbors * num_neighbors.saturating_sub(1) / 2
+            })
+            .sum();
+        num_triangles as f64 / num_triples as f64
+    }
+}
+impl<S: CsrStorage> WriteSnapshot for CsrGraph<S> {}
+/// copies an undirected `GraphSnapshot`; see `CsrSnapshotGraph::view` to read
+/// one in place.
+impl ReadSnapshot for CsrUndirectedGraph {
+    fn _from_snapshot(snapshot: &GraphSnapshot) -> Self {
+        let neighbors = (0..snapshot.num_nodes())
+            .flat_map(|i| snapshot.neighbors(i).iter().copied())
+            .collect();
+        Self::from_arrays(
+            snapshot.ids().to_vec(),
+            snapshot.offsets().to_vec(),
+            neighbors,
+        )
+    }
+}
@@ -0,0 +1,50 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::csr_undirected_graph::CsrUndirectedGraph;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::NodeId;
+
+pub struct CsrUndirectedGraphBuilder {}
+
+impl GraphBuilderBase for CsrUndirectedGraphBuilder {
+    type GraphType = CsrUndirectedGraph;
+    type RowType = (i64, i64);
+
+    // builds a graph from a vector of IDs. Repeated edges are ignored.
+    // Edges only need to be provided once (this being an undirected graph)
+    fn from_vector(&mut self, data: Vec<(i64, i64)>) -> CLQResult<CsrUndirectedGraph> {
+        let mut arcs: Vec<(i64, i64)> = Vec::with_capacity(2 * data.len());
+        for (id1, id2) in data {
+            arcs.push((id1, id2));
+            arcs.push((id2, id1));
+        }
+        arcs.sort_unstable();
+        arcs.dedup();
+
+        // every endpoint shows up as a source, since arcs go both ways.
+        let mut ids: Vec<NodeId> = arcs.iter().map(|x| NodeId::from(x.0)).collect();
+        ids.dedup();
+        if ids.len() > u32::MAX as usize {
+            return Err(CLQError::from("Too many nodes for a CsrUndirectedGraph"));
+        }
+        let mut offsets: Vec<u64> = Vec::with_capacity(ids.len() + 1);
+        let mut neighbors: Vec<u32> = Vec::with_capacity(arcs.len());
+        offsets.push(0);
+        for (i, (source, target)) in arcs.iter().enumerate() {
+            if i > 0 && arcs[i - 1].0 != *source {
+                offsets.push(neighbors.len() as u64);
+            }
+            let position = ids.binary_search(&NodeId::from(*target)).unwrap();
+            neighbors.push(position as u32);
+        }
+        if !ids.is_empty() {
+            offsets.push(neighbors.len() as u64);
+        }
+        Ok(CsrUndirectedGraph::from_arrays(ids, offsets, neighbors))
+    }
+}
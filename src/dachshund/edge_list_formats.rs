@@ -0,0 +1,400 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_builder_base::GraphBuilderBase;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{SimpleDirectedNode, SimpleNode, WeightedNode};
+use crate::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use crate::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use crate::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use fxhash::FxHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::BufRead;
+
+/// Format-neutral representation of a single graph, used to read and write
+/// standard edge list formats: SNAP edge lists, Matrix Market (coordinate) files,
+/// and METIS adjacency files. Undirected edges are listed once. Unweighted edges
+/// carry a weight of 1.0.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeList {
+    pub node_ids: BTreeSet<i64>,
+    pub edges: Vec<(i64, i64, f64)>,
+    pub weighted: bool,
+    pub directed: bool,
+}
+
+fn parse_field<T: std::str::FromStr>(field: Option<&str>, what: &str) -> CLQResult<T>
+where
+    CLQError: From<T::Err>,
+{
+    match field {
+        Some(field) => Ok(field.parse::<T>()?),
+        None => Err(format!("Missing {}", what).into()),
+    }
+}
+
+/// nodes without edges, e.g. isolated nodes declared in Matrix Market and METIS
+/// headers, which graph builders never see.
+fn get_isolated_ids<T>(node_ids: &BTreeSet<i64>, nodes: &FxHashMap<NodeId, T>) -> Vec<NodeId> {
+    node_ids
+        .iter()
+        .map(|id| NodeId::from(*id))
+        .filter(|id| !nodes.contains_key(id))
+        .collect()
+}
+
+impl EdgeList {
+    pub fn new(weighted: bool, directed: bool) -> Self {
+        Self {
+            node_ids: BTreeSet::new(),
+            edges: Vec::new(),
+            weighted,
+            directed,
+        }
+    }
+    pub fn add_edge(&mut self, source: i64, target: i64, weight: f64) {
+        self.node_ids.insert(source);
+        self.node_ids.insert(target);
+        self.edges.push((source, target, weight));
+    }
+
+    /// reads a SNAP edge list: one whitespace-separated `source target` pair per
+    /// line, with an optional third column holding the edge weight. Lines starting
+    /// with `#` are comments.
+    pub fn from_snap<R: BufRead>(reader: R, directed: bool) -> CLQResult<Self> {
+        let mut edge_list = Self::new(false, directed);
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let source: i64 = parse_field(fields.next(), "source id")?;
+            let target: i64 = parse_field(fields.next(), "target id")?;
+            let weight: f64 = match fields.next() {
+                Some(w) => {
+                    edge_list.weighted = true;
+                    w.parse::<f64>()?
+                }
+                None => 1.0,
+            };
+            edge_list.add_edge(source, target, weight);
+        }
+        Ok(edge_list)
+    }
+
+    /// reads a Matrix Market coordinate file, treating the matrix as an adjacency
+    /// matrix. `pattern` matrices are unweighted, `real` and `integer` ones weighted.
+    /// `symmetric` matrices become undirected graphs, `general` ones directed.
+    /// Node ids are the (1-based) row and column indices.
+    pub fn from_matrix_market<R: BufRead>(reader: R) -> CLQResult<Self> {
+        let mut lines = reader.lines();
+        let banner = lines
+            .next()
+            .ok_or_else(|| CLQError::from("Empty Matrix Market file"))??;
+        let banner: Vec<String> = banner
+            .split_whitespace()
+            .map(|x| x.to_lowercase())
+            .collect();
+        if banner.len() != 5 || banner[0] != "%%matrixmarket" || banner[1] != "matrix" {
+            return Err(format!("Invalid Matrix Market banner: {}", banner.join(" ")).into());
+        }
+        if banner[2] != "coordinate" {
+            return Err("Only coordinate Matrix Market files are supported".into());
+        }
+        let weighted = match banner[3].as_str() {
+            "pattern" => false,
+            "real" | "integer" => true,
+            other => return Err(format!("Unsupported Matrix Market field: {}", other).into()),
+        };
+        let directed = match banner[4].as_str() {
+            "general" => true,
+            "symmetric" => false,
+            other => return Err(format!("Unsupported Matrix Market symmetry: {}", other).into()),
+        };
+        let mut edge_list = Self::new(weighted, directed);
+        let mut num_entries: Option<usize> = None;
+        for line in lines {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('%') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            match num_entries {
+                None => {
+                    let num_rows: i64 = parse_field(fields.next(), "row count")?;
+                    let num_cols: i64 = parse_field(fields.next(), "column count")?;
+                    num_entries = Some(parse_field(fields.next(), "entry count")?);
+                    edge_list.node_ids = (1..=std::cmp::max(num_rows, num_cols)).collect();
+                }
+                Some(_) => {
+                    let row: i64 = parse_field(fields.next(), "row index")?;
+                    let col: i64 = parse_field(fields.next(), "column index")?;
+                    let weight: f64 = match weighted {
+                        true => parse_field(fields.next(), "value")?,
+                        false => 1.0,
+                    };
+                    edge_list.add_edge(row, col, weight);
+                }
+            }
+        }
+        match num_entries {
+            None => Err("Matrix Market file has no size line".into()),
+            Some(n) if n != edge_list.edges.len() => Err(format!(
+                "Matrix Market file declares {} entries, found {}",
+                n,
+                edge_list.edges.len()
+            )
+            .into()),
+            Some(_) => Ok(edge_list),
+        }
+    }
+
+    /// reads a METIS graph file. Line i (1-based, after the header) lists the
+    /// neighbors of node i, each followed by an edge weight if the format code
+    /// says so. Vertex sizes and weights are ignored. Lines starting with `%`
+    /// are comments. Self-loops, which METIS does not allow, are malformed rows.
+    pub fn from_metis<R: BufRead>(reader: R) -> CLQResult<Self> {
+        let mut header: Option<(i64, usize, bool, usize)> = None;
+        let mut edge_list = Self::new(false, false);
+        let mut node_id: i64 = 0;
+        for (line_ix, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.starts_with('%') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            match header {
+                None => {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let num_nodes: i64 = parse_field(fields.next(), "node count")?;
+                    let num_edges: usize = parse_field(fields.next(), "edge count")?;
+                    let fmt: String = format!("{:0>3}", fields.next().unwrap_or("0"));
+                    let fmt: Vec<char> = fmt.chars().collect();
+                    let has_sizes = fmt[fmt.len() - 3] == '1';
+                    let has_vertex_weights = fmt[fmt.len() - 2] == '1';
+                    let has_edge_weights = fmt[fmt.len() - 1] == '1';
+                    let num_constraints: usize = match fields.next() {
+                        Some(ncon) => ncon.parse::<usize>()?,
+                        None => 1,
+                    };
+                    let num_skipped = has_sizes as usize
+                        + if has_vertex_weights {
+                            num_constraints
+                        } else {
+                            0
+                        };
+                    edge_list.weighted = has_edge_weights;
+                    edge_list.node_ids = (1..=num_nodes).collect();
+                    header = Some((num_nodes, num_edges, has_edge_weights, num_skipped));
+                }
+                Some((num_nodes, _, has_edge_weights, num_skipped)) => {
+                    node_id += 1;
+                    if node_id > num_nodes {
+                        if line.trim().is_empty() {
+                            continue;
+                        }
+                        return Err(format!(
+                            "METIS file declares {} nodes, found more adjacency lines",
+                            num_nodes
+                        )
+                        .into());
+                    }
+                    let mut fields = fields.skip(num_skipped);
+                    while let Some(neighbor) = fields.next() {
+                        let neighbor: i64 = neighbor.parse::<i64>()?;
+                        if neighbor == node_id {
+                            return Err(CLQError::MalformedRow {
+                                line_number: line_ix + 1,
+                                source: Box::new(CLQError::from(format!(
+                                    "self-loop on node {}",
+                                    node_id
+                                ))),
+                            });
+                        }
+                        let weight: f64 = match has_edge_weights {
+                            true => parse_field(fields.next(), "edge weight")?,
+                            false => 1.0,
+                        };
+                        // each edge is listed under both of its endpoints.
+                        if node_id < neighbor {
+                            edge_list.add_edge(node_id, neighbor, weight);
+                        }
+                    }
+                }
+            }
+        }
+        match header {
+            None => Err("METIS file has no header".into()),
+            Some((_, num_edges, _, _)) if num_edges != edge_list.edges.len() => Err(format!(
+                "METIS file declares {} edges, found {}",
+                num_edges,
+                edge_list.edges.len()
+            )
+            .into()),
+            Some(_) => Ok(edge_list),
+        }
+    }
+
+    /// maps node ids to 1..n in increasing id order, as required by Matrix
+    /// Market and METIS indices.
+    fn get_dense_ids(&self) -> BTreeMap<i64, usize> {
+        self.node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i + 1))
+            .collect()
+    }
+
+    pub fn to_snap(&self) -> String {
+        let mut rows: Vec<String> = vec![
+            format!(
+                "# {} graph",
+                if self.directed {
+                    "Directed"
+                } else {
+                    "Undirected"
+                }
+            ),
+            format!(
+                "# Nodes: {} Edges: {}",
+                self.node_ids.len(),
+                self.edges.len()
+            ),
+            "# FromNodeId\tToNodeId".to_string(),
+        ];
+        for (source, target, weight) in &self.edges {
+            rows.push(match self.weighted {
+                true => format!("{}\t{}\t{}", source, target, weight),
+                false => format!("{}\t{}", source, target),
+            });
+        }
+        rows.join("\n")
+    }
+
+    /// node ids are written as 1-based indices, in increasing id order.
+    pub fn to_matrix_market(&self) -> String {
+        let ids = self.get_dense_ids();
+        let mut rows: Vec<String> = vec![format!(
+            "%%MatrixMarket matrix coordinate {} {}",
+            if self.weighted { "real" } else { "pattern" },
+            if self.directed {
+                "general"
+            } else {
+                "symmetric"
+            },
+        )];
+        rows.push(format!("{} {} {}", ids.len(), ids.len(), self.edges.len()));
+        for (source, target, weight) in &self.edges {
+            let (mut row, mut col) = (ids[source], ids[target]);
+            // symmetric matrices are stored as their lower triangle.
+            if !self.directed && row < col {
+                std::mem::swap(&mut row, &mut col);
+            }
+            rows.push(match self.weighted {
+                true => format!("{} {} {}", row, col, weight),
+                false => format!("{} {}", row, col),
+            });
+        }
+        rows.join("\n")
+    }
+
+    /// node ids are written as 1-based indices, in increasing id order. Only
+    /// undirected graphs without self-loops can be written. Note that METIS
+    /// proper only accepts integral edge weights.
+    pub fn to_metis(&self) -> CLQResult<String> {
+        if self.directed {
+            return Err("METIS files can only hold undirected graphs".into());
+        }
+        let ids = self.get_dense_ids();
+        let format_neighbor = |neighbor: usize, weight: f64| match self.weighted {
+            true => format!("{} {}", neighbor, weight),
+            false => neighbor.to_string(),
+        };
+        let mut adjacency: Vec<Vec<String>> = vec![Vec::new(); ids.len()];
+        for (source, target, weight) in &self.edges {
+            let (source, target) = (ids[source], ids[target]);
+            if source == target {
+                return Err(format!("METIS files cannot hold self-loops ({})", source).into());
+            }
+            adjacency[source - 1].push(format_neighbor(target, *weight));
+            adjacency[target - 1].push(format_neighbor(source, *weight));
+        }
+        let mut rows: Vec<String> = vec![match self.weighted {
+            true => format!("{} {} 001", ids.len(), self.edges.len()),
+            false => format!("{} {}", ids.len(), self.edges.len()),
+        }];
+        rows.extend(adjacency.into_iter().map(|x| x.join(" ")));
+        Ok(rows.join("\n"))
+    }
+
+    /// edge weights are dropped.
+    pub fn into_simple_undirected_graph(self) -> CLQResult<SimpleUndirectedGraph> {
+        let mut graph = SimpleUndirectedGraphBuilder {}.from_vector(
+            self.edges
+                .into_iter()
+                .map(|(source, target, _)| (source, target))
+                .collect(),
+        )?;
+        for id in get_isolated_ids(&self.node_ids, &graph.nodes) {
+            graph.nodes.insert(
+                id,
+                SimpleNode {
+                    node_id: id,
+                    neighbors: BTreeSet::new(),
+                },
+            );
+            graph.ids.push(id);
+        }
+        Ok(graph)
+    }
+    pub fn into_weighted_undirected_graph(self) -> CLQResult<WeightedUndirectedGraph> {
+        let mut graph = WeightedUndirectedGraphBuilder {}.from_vector(self.edges)?;
+        for id in get_isolated_ids(&self.node_ids, &graph.nodes) {
+            graph.nodes.insert(
+                id,
+                WeightedNode {
+                    node_id: id,
+                    edges: Vec::new(),
+                    neighbors: BTreeSet::new(),
+                },
+            );
+            graph.ids.push(id);
+        }
+        Ok(graph)
+    }
+    /// edge weights are dropped. Undirected edges yield an arc in each direction.
+    pub fn into_simple_directed_graph(self) -> CLQResult<SimpleDirectedGraph> {
+        let mut rows: Vec<(i64, i64)> = Vec::with_capacity(self.edges.len());
+        for (source, target, _) in self.edges {
+            rows.push((source, target));
+            if !self.directed {
+                rows.push((target, source));
+            }
+        }
+        let mut graph = SimpleDirectedGraphBuilder {}.from_vector(rows)?;
+        for id in get_isolated_ids(&self.node_ids, &graph.nodes) {
+            graph.nodes.insert(
+                id,
+                SimpleDirectedNode {
+                    node_id: id,
+                    in_neighbors: BTreeSet::new(),
+                    out_neighbors: BTreeSet::new(),
+                },
+            );
+            graph.ids.push(id);
+        }
+        Ok(graph)
+    }
+}
@@ -35,6 +35,16 @@ pub enum CLQError {
 
     #[error("Impossible error: {0}")]
     Infallible(#[from] std::convert::Infallible),
+
+    #[error("Expected {expected} fields, found {found}")]
+    FieldCount { expected: usize, found: usize },
+
+    #[error("Malformed row at line {line_number}: {source}")]
+    MalformedRow {
+        line_number: usize,
+        #[source]
+        source: Box<CLQError>,
+    },
 }
 
 impl CLQError {
@@ -4,12 +4,46 @@
  * This source code is licensed under the MIT license found in the
  * LICENSE file in the root directory of this source tree.
  */
-use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::NodeBase;
-
 extern crate fxhash;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
 use fxhash::FxHashMap;
-use std::collections::hash_map::{Keys, Values};
+use std::collections::HashMap;
+
+/// Maps the node ids of a graph to dense indices 0..n (in id order) and back,
+/// so that per-node algorithm state can live in a `Vec` rather than a `HashMap`.
+pub struct DenseIndex {
+    ids: Vec<NodeId>,
+    positions: FxHashMap<NodeId, usize>,
+}
+impl DenseIndex {
+    pub fn new(ids: Vec<NodeId>) -> Self {
+        let positions = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        Self { ids, positions }
+    }
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+    pub fn ids(&self) -> &[NodeId] {
+        &self.ids
+    }
+    pub fn get_id(&self, index: usize) -> NodeId {
+        self.ids[index]
+    }
+    pub fn get_index(&self, node_id: NodeId) -> usize {
+        self.positions[&node_id]
+    }
+    pub fn try_get_index(&self, node_id: NodeId) -> Option<usize> {
+        self.positions.get(&node_id).copied()
+    }
+    /// turns per-index values back into a map keyed by node id.
+    pub fn to_node_map<T>(&self, values: Vec<T>) -> HashMap<NodeId, T> {
+        self.ids.iter().cloned().zip(values).collect()
+    }
+}
 
 /// General-purpose trait which indicates the minimum amount of shared context
 /// required between all graph objects. Currently built to accommodate a graph
@@ -20,12 +54,21 @@ where
     Self::NodeType: NodeBase,
 {
     type NodeType;
+    type IdsIter<'a>: Iterator<Item = &'a NodeId>
+    where
+        Self: 'a;
+    type NodesIter<'a>: Iterator<Item = &'a Self::NodeType>
+    where
+        Self: 'a;
+    /// what `get_mut_nodes` hands out: the node map of hash-based graphs, the
+    /// node table of CSR graphs.
+    type NodeMap: ?Sized;
 
     fn get_core_ids(&self) -> &Vec<NodeId>;
     fn get_non_core_ids(&self) -> Option<&Vec<NodeId>>;
-    fn get_ids_iter(&self) -> Keys<NodeId, Self::NodeType>;
-    fn get_nodes_iter(&self) -> Values<NodeId, Self::NodeType>;
-    fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, Self::NodeType>;
+    fn get_ids_iter(&self) -> Self::IdsIter<'_>;
+    fn get_nodes_iter(&self) -> Self::NodesIter<'_>;
+    fn get_mut_nodes(&mut self) -> &mut Self::NodeMap;
     fn has_node(&self, node_id: NodeId) -> bool;
     fn get_node(&self, node_id: NodeId) -> &Self::NodeType;
     fn count_edges(&self) -> usize;
@@ -37,4 +80,20 @@ where
         node_ids.sort();
         node_ids
     }
+    fn get_dense_index(&self) -> DenseIndex {
+        DenseIndex::new(self.get_ordered_node_ids())
+    }
+    /// neighbors of every node, as dense indices, in the order of `index`.
+    fn get_dense_adjacency(&self, index: &DenseIndex) -> Vec<Vec<usize>> {
+        index
+            .ids()
+            .iter()
+            .map(|id| {
+                self.get_node(*id)
+                    .get_edges()
+                    .map(|e| index.get_index(e.get_neighbor_id()))
+                    .collect()
+            })
+            .collect()
+    }
 }
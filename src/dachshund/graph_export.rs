@@ -0,0 +1,607 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::id_types::NodeId;
+use crate::dachshund::node::{NodeBase, NodeEdgeBase};
+use crate::dachshund::non_core_type_ids::NonCoreTypeIds;
+use crate::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::typed_graph::TypedGraph;
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::io::Write;
+
+/// Value of a per-node attribute attached to an exported graph.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttributeValue::Bool(x) => write!(f, "{}", x),
+            AttributeValue::Int(x) => write!(f, "{}", x),
+            AttributeValue::Float(x) => write!(f, "{}", x),
+            AttributeValue::Str(x) => write!(f, "{}", x),
+        }
+    }
+}
+impl From<bool> for AttributeValue {
+    fn from(x: bool) -> Self {
+        AttributeValue::Bool(x)
+    }
+}
+impl From<i32> for AttributeValue {
+    fn from(x: i32) -> Self {
+        AttributeValue::Int(x as i64)
+    }
+}
+impl From<i64> for AttributeValue {
+    fn from(x: i64) -> Self {
+        AttributeValue::Int(x)
+    }
+}
+impl From<usize> for AttributeValue {
+    fn from(x: usize) -> Self {
+        AttributeValue::Int(x as i64)
+    }
+}
+impl From<f64> for AttributeValue {
+    fn from(x: f64) -> Self {
+        AttributeValue::Float(x)
+    }
+}
+impl From<String> for AttributeValue {
+    fn from(x: String) -> Self {
+        AttributeValue::Str(x)
+    }
+}
+impl From<&str> for AttributeValue {
+    fn from(x: &str) -> Self {
+        AttributeValue::Str(x.to_owned())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AttributeType {
+    Bool,
+    Int,
+    Float,
+    Str,
+}
+impl AttributeType {
+    fn of(value: &AttributeValue) -> Self {
+        match value {
+            AttributeValue::Bool(_) => AttributeType::Bool,
+            AttributeValue::Int(_) => AttributeType::Int,
+            AttributeValue::Float(_) => AttributeType::Float,
+            AttributeValue::Str(_) => AttributeType::Str,
+        }
+    }
+    /// GraphML and GEXF happen to share these type names.
+    fn xml_name(&self) -> &'static str {
+        match self {
+            AttributeType::Bool => "boolean",
+            AttributeType::Int => "long",
+            AttributeType::Float => "double",
+            AttributeType::Str => "string",
+        }
+    }
+}
+
+/// A named per-node attribute (e.g. coreness, community id, clique membership).
+/// Nodes without a value get `default`, if any.
+#[derive(Clone, Debug)]
+pub struct NodeAttribute {
+    pub name: String,
+    pub values: HashMap<NodeId, AttributeValue>,
+    pub default: Option<AttributeValue>,
+}
+impl NodeAttribute {
+    pub fn new<T: Clone + Into<AttributeValue>>(name: &str, values: &HashMap<NodeId, T>) -> Self {
+        Self {
+            name: name.to_owned(),
+            values: values
+                .iter()
+                .map(|(id, value)| (*id, value.clone().into()))
+                .collect(),
+            default: None,
+        }
+    }
+    /// true for the given members (e.g. of a clique), false for all other nodes.
+    pub fn membership(name: &str, members: &BTreeSet<NodeId>) -> Self {
+        Self {
+            name: name.to_owned(),
+            values: members
+                .iter()
+                .map(|id| (*id, AttributeValue::Bool(true)))
+                .collect(),
+            default: Some(AttributeValue::Bool(false)),
+        }
+    }
+    /// values of mixed types are written as doubles if all numeric, as strings otherwise.
+    fn get_type(&self) -> AttributeType {
+        let mut attribute_type: Option<AttributeType> = None;
+        for value in self.values.values().chain(self.default.iter()) {
+            let value_type = AttributeType::of(value);
+            attribute_type = match attribute_type {
+                None => Some(value_type),
+                Some(t) if t == value_type => Some(t),
+                Some(AttributeType::Int) | Some(AttributeType::Float)
+                    if value_type == AttributeType::Int || value_type == AttributeType::Float =>
+                {
+                    Some(AttributeType::Float)
+                }
+                Some(_) => Some(AttributeType::Str),
+            };
+        }
+        attribute_type.unwrap_or(AttributeType::Str)
+    }
+}
+
+/// An edge as written by the GraphML and GEXF exporters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportEdge {
+    pub source_id: NodeId,
+    pub target_id: NodeId,
+    pub weight: Option<f64>,
+    pub edge_type: Option<String>,
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Serializes graphs into GraphML and GEXF (e.g. for visualization in Gephi).
+/// Implementors only describe their nodes and edges, sorted by id.
+pub trait GraphExport {
+    fn is_directed(&self) -> bool;
+    /// node ids, each with an optional node type.
+    fn get_export_nodes(&self) -> Vec<(NodeId, Option<String>)>;
+    fn get_export_edges(&self) -> Vec<ExportEdge>;
+
+    /// streams the graph out as GraphML, one element per line.
+    fn write_graphml<W: Write>(
+        &self,
+        writer: &mut W,
+        attributes: &[NodeAttribute],
+    ) -> CLQResult<()> {
+        let nodes = self.get_export_nodes();
+        let edges = self.get_export_edges();
+        let has_node_types = nodes.iter().any(|(_, t)| t.is_some());
+        let has_weights = edges.iter().any(|e| e.weight.is_some());
+        let has_edge_types = edges.iter().any(|e| e.edge_type.is_some());
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            writer,
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"
+        )?;
+        if has_node_types {
+            writeln!(
+                writer,
+                "  <key id=\"node_type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>"
+            )?;
+        }
+        if has_weights {
+            writeln!(
+                writer,
+                "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>"
+            )?;
+        }
+        if has_edge_types {
+            writeln!(
+                writer,
+                "  <key id=\"edge_type\" for=\"edge\" attr.name=\"type\" attr.type=\"string\"/>"
+            )?;
+        }
+        for (i, attribute) in attributes.iter().enumerate() {
+            write!(
+                writer,
+                "  <key id=\"a{}\" for=\"node\" attr.name=\"{}\" attr.type=\"{}\"",
+                i,
+                escape_xml(&attribute.name),
+                attribute.get_type().xml_name()
+            )?;
+            match &attribute.default {
+                Some(default) => writeln!(
+                    writer,
+                    "><default>{}</default></key>",
+                    escape_xml(&default.to_string())
+                )?,
+                None => writeln!(writer, "/>")?,
+            }
+        }
+        writeln!(
+            writer,
+            "  <graph id=\"G\" edgedefault=\"{}\">",
+            if self.is_directed() {
+                "directed"
+            } else {
+                "undirected"
+            }
+        )?;
+        for (id, node_type) in &nodes {
+            let values: Vec<(usize, &AttributeValue)> = attributes
+                .iter()
+                .enumerate()
+                .filter_map(|(i, attribute)| attribute.values.get(id).map(|value| (i, value)))
+                .collect();
+            write!(writer, "    <node id=\"{}\"", id.value())?;
+            if node_type.is_none() && values.is_empty() {
+                writeln!(writer, "/>")?;
+                continue;
+            }
+            write!(writer, ">")?;
+            if let Some(node_type) = node_type {
+                write!(
+                    writer,
+                    "<data key=\"node_type\">{}</data>",
+                    escape_xml(node_type)
+                )?;
+            }
+            for (i, value) in values {
+                write!(
+                    writer,
+                    "<data key=\"a{}\">{}</data>",
+                    i,
+                    escape_xml(&value.to_string())
+                )?;
+            }
+            writeln!(writer, "</node>")?;
+        }
+        for edge in &edges {
+            write!(
+                writer,
+                "    <edge source=\"{}\" target=\"{}\"",
+                edge.source_id.value(),
+                edge.target_id.value()
+            )?;
+            if edge.weight.is_none() && edge.edge_type.is_none() {
+                writeln!(writer, "/>")?;
+                continue;
+            }
+            write!(writer, ">")?;
+            if let Some(weight) = edge.weight {
+                write!(writer, "<data key=\"weight\">{}</data>", weight)?;
+            }
+            if let Some(edge_type) = &edge.edge_type {
+                write!(
+                    writer,
+                    "<data key=\"edge_type\">{}</data>",
+                    escape_xml(edge_type)
+                )?;
+            }
+            writeln!(writer, "</edge>")?;
+        }
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")?;
+        Ok(())
+    }
+
+    /// streams the graph out as GEXF, one element per line.
+    fn write_gexf<W: Write>(&self, writer: &mut W, attributes: &[NodeAttribute]) -> CLQResult<()> {
+        let nodes = self.get_export_nodes();
+        let edges = self.get_export_edges();
+        let has_node_types = nodes.iter().any(|(_, t)| t.is_some());
+        let has_edge_types = edges.iter().any(|e| e.edge_type.is_some());
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            writer,
+            "<gexf xmlns=\"http://www.gexf.net/1.2draft\" version=\"1.2\">"
+        )?;
+        writeln!(
+            writer,
+            "  <graph mode=\"static\" defaultedgetype=\"{}\">",
+            if self.is_directed() {
+                "directed"
+            } else {
+                "undirected"
+            }
+        )?;
+        // attribute ids: node type (if any) first, then the given attributes.
+        let offset: usize = has_node_types as usize;
+        if has_node_types || !attributes.is_empty() {
+            writeln!(writer, "    <attributes class=\"node\">")?;
+            if has_node_types {
+                writeln!(
+                    writer,
+                    "      <attribute id=\"0\" title=\"type\" type=\"string\"/>"
+                )?;
+            }
+            for (i, attribute) in attributes.iter().enumerate() {
+                write!(
+                    writer,
+                    "      <attribute id=\"{}\" title=\"{}\" type=\"{}\"",
+                    i + offset,
+                    escape_xml(&attribute.name),
+                    attribute.get_type().xml_name()
+                )?;
+                match &attribute.default {
+                    Some(default) => writeln!(
+                        writer,
+                        "><default>{}</default></attribute>",
+                        escape_xml(&default.to_string())
+                    )?,
+                    None => writeln!(writer, "/>")?,
+                }
+            }
+            writeln!(writer, "    </attributes>")?;
+        }
+        if has_edge_types {
+            writeln!(writer, "    <attributes class=\"edge\">")?;
+            writeln!(
+                writer,
+                "      <attribute id=\"0\" title=\"type\" type=\"string\"/>"
+            )?;
+            writeln!(writer, "    </attributes>")?;
+        }
+        writeln!(writer, "    <nodes>")?;
+        for (id, node_type) in &nodes {
+            let values: Vec<(usize, &AttributeValue)> = attributes
+                .iter()
+                .enumerate()
+                .filter_map(|(i, attribute)| attribute.values.get(id).map(|value| (i, value)))
+                .collect();
+            write!(writer, "      <node id=\"{0}\" label=\"{0}\"", id.value())?;
+            if node_type.is_none() && values.is_empty() {
+                writeln!(writer, "/>")?;
+                continue;
+            }
+            write!(writer, "><attvalues>")?;
+            if let Some(node_type) = node_type {
+                write!(
+                    writer,
+                    "<attvalue for=\"0\" value=\"{}\"/>",
+                    escape_xml(node_type)
+                )?;
+            }
+            for (i, value) in values {
+                write!(
+                    writer,
+                    "<attvalue for=\"{}\" value=\"{}\"/>",
+                    i + offset,
+                    escape_xml(&value.to_string())
+                )?;
+            }
+            writeln!(writer, "</attvalues></node>")?;
+        }
+        writeln!(writer, "    </nodes>")?;
+        writeln!(writer, "    <edges>")?;
+        for (i, edge) in edges.iter().enumerate() {
+            write!(
+                writer,
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\"",
+                i,
+                edge.source_id.value(),
+                edge.target_id.value()
+            )?;
+            if let Some(weight) = edge.weight {
+                write!(writer, " weight=\"{}\"", weight)?;
+            }
+            match &edge.edge_type {
+                Some(edge_type) => writeln!(
+                    writer,
+                    "><attvalues><attvalue for=\"0\" value=\"{}\"/></attvalues></edge>",
+                    escape_xml(edge_type)
+                )?,
+                None => writeln!(writer, "/>")?,
+            }
+        }
+        writeln!(writer, "    </edges>")?;
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</gexf>")?;
+        Ok(())
+    }
+
+    /// the GraphML document as a string, without the final newline.
+    fn as_graphml(&self, attributes: &[NodeAttribute]) -> String {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_graphml(&mut buffer, attributes).unwrap();
+        as_document(buffer)
+    }
+    /// the GEXF document as a string, without the final newline.
+    fn as_gexf(&self, attributes: &[NodeAttribute]) -> String {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_gexf(&mut buffer, attributes).unwrap();
+        as_document(buffer)
+    }
+}
+
+/// documents are written as valid UTF-8, one element per line.
+fn as_document(buffer: Vec<u8>) -> String {
+    let mut document = String::from_utf8(buffer).unwrap();
+    document.pop();
+    document
+}
+
+fn sorted_untyped_nodes<'a>(
+    ids: impl Iterator<Item = &'a NodeId>,
+) -> Vec<(NodeId, Option<String>)> {
+    let ids: BTreeSet<NodeId> = ids.cloned().collect();
+    ids.into_iter().map(|id| (id, None)).collect()
+}
+fn sort_edges(edges: &mut [ExportEdge]) {
+    edges.sort_by_key(|e| (e.source_id, e.target_id));
+}
+
+impl GraphExport for SimpleUndirectedGraph {
+    fn is_directed(&self) -> bool {
+        false
+    }
+    fn get_export_nodes(&self) -> Vec<(NodeId, Option<String>)> {
+        sorted_untyped_nodes(self.nodes.keys())
+    }
+    fn get_export_edges(&self) -> Vec<ExportEdge> {
+        let mut edges: Vec<ExportEdge> = Vec::new();
+        for (id, node) in &self.nodes {
+            for e in node.get_edges() {
+                if *id <= e.get_neighbor_id() {
+                    edges.push(ExportEdge {
+                        source_id: *id,
+                        target_id: e.get_neighbor_id(),
+                        weight: None,
+                        edge_type: None,
+                    });
+                }
+            }
+        }
+        sort_edges(&mut edges);
+        edges
+    }
+}
+impl GraphExport for WeightedUndirectedGraph {
+    fn is_directed(&self) -> bool {
+        false
+    }
+    fn get_export_nodes(&self) -> Vec<(NodeId, Option<String>)> {
+        sorted_untyped_nodes(self.nodes.keys())
+    }
+    fn get_export_edges(&self) -> Vec<ExportEdge> {
+        let mut edges: Vec<ExportEdge> = Vec::new();
+        for (id, node) in &self.nodes {
+            for e in node.get_edges() {
+                if *id <= e.get_neighbor_id() {
+                    edges.push(ExportEdge {
+                        source_id: *id,
+                        target_id: e.get_neighbor_id(),
+                        weight: Some(e.weight),
+                        edge_type: None,
+                    });
+                }
+            }
+        }
+        sort_edges(&mut edges);
+        edges
+    }
+}
+impl GraphExport for SimpleDirectedGraph {
+    fn is_directed(&self) -> bool {
+        true
+    }
+    fn get_export_nodes(&self) -> Vec<(NodeId, Option<String>)> {
+        sorted_untyped_nodes(self.nodes.keys())
+    }
+    fn get_export_edges(&self) -> Vec<ExportEdge> {
+        let mut edges: Vec<ExportEdge> = Vec::new();
+        for (id, node) in &self.nodes {
+            for target_id in &node.out_neighbors {
+                edges.push(ExportEdge {
+                    source_id: *id,
+                    target_id: *target_id,
+                    weight: None,
+                    edge_type: None,
+                });
+            }
+        }
+        sort_edges(&mut edges);
+        edges
+    }
+}
+
+/// Exports a `TypedGraph` with its node and edge types spelled out. Core
+/// nodes are labelled with `core_type`, non-core nodes with the name of
+/// their type in `non_core_type_ids`, and edges with `edge_types`.
+pub struct NamedTypedGraph<'a> {
+    pub graph: &'a TypedGraph,
+    pub core_type: &'a str,
+    pub non_core_type_ids: &'a NonCoreTypeIds,
+    pub edge_types: &'a [String],
+}
+impl<'a> NamedTypedGraph<'a> {
+    pub fn new(
+        graph: &'a TypedGraph,
+        core_type: &'a str,
+        non_core_type_ids: &'a NonCoreTypeIds,
+        edge_types: &'a [String],
+    ) -> Self {
+        Self {
+            graph,
+            core_type,
+            non_core_type_ids,
+            edge_types,
+        }
+    }
+}
+impl<'a> GraphExport for NamedTypedGraph<'a> {
+    fn is_directed(&self) -> bool {
+        false
+    }
+    fn get_export_nodes(&self) -> Vec<(NodeId, Option<String>)> {
+        let mut nodes = self.graph.get_export_nodes();
+        for (id, node_type) in nodes.iter_mut() {
+            *node_type = match self.graph.nodes[id].non_core_type {
+                None => Some(self.core_type.to_owned()),
+                Some(t) => self
+                    .non_core_type_ids
+                    .type_name(&t)
+                    .or_else(|| node_type.take()),
+            };
+        }
+        nodes
+    }
+    fn get_export_edges(&self) -> Vec<ExportEdge> {
+        let mut edges = self.graph.get_export_edges();
+        for edge in edges.iter_mut() {
+            let name = edge
+                .edge_type
+                .as_ref()
+                .and_then(|t| t.parse::<usize>().ok())
+                .and_then(|t| self.edge_types.get(t));
+            if let Some(name) = name {
+                edge.edge_type = Some(name.clone());
+            }
+        }
+        edges
+    }
+}
+
+/// Without type names, core nodes are typed "core", non-core nodes and edges
+/// by their numeric type ids. See `NamedTypedGraph` for named types.
+impl GraphExport for TypedGraph {
+    fn is_directed(&self) -> bool {
+        false
+    }
+    fn get_export_nodes(&self) -> Vec<(NodeId, Option<String>)> {
+        let ids: BTreeSet<NodeId> = self.nodes.keys().cloned().collect();
+        ids.into_iter()
+            .map(|id| {
+                let node_type = match self.nodes[&id].non_core_type {
+                    None => "core".to_string(),
+                    Some(t) => t.value().to_string(),
+                };
+                (id, Some(node_type))
+            })
+            .collect()
+    }
+    /// edges are stored on both endpoints; only those leaving core nodes are written.
+    fn get_export_edges(&self) -> Vec<ExportEdge> {
+        let mut edges: Vec<ExportEdge> = Vec::new();
+        for (id, node) in &self.nodes {
+            if !node.is_core() {
+                continue;
+            }
+            for e in &node.edges {
+                edges.push(ExportEdge {
+                    source_id: *id,
+                    target_id: e.target_id,
+                    weight: None,
+                    edge_type: Some(e.edge_type.value().to_string()),
+                });
+            }
+        }
+        sort_edges(&mut edges);
+        edges
+    }
+}
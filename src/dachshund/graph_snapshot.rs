@@ -0,0 +1,564 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate fxhash;
+extern crate memmap2;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::id_types::{EdgeTypeId, NodeId, NodeTypeId};
+use crate::dachshund::node::{
+    DirectedNodeBase, Node, NodeBase, NodeEdge, NodeEdgeBase, SimpleDirectedNode, SimpleNode,
+    WeightedNode, WeightedNodeEdge,
+};
+use crate::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use crate::dachshund::typed_graph::TypedGraph;
+use crate::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use fxhash::FxHashMap;
+use memmap2::Mmap;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::mem::size_of;
+
+const MAGIC: &[u8; 8] = b"DACHCSR1";
+const HEADER_SIZE: usize = 32;
+const FLAG_DIRECTED: u64 = 1;
+const FLAG_WEIGHTS: u64 = 2;
+const FLAG_NODE_TYPES: u64 = 4;
+const FLAG_EDGE_TYPES: u64 = 8;
+/// node type stored for core nodes of a `TypedGraph`.
+pub const CORE_NODE_TYPE: u32 = u32::MAX;
+
+fn padded(num_bytes: usize) -> usize {
+    num_bytes.div_ceil(8) * 8
+}
+
+/// Byte offsets of the sections of a snapshot file. The layout is:
+///
+/// - header: magic, flags, node count, arc count (u64 each)
+/// - node ids (i64, sorted), CSR offsets (u64, one more than there are nodes)
+/// - neighbor indices (u32), then optionally weights (f64), node types (u32)
+///   and edge types (u32).
+///
+/// Every section starts on an 8-byte boundary; all numbers are little-endian.
+/// Undirected edges are stored as two arcs, directed graphs store out-arcs.
+#[derive(Clone, Copy, Debug)]
+struct Layout {
+    flags: u64,
+    num_nodes: usize,
+    num_arcs: usize,
+    node_ids: usize,
+    offsets: usize,
+    neighbors: usize,
+    weights: usize,
+    node_types: usize,
+    edge_types: usize,
+    size: usize,
+}
+impl Layout {
+    fn new(flags: u64, num_nodes: usize, num_arcs: usize) -> Self {
+        let node_ids = HEADER_SIZE;
+        let offsets = node_ids + num_nodes * size_of::<i64>();
+        let neighbors = offsets + (num_nodes + 1) * size_of::<u64>();
+        let mut end = neighbors + padded(num_arcs * size_of::<u32>());
+        let weights = end;
+        if flags & FLAG_WEIGHTS != 0 {
+            end += num_arcs * size_of::<f64>();
+        }
+        let node_types = end;
+        if flags & FLAG_NODE_TYPES != 0 {
+            end += padded(num_nodes * size_of::<u32>());
+        }
+        let edge_types = end;
+        if flags & FLAG_EDGE_TYPES != 0 {
+            end += padded(num_arcs * size_of::<u32>());
+        }
+        Self {
+            flags,
+            num_nodes,
+            num_arcs,
+            node_ids,
+            offsets,
+            neighbors,
+            weights,
+            node_types,
+            edge_types,
+            size: end,
+        }
+    }
+}
+
+enum SnapshotData {
+    Mapped(Mmap),
+    // u64s, so that the data is suitably aligned for all sections.
+    Owned(Vec<u64>, usize),
+}
+impl SnapshotData {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            SnapshotData::Mapped(mmap) => &mmap[..],
+            SnapshotData::Owned(words, len) => unsafe {
+                std::slice::from_raw_parts(words.as_ptr() as *const u8, *len)
+            },
+        }
+    }
+}
+
+/// A read-only graph in compressed sparse row form, backed either by a
+/// memory-mapped snapshot file or by an in-memory copy of one. Nodes are
+/// addressed by their index (0..num_nodes, in increasing id order).
+pub struct GraphSnapshot {
+    data: SnapshotData,
+    layout: Layout,
+}
+impl GraphSnapshot {
+    /// memory-maps a snapshot file. The file must not be modified while mapped.
+    pub fn open(path: &str) -> CLQResult<Self> {
+        let file = File::open(path)?;
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::new(SnapshotData::Mapped(mmap))
+    }
+    pub fn from_bytes(bytes: &[u8]) -> CLQResult<Self> {
+        let mut words: Vec<u64> = vec![0; padded(bytes.len()) / 8];
+        for (i, chunk) in bytes.chunks(8).enumerate() {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            words[i] = u64::from_ne_bytes(word);
+        }
+        Self::new(SnapshotData::Owned(words, bytes.len()))
+    }
+    fn new(data: SnapshotData) -> CLQResult<Self> {
+        if cfg!(target_endian = "big") {
+            return Err("Graph snapshots can only be read on little-endian machines".into());
+        }
+        let bytes = data.bytes();
+        if bytes.len() < HEADER_SIZE || &bytes[..8] != MAGIC {
+            return Err("Not a graph snapshot".into());
+        }
+        if !(bytes.as_ptr() as usize).is_multiple_of(8) {
+            return Err("Graph snapshot data is not 8-byte aligned".into());
+        }
+        let read_u64 = |pos: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[pos..pos + 8]);
+            u64::from_le_bytes(word)
+        };
+        let (num_nodes, num_arcs) = (read_u64(16) as usize, read_u64(24) as usize);
+        // guards the layout computation against nonsensical headers.
+        if num_nodes > bytes.len() || num_arcs > bytes.len() {
+            return Err("Graph snapshot header does not match its size".into());
+        }
+        let layout = Layout::new(read_u64(8), num_nodes, num_arcs);
+        if bytes.len() != layout.size {
+            return Err(format!(
+                "Graph snapshot should be {} bytes long, found {}",
+                layout.size,
+                bytes.len()
+            )
+            .into());
+        }
+        let snapshot = Self { data, layout };
+        let offsets = snapshot.offsets();
+        if offsets[0] != 0
+            || offsets[layout.num_nodes] as usize != layout.num_arcs
+            || offsets.windows(2).any(|w| w[0] > w[1])
+        {
+            return Err("Graph snapshot has invalid offsets".into());
+        }
+        let neighbors = snapshot.section::<u32>(layout.neighbors, layout.num_arcs);
+        if neighbors.iter().any(|x| *x as usize >= layout.num_nodes) {
+            return Err("Graph snapshot has invalid neighbor indices".into());
+        }
+        if snapshot.node_ids().windows(2).any(|w| w[0] >= w[1]) {
+            return Err("Graph snapshot node ids are not sorted".into());
+        }
+        Ok(snapshot)
+    }
+    /// an undirected snapshot without nodes.
+    pub fn create_empty() -> Self {
+        let mut bytes = MAGIC.to_vec();
+        bytes.resize(Layout::new(0, 0, 0).size, 0);
+        Self::from_bytes(&bytes).unwrap()
+    }
+    fn section<T>(&self, start: usize, len: usize) -> &[T] {
+        let bytes = &self.data.bytes()[start..start + len * size_of::<T>()];
+        // sections are 8-byte aligned (checked on load) and sized by the layout.
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, len) }
+    }
+    fn has_flag(&self, flag: u64) -> bool {
+        self.layout.flags & flag != 0
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.layout.num_nodes
+    }
+    pub fn num_arcs(&self) -> usize {
+        self.layout.num_arcs
+    }
+    /// edges of an undirected graph are counted once.
+    pub fn count_edges(&self) -> usize {
+        match self.is_directed() {
+            true => self.num_arcs(),
+            false => self.num_arcs() / 2,
+        }
+    }
+    pub fn is_directed(&self) -> bool {
+        self.has_flag(FLAG_DIRECTED)
+    }
+    pub fn has_weights(&self) -> bool {
+        self.has_flag(FLAG_WEIGHTS)
+    }
+    pub fn has_node_types(&self) -> bool {
+        self.has_flag(FLAG_NODE_TYPES)
+    }
+    pub fn has_edge_types(&self) -> bool {
+        self.has_flag(FLAG_EDGE_TYPES)
+    }
+    pub fn node_ids(&self) -> &[i64] {
+        self.section(self.layout.node_ids, self.layout.num_nodes)
+    }
+    /// the same ids as `node_ids`, as `NodeId`s.
+    pub fn ids(&self) -> &[NodeId] {
+        self.section(self.layout.node_ids, self.layout.num_nodes)
+    }
+    pub fn offsets(&self) -> &[u64] {
+        self.section(self.layout.offsets, self.layout.num_nodes + 1)
+    }
+    pub fn get_node_id(&self, index: usize) -> NodeId {
+        NodeId::from(self.node_ids()[index])
+    }
+    pub fn get_index(&self, node_id: NodeId) -> Option<usize> {
+        self.node_ids().binary_search(&node_id.value()).ok()
+    }
+    fn arc_range(&self, index: usize) -> (usize, usize) {
+        let offsets = self.offsets();
+        (offsets[index] as usize, offsets[index + 1] as usize)
+    }
+    pub fn degree(&self, index: usize) -> usize {
+        let (start, end) = self.arc_range(index);
+        end - start
+    }
+    /// neighbor indices, in increasing order.
+    pub fn neighbors(&self, index: usize) -> &[u32] {
+        let (start, end) = self.arc_range(index);
+        &self.section::<u32>(self.layout.neighbors, self.layout.num_arcs)[start..end]
+    }
+    pub fn neighbor_ids(&self, index: usize) -> impl Iterator<Item = NodeId> + '_ {
+        let ids = self.ids();
+        self.neighbors(index).iter().map(move |x| ids[*x as usize])
+    }
+    /// weights of the arcs returned by `neighbors`, if the snapshot has any.
+    pub fn weights(&self, index: usize) -> Option<&[f64]> {
+        if !self.has_weights() {
+            return None;
+        }
+        let (start, end) = self.arc_range(index);
+        Some(&self.section::<f64>(self.layout.weights, self.layout.num_arcs)[start..end])
+    }
+    /// `CORE_NODE_TYPE` for core nodes of typed graphs.
+    pub fn node_type(&self, index: usize) -> Option<u32> {
+        if !self.has_node_types() {
+            return None;
+        }
+        Some(self.section::<u32>(self.layout.node_types, self.layout.num_nodes)[index])
+    }
+    /// edge types of the arcs returned by `neighbors`, if the snapshot has any.
+    pub fn edge_types(&self, index: usize) -> Option<&[u32]> {
+        if !self.has_edge_types() {
+            return None;
+        }
+        let (start, end) = self.arc_range(index);
+        Some(&self.section::<u32>(self.layout.edge_types, self.layout.num_arcs)[start..end])
+    }
+}
+
+/// Writes any graph as a `GraphSnapshot` file. Implementors say what extra
+/// data their edges and nodes carry; structure comes from `GraphBase`.
+pub trait WriteSnapshot: GraphBase {
+    const DIRECTED: bool = false;
+    const WEIGHTED: bool = false;
+    const TYPED: bool = false;
+
+    /// the edges stored as arcs of a node.
+    fn get_snapshot_arcs(
+        node: &Self::NodeType,
+    ) -> Box<dyn Iterator<Item = &<Self::NodeType as NodeBase>::NodeEdgeType> + '_> {
+        node.get_outgoing_edges()
+    }
+    fn get_arc_weight(_edge: &<Self::NodeType as NodeBase>::NodeEdgeType) -> f64 {
+        1.0
+    }
+    fn get_arc_type(_edge: &<Self::NodeType as NodeBase>::NodeEdgeType) -> u32 {
+        0
+    }
+    fn get_snapshot_node_type(_node: &Self::NodeType) -> u32 {
+        0
+    }
+
+    fn write_snapshot<W: Write>(&self, writer: &mut W) -> CLQResult<()> {
+        let node_ids = self.get_ordered_node_ids();
+        if node_ids.len() >= u32::MAX as usize {
+            return Err(CLQError::from("Too many nodes for a graph snapshot"));
+        }
+        let index: FxHashMap<NodeId, u32> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i as u32))
+            .collect();
+        let mut offsets: Vec<u64> = vec![0];
+        let mut neighbors: Vec<u32> = Vec::new();
+        let mut weights: Vec<f64> = Vec::new();
+        let mut edge_types: Vec<u32> = Vec::new();
+        for id in &node_ids {
+            let mut arcs: Vec<(u32, f64, u32)> = Self::get_snapshot_arcs(self.get_node(*id))
+                .map(|e| {
+                    (
+                        index[&e.get_neighbor_id()],
+                        Self::get_arc_weight(e),
+                        Self::get_arc_type(e),
+                    )
+                })
+                .collect();
+            arcs.sort_by_key(|x| (x.0, x.2));
+            for (neighbor, weight, edge_type) in arcs {
+                neighbors.push(neighbor);
+                weights.push(weight);
+                edge_types.push(edge_type);
+            }
+            offsets.push(neighbors.len() as u64);
+        }
+        let flags = (Self::DIRECTED as u64 * FLAG_DIRECTED)
+            | (Self::WEIGHTED as u64 * FLAG_WEIGHTS)
+            | (Self::TYPED as u64 * (FLAG_NODE_TYPES | FLAG_EDGE_TYPES));
+        let layout = Layout::new(flags, node_ids.len(), neighbors.len());
+
+        let mut written: usize = 0;
+        let mut write = |bytes: &[u8]| -> CLQResult<()> {
+            writer.write_all(bytes)?;
+            written += bytes.len();
+            Ok(())
+        };
+        write(MAGIC)?;
+        for x in &[flags, node_ids.len() as u64, neighbors.len() as u64] {
+            write(&x.to_le_bytes())?;
+        }
+        for id in &node_ids {
+            write(&id.value().to_le_bytes())?;
+        }
+        for x in &offsets {
+            write(&x.to_le_bytes())?;
+        }
+        let padding = |len: usize| vec![0u8; padded(len) - len];
+        for x in &neighbors {
+            write(&x.to_le_bytes())?;
+        }
+        write(&padding(neighbors.len() * size_of::<u32>()))?;
+        if Self::WEIGHTED {
+            for x in &weights {
+                write(&x.to_le_bytes())?;
+            }
+        }
+        if Self::TYPED {
+            for id in &node_ids {
+                write(&Self::get_snapshot_node_type(self.get_node(*id)).to_le_bytes())?;
+            }
+            write(&padding(node_ids.len() * size_of::<u32>()))?;
+            for x in &edge_types {
+                write(&x.to_le_bytes())?;
+            }
+            write(&padding(edge_types.len() * size_of::<u32>()))?;
+        }
+        debug_assert_eq!(written, layout.size);
+        Ok(())
+    }
+    fn save_snapshot(&self, path: &str) -> CLQResult<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_snapshot(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl WriteSnapshot for SimpleUndirectedGraph {}
+impl WriteSnapshot for SimpleDirectedGraph {
+    const DIRECTED: bool = true;
+    /// `get_outgoing_edges` also returns in-neighbors.
+    fn get_snapshot_arcs(node: &SimpleDirectedNode) -> Box<dyn Iterator<Item = &NodeId> + '_> {
+        node.get_out_neighbors()
+    }
+}
+impl WriteSnapshot for WeightedUndirectedGraph {
+    const WEIGHTED: bool = true;
+    fn get_arc_weight(edge: &WeightedNodeEdge) -> f64 {
+        edge.weight
+    }
+}
+impl WriteSnapshot for TypedGraph {
+    const TYPED: bool = true;
+    fn get_arc_type(edge: &NodeEdge) -> u32 {
+        edge.edge_type.value() as u32
+    }
+    fn get_snapshot_node_type(node: &Node) -> u32 {
+        match node.non_core_type {
+            None => CORE_NODE_TYPE,
+            Some(t) => t.value() as u32,
+        }
+    }
+}
+
+/// Reads a graph back from a `GraphSnapshot`, such as one written by its
+/// `WriteSnapshot` implementation.
+pub trait ReadSnapshot: WriteSnapshot {
+    /// builds the graph from a snapshot known to carry the data it needs.
+    fn _from_snapshot(snapshot: &GraphSnapshot) -> Self;
+
+    fn from_snapshot(snapshot: &GraphSnapshot) -> CLQResult<Self> {
+        let kind = |directed: bool| match directed {
+            true => "directed",
+            false => "undirected",
+        };
+        if snapshot.is_directed() != Self::DIRECTED {
+            return Err(format!(
+                "Cannot read a {} snapshot into a {} graph",
+                kind(snapshot.is_directed()),
+                kind(Self::DIRECTED)
+            )
+            .into());
+        }
+        if Self::WEIGHTED && !snapshot.has_weights() {
+            return Err("Graph snapshot has no weights".into());
+        }
+        if Self::TYPED && !(snapshot.has_node_types() && snapshot.has_edge_types()) {
+            return Err("Graph snapshot has no node and edge types".into());
+        }
+        Ok(Self::_from_snapshot(snapshot))
+    }
+    fn load_snapshot(path: &str) -> CLQResult<Self> {
+        Self::from_snapshot(&GraphSnapshot::open(path)?)
+    }
+}
+
+impl ReadSnapshot for SimpleUndirectedGraph {
+    fn _from_snapshot(snapshot: &GraphSnapshot) -> Self {
+        let ids = snapshot.ids();
+        let nodes = (0..ids.len())
+            .map(|i| {
+                let node = SimpleNode {
+                    node_id: ids[i],
+                    neighbors: snapshot.neighbor_ids(i).collect(),
+                };
+                (ids[i], node)
+            })
+            .collect();
+        SimpleUndirectedGraph {
+            nodes,
+            ids: ids.to_vec(),
+        }
+    }
+}
+impl ReadSnapshot for SimpleDirectedGraph {
+    /// in-neighbors are recovered from the stored out-arcs.
+    fn _from_snapshot(snapshot: &GraphSnapshot) -> Self {
+        let ids = snapshot.ids();
+        let mut nodes: FxHashMap<NodeId, SimpleDirectedNode> = ids
+            .iter()
+            .map(|id| {
+                let node = SimpleDirectedNode {
+                    node_id: *id,
+                    in_neighbors: BTreeSet::new(),
+                    out_neighbors: BTreeSet::new(),
+                };
+                (*id, node)
+            })
+            .collect();
+        for (i, id) in ids.iter().enumerate() {
+            for neighbor_id in snapshot.neighbor_ids(i) {
+                nodes.get_mut(id).unwrap().out_neighbors.insert(neighbor_id);
+                nodes
+                    .get_mut(&neighbor_id)
+                    .unwrap()
+                    .in_neighbors
+                    .insert(*id);
+            }
+        }
+        SimpleDirectedGraph {
+            nodes,
+            ids: ids.to_vec(),
+        }
+    }
+}
+impl ReadSnapshot for WeightedUndirectedGraph {
+    fn _from_snapshot(snapshot: &GraphSnapshot) -> Self {
+        let ids = snapshot.ids();
+        let nodes = (0..ids.len())
+            .map(|i| {
+                let edges = snapshot
+                    .neighbor_ids(i)
+                    .zip(snapshot.weights(i).unwrap())
+                    .map(|(target_id, weight)| WeightedNodeEdge::new(target_id, *weight))
+                    .collect();
+                let node = WeightedNode {
+                    node_id: ids[i],
+                    edges,
+                    neighbors: snapshot.neighbor_ids(i).collect(),
+                };
+                (ids[i], node)
+            })
+            .collect();
+        WeightedUndirectedGraph {
+            nodes,
+            ids: ids.to_vec(),
+        }
+    }
+}
+impl ReadSnapshot for TypedGraph {
+    /// node types only keep their value: snapshots do not store the
+    /// bookkeeping of `NodeTypeId`s.
+    fn _from_snapshot(snapshot: &GraphSnapshot) -> Self {
+        let ids = snapshot.ids();
+        let mut core_ids: Vec<NodeId> = Vec::new();
+        let mut non_core_ids: Vec<NodeId> = Vec::new();
+        let mut nodes: FxHashMap<NodeId, Node> = FxHashMap::default();
+        for (i, id) in ids.iter().enumerate() {
+            let non_core_type = match snapshot.node_type(i).unwrap() {
+                CORE_NODE_TYPE => None,
+                node_type => Some(NodeTypeId::from(node_type as usize)),
+            };
+            match non_core_type {
+                None => core_ids.push(*id),
+                Some(_) => non_core_ids.push(*id),
+            }
+            let arcs: Vec<(NodeId, EdgeTypeId)> = snapshot
+                .neighbor_ids(i)
+                .zip(snapshot.edge_types(i).unwrap())
+                .map(|(target_id, edge_type)| (target_id, EdgeTypeId::from(*edge_type as usize)))
+                .collect();
+            let mut neighbors: HashMap<NodeId, Vec<NodeEdge>> = HashMap::new();
+            for (target_id, edge_type) in &arcs {
+                neighbors
+                    .entry(*target_id)
+                    .or_default()
+                    .push(NodeEdge::new(*edge_type, *target_id));
+            }
+            let edges = arcs
+                .into_iter()
+                .map(|(target_id, edge_type)| NodeEdge::new(edge_type, target_id))
+                .collect();
+            let is_core = non_core_type.is_none();
+            nodes.insert(
+                *id,
+                Node::new(*id, is_core, non_core_type, edges, neighbors),
+            );
+        }
+        TypedGraph {
+            nodes,
+            core_ids,
+            non_core_ids,
+        }
+    }
+}
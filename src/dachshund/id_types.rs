@@ -66,7 +66,9 @@ where
 }
 
 /// Uniquely identifies a `Node`, relative an existing `Graph`.
+// transparent, so that graph snapshots can hand out their i64 ids in place.
 #[derive(Hash, Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
+#[repr(transparent)]
 pub struct NodeId {
     id: i64,
 }
@@ -100,6 +102,14 @@ impl GraphId {
     pub fn value(&self) -> i64 {
         self.id
     }
+    /// a stable id for an original graph key: a fixed (FNV-1a) hash of its
+    /// bytes, so that e.g. "7" and "007" are told apart.
+    pub fn from_key(key: &str) -> Self {
+        let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        Self { id: hash as i64 }
+    }
 }
 impl<T> From<T> for GraphId
 where
@@ -5,33 +5,94 @@
  * LICENSE file in the root directory of this source tree.
  */
 // see https://stackoverflow.com/questions/36088116/how-to-do-polymorphic-io-from-either-a-file-or-stdin-in-rust
+extern crate clap;
+
+use clap::{Arg, ArgMatches};
+
+use crate::dachshund::compression::Compression;
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::input_format::InputFormat;
+use crate::dachshund::input_ordering::InputOrdering;
 use std::fs::File;
 use std::io::{self, BufRead, Read};
 use std::os::unix::io::FromRawFd;
 pub struct Input<'a> {
     source: Box<dyn BufRead + 'a>,
+    format: InputFormat,
+    ordering: InputOrdering,
 }
 
 impl<'a> Input<'a> {
-    pub fn console(_stdin: &'a io::Stdin) -> Input<'a> {
+    /// reads stdin, decompressing gzip or zstd input.
+    pub fn console(_stdin: &'a io::Stdin) -> io::Result<Input<'a>> {
+        Input::stdin()
+    }
+
+    fn stdin() -> io::Result<Input<'static>> {
         let stdin = unsafe { File::from_raw_fd(0) };
-        let reader = io::BufReader::new(stdin);
-        Input {
-            source: Box::new(reader),
-        }
+        Input::reader(stdin)
     }
 
+    /// reads a file, decompressing it if it is gzip or zstd compressed
+    /// (recognized by content rather than name).
     pub fn file(path: &str) -> io::Result<Input<'a>> {
-        File::open(path).map(|file| Input {
-            source: Box::new(io::BufReader::new(file)),
+        Input::reader(File::open(path)?)
+    }
+
+    /// reads from any source, decompressing gzip or zstd input.
+    pub fn reader<R: Read + 'a>(reader: R) -> io::Result<Input<'a>> {
+        Ok(Input {
+            source: Compression::decode(reader)?,
+            format: InputFormat::default(),
+            ordering: InputOrdering::default(),
         })
     }
 
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![Arg::with_name("input")
+            .long("input")
+            .short("i")
+            .takes_value(true)
+            .help("File to read input from (stdin by default). May be gzip or zstd compressed.")]
+    }
+
+    /// opens the input named on the command line, or standard input.
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Input<'static>> {
+        let input = match matches.value_of("input") {
+            Some(path) => Input::file(path),
+            None => Input::stdin(),
+        };
+        input.map_err(|e| CLQError::from(format!("Could not open input: {}", e)))
+    }
+
     pub fn string(text: &'a [u8]) -> Input<'a> {
         Input {
             source: Box::new(text),
+            format: InputFormat::default(),
+            ordering: InputOrdering::default(),
         }
     }
+
+    /// sets the layout of input lines (native tab-separated format by default).
+    pub fn with_format(mut self, format: InputFormat) -> Input<'a> {
+        self.format = format;
+        self
+    }
+
+    pub fn get_format(&self) -> &InputFormat {
+        &self.format
+    }
+
+    /// sets whether rows come grouped by graph id (the default) or interleaved.
+    pub fn with_ordering(mut self, ordering: InputOrdering) -> Input<'a> {
+        self.ordering = ordering;
+        self
+    }
+
+    pub fn get_ordering(&self) -> &InputOrdering {
+        &self.ordering
+    }
 }
 
 impl<'a> Read for Input<'a> {
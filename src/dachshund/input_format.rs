@@ -0,0 +1,296 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+
+use clap::{Arg, ArgMatches};
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use std::collections::HashMap;
+
+/// How fields are separated on an input line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delimiter {
+    Char(char),
+    /// any run of whitespace separates fields; leading and trailing whitespace is ignored.
+    Whitespace,
+}
+impl Delimiter {
+    pub fn parse(s: &str) -> CLQResult<Self> {
+        match s {
+            "tab" | "\\t" => Ok(Delimiter::Char('\t')),
+            "comma" => Ok(Delimiter::Char(',')),
+            "space" => Ok(Delimiter::Char(' ')),
+            "whitespace" => Ok(Delimiter::Whitespace),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Delimiter::Char(c)),
+                    _ => Err(format!("Invalid delimiter: {}", s).into()),
+                }
+            }
+        }
+    }
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Delimiter::Char(d) => c == *d,
+            Delimiter::Whitespace => c.is_whitespace(),
+        }
+    }
+}
+
+/// Describes the layout of input lines. The default (tab-separated, no header,
+/// no comments, no quoting, columns in the order each line processor expects)
+/// is the native dachshund format.
+#[derive(Clone, Debug)]
+pub struct InputFormat {
+    pub delimiter: Delimiter,
+    /// whether the first (non-comment) line names the columns.
+    pub has_header: bool,
+    /// lines starting with this character are ignored.
+    pub comment_char: Option<char>,
+    /// fields may be enclosed in this character, in which case they can contain
+    /// the delimiter. A doubled quote character inside a quoted field is a literal quote.
+    pub quote_char: Option<char>,
+    /// maps the column names a line processor expects (e.g. "source_id") to either
+    /// a column name in the header, or a 0-based column index.
+    pub column_map: HashMap<String, String>,
+}
+impl Default for InputFormat {
+    fn default() -> Self {
+        Self {
+            delimiter: Delimiter::Char('\t'),
+            has_header: false,
+            comment_char: None,
+            quote_char: None,
+            column_map: HashMap::new(),
+        }
+    }
+}
+impl InputFormat {
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![
+            Arg::with_name("delimiter")
+                .long("delimiter")
+                .takes_value(true)
+                .help(
+                    "Field delimiter: tab (default), comma, space, whitespace (any run of \
+                     whitespace), or any single character.",
+                ),
+            Arg::with_name("header")
+                .long("header")
+                .help("Treat the first line of input as a header naming the columns."),
+            Arg::with_name("columns")
+                .long("columns")
+                .takes_value(true)
+                .help(
+                    "Comma-separated column mapping, e.g. graph_id=g,source_id=src,target_id=2. \
+                     Values are header names or 0-based column indices. With --header and no \
+                     mapping, columns are matched by name if the header contains all of them.",
+                ),
+            Arg::with_name("comment_char")
+                .long("comment_char")
+                .takes_value(true)
+                .help("Ignore lines starting with this character, e.g. '#'."),
+            Arg::with_name("quote_char")
+                .long("quote_char")
+                .takes_value(true)
+                .help("Fields enclosed in this character may contain the delimiter, e.g. '\"'."),
+        ]
+    }
+    /// constructs a format from an ArgMatches object (to help with command line arguments).
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        let single_char = |name: &str| -> CLQResult<Option<char>> {
+            match matches.value_of(name) {
+                None => Ok(None),
+                Some(s) => {
+                    let mut chars = s.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Ok(Some(c)),
+                        _ => Err(format!("--{} must be a single character", name).into()),
+                    }
+                }
+            }
+        };
+        let mut column_map: HashMap<String, String> = HashMap::new();
+        if let Some(columns) = matches.value_of("columns") {
+            for item in columns.split(',') {
+                let mut kv = item.splitn(2, '=');
+                match (kv.next(), kv.next()) {
+                    (Some(k), Some(v)) => {
+                        column_map.insert(k.trim().to_owned(), v.trim().to_owned());
+                    }
+                    _ => return Err(format!("Invalid column mapping: {}", item).into()),
+                }
+            }
+        }
+        Ok(Self {
+            delimiter: match matches.value_of("delimiter") {
+                Some(d) => Delimiter::parse(d)?,
+                None => Delimiter::Char('\t'),
+            },
+            has_header: matches.is_present("header"),
+            comment_char: single_char("comment_char")?,
+            quote_char: single_char("quote_char")?,
+            column_map,
+        })
+    }
+    /// whether lines are already in native format, and can be passed through untouched.
+    pub fn is_native(&self) -> bool {
+        self.delimiter == Delimiter::Char('\t')
+            && !self.has_header
+            && self.comment_char.is_none()
+            && self.quote_char.is_none()
+            && self.column_map.is_empty()
+    }
+    pub fn is_comment(&self, line: &str) -> bool {
+        match self.comment_char {
+            Some(c) => line.trim_start().starts_with(c),
+            None => false,
+        }
+    }
+    /// splits a line into fields, honoring delimiter and quoting rules.
+    pub fn split_fields(&self, line: &str) -> Vec<String> {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let is_whitespace = self.delimiter == Delimiter::Whitespace;
+        let mut fields: Vec<String> = Vec::new();
+        let mut field = String::new();
+        // only used for whitespace delimiters, where runs of delimiters collapse.
+        let mut field_started = false;
+        let mut in_quotes = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_quotes {
+                if Some(c) == self.quote_char {
+                    if chars.peek() == Some(&c) {
+                        field.push(c);
+                        chars.next();
+                    } else {
+                        in_quotes = false;
+                    }
+                } else {
+                    field.push(c);
+                }
+            } else if Some(c) == self.quote_char {
+                in_quotes = true;
+                field_started = true;
+            } else if self.delimiter.matches(c) {
+                if !is_whitespace || field_started {
+                    fields.push(std::mem::take(&mut field));
+                    field_started = false;
+                }
+            } else {
+                field.push(c);
+                field_started = true;
+            }
+        }
+        if !is_whitespace || field_started {
+            fields.push(field);
+        }
+        fields
+    }
+}
+
+/// Turns lines in an arbitrary `InputFormat` into the native, tab-separated format
+/// expected by a line processor with the given column names. Keeps track of whether
+/// the header has been seen yet.
+pub struct LineNormalizer {
+    format: InputFormat,
+    column_names: Vec<&'static str>,
+    // input column index for each column the line processor expects. None means
+    // columns are already in the expected order.
+    column_indices: Option<Vec<usize>>,
+    awaiting_header: bool,
+}
+impl LineNormalizer {
+    pub fn new(format: InputFormat, column_names: Vec<&'static str>) -> CLQResult<Self> {
+        let awaiting_header = format.has_header;
+        let mut normalizer = Self {
+            format,
+            column_names,
+            column_indices: None,
+            awaiting_header,
+        };
+        if !awaiting_header {
+            normalizer.column_indices = normalizer.resolve_columns(None)?;
+        }
+        Ok(normalizer)
+    }
+    fn resolve_columns(&self, header: Option<&[String]>) -> CLQResult<Option<Vec<usize>>> {
+        let position_in_header =
+            |name: &str| header.and_then(|h| h.iter().position(|x| x.trim() == name));
+        if self.format.column_map.is_empty() {
+            // match by name only if the header names every column we need.
+            let by_name: Option<Vec<usize>> = self
+                .column_names
+                .iter()
+                .map(|name| position_in_header(name))
+                .collect();
+            return Ok(by_name);
+        }
+        for name in self.format.column_map.keys() {
+            if !self.column_names.contains(&name.as_str()) {
+                return Err(format!(
+                    "Unknown column in mapping: {} (expected one of {:?})",
+                    name, self.column_names
+                )
+                .into());
+            }
+        }
+        let mut indices: Vec<usize> = Vec::with_capacity(self.column_names.len());
+        for name in &self.column_names {
+            let idx = match self.format.column_map.get(*name) {
+                Some(source) => position_in_header(source)
+                    .or_else(|| source.parse::<usize>().ok())
+                    .ok_or_else(|| {
+                        CLQError::from(format!("Column {} not found for {}", source, name))
+                    })?,
+                None => position_in_header(name)
+                    .ok_or_else(|| CLQError::from(format!("No column given for {}", name)))?,
+            };
+            indices.push(idx);
+        }
+        Ok(Some(indices))
+    }
+    /// whether the next row-bearing line is expected to be the header.
+    pub fn awaiting_header(&self) -> bool {
+        self.awaiting_header
+    }
+    /// returns None for lines that carry no row (comments, blank lines, the header).
+    pub fn normalize(&mut self, line: &str) -> CLQResult<Option<String>> {
+        if self.format.is_native() {
+            return Ok(Some(line.to_owned()));
+        }
+        if line.trim().is_empty() || self.format.is_comment(line) {
+            return Ok(None);
+        }
+        let fields = self.format.split_fields(line);
+        if self.awaiting_header {
+            self.column_indices = self.resolve_columns(Some(&fields))?;
+            self.awaiting_header = false;
+            return Ok(None);
+        }
+        // a (quoted) tab would split the field again in the native format.
+        if let Some(field) = fields.iter().find(|x| x.contains('\t')) {
+            return Err(format!("Field contains a tab: {:?}", field).into());
+        }
+        match &self.column_indices {
+            None => Ok(Some(fields.join("\t"))),
+            Some(indices) => {
+                let mut reordered: Vec<&str> = Vec::with_capacity(indices.len());
+                for idx in indices {
+                    let field = fields.get(*idx).ok_or(CLQError::FieldCount {
+                        expected: idx + 1,
+                        found: fields.len(),
+                    })?;
+                    reordered.push(field);
+                }
+                Ok(Some(reordered.join("\t")))
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+
+use clap::{Arg, ArgMatches};
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::GraphId;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_MAX_BUFFERED_ROWS: usize = 1_000_000;
+/// number of files rows are hash-partitioned into (by graph id) once they spill.
+const NUM_SPILL_PARTITIONS: usize = 64;
+
+/// How rows belonging to the same graph are laid out in the input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputOrdering {
+    /// all rows of a graph are contiguous. A graph id showing up again after
+    /// rows of another graph is an error, rather than being split into two graphs.
+    #[default]
+    Grouped,
+    /// rows of different graphs may be interleaved. Rows are buffered by graph
+    /// id until input is exhausted; beyond `max_buffered_rows` rows the buffer
+    /// spills to files in `spill_dir`.
+    Unsorted {
+        max_buffered_rows: usize,
+        spill_dir: PathBuf,
+    },
+}
+impl InputOrdering {
+    pub fn unsorted() -> Self {
+        InputOrdering::Unsorted {
+            max_buffered_rows: DEFAULT_MAX_BUFFERED_ROWS,
+            spill_dir: std::env::temp_dir(),
+        }
+    }
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![
+            Arg::with_name("unsorted").long("unsorted").help(
+                "Input rows are not grouped by graph id: buffer them per graph \
+                     and process graphs once input is exhausted.",
+            ),
+            Arg::with_name("max_buffered_rows")
+                .long("max_buffered_rows")
+                .takes_value(true)
+                .help("With --unsorted, number of rows kept in memory before spilling to disk."),
+            Arg::with_name("spill_dir")
+                .long("spill_dir")
+                .takes_value(true)
+                .help("With --unsorted, directory for spilled rows (system temp dir by default)."),
+        ]
+    }
+    /// constructs an ordering from an ArgMatches object (to help with command line arguments).
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        if !matches.is_present("unsorted") {
+            return Ok(InputOrdering::Grouped);
+        }
+        let max_buffered_rows = match matches.value_of("max_buffered_rows") {
+            Some(value) => value
+                .parse::<usize>()
+                .map_err(|_| CLQError::from(format!("Invalid --max_buffered_rows: {}", value)))?,
+            None => DEFAULT_MAX_BUFFERED_ROWS,
+        };
+        let spill_dir = match matches.value_of("spill_dir") {
+            Some(path) => PathBuf::from(path),
+            None => std::env::temp_dir(),
+        };
+        Ok(InputOrdering::Unsorted {
+            max_buffered_rows,
+            spill_dir,
+        })
+    }
+}
+
+/// Collects (normalized) input lines by graph id, for input that is not grouped
+/// by graph. Lines are held in memory up to a limit, after which everything is
+/// written out to partition files, hash-partitioned by graph id.
+pub struct GraphRowBuffer {
+    max_buffered_rows: usize,
+    spill_root: PathBuf,
+    spill_dir: Option<PathBuf>,
+    // graph ids in order of first appearance, with their buffered lines.
+    order: Vec<GraphId>,
+    rows: HashMap<GraphId, Vec<String>>,
+    num_buffered: usize,
+}
+impl GraphRowBuffer {
+    pub fn new(max_buffered_rows: usize, spill_root: PathBuf) -> Self {
+        Self {
+            max_buffered_rows,
+            spill_root,
+            spill_dir: None,
+            order: Vec::new(),
+            rows: HashMap::new(),
+            num_buffered: 0,
+        }
+    }
+    pub fn has_spilled(&self) -> bool {
+        self.spill_dir.is_some()
+    }
+    pub fn push(&mut self, graph_id: GraphId, line: String) -> CLQResult<()> {
+        let order = &mut self.order;
+        self.rows
+            .entry(graph_id)
+            .or_insert_with(|| {
+                order.push(graph_id);
+                Vec::new()
+            })
+            .push(line);
+        self.num_buffered += 1;
+        if self.num_buffered > self.max_buffered_rows {
+            self.spill()?;
+        }
+        Ok(())
+    }
+    fn get_partition(graph_id: GraphId) -> usize {
+        graph_id.value().rem_euclid(NUM_SPILL_PARTITIONS as i64) as usize
+    }
+    fn get_spill_dir(&mut self) -> CLQResult<PathBuf> {
+        if let Some(dir) = &self.spill_dir {
+            return Ok(dir.clone());
+        }
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_nanos())
+            .unwrap_or(0);
+        let dir = self
+            .spill_root
+            .join(format!("dachshund_spill_{}_{}", std::process::id(), nanos));
+        fs::create_dir_all(&dir)?;
+        self.spill_dir = Some(dir.clone());
+        Ok(dir)
+    }
+    /// appends all buffered lines to their partition files.
+    fn spill(&mut self) -> CLQResult<()> {
+        let dir = self.get_spill_dir()?;
+        let mut writers: HashMap<usize, BufWriter<File>> = HashMap::new();
+        for graph_id in self.order.drain(..) {
+            let partition = Self::get_partition(graph_id);
+            let writer = match writers.entry(partition) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let file = OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(dir.join(format!("part_{}", partition)))?;
+                    entry.insert(BufWriter::new(file))
+                }
+            };
+            // the graph id goes along, so that lines need not be parsed again for it.
+            for line in self.rows.remove(&graph_id).unwrap() {
+                writeln!(writer, "{}\t{}", graph_id.value(), line)?;
+            }
+        }
+        for writer in writers.values_mut() {
+            writer.flush()?;
+        }
+        self.num_buffered = 0;
+        Ok(())
+    }
+    /// reads back a spilled line, as written by `spill`.
+    fn parse_spilled_line(line: &str) -> CLQResult<(GraphId, &str)> {
+        let (graph_id, line) = line
+            .split_once('\t')
+            .ok_or_else(|| CLQError::from(format!("Malformed spilled row: {}", line)))?;
+        Ok((GraphId::from(graph_id.parse::<i64>()?), line))
+    }
+    /// hands each graph's lines to `f`, one graph at a time, and removes any
+    /// spill files.
+    pub fn drain<F>(mut self, mut f: F) -> CLQResult<()>
+    where
+        F: FnMut(GraphId, Vec<String>) -> CLQResult<()>,
+    {
+        if !self.has_spilled() {
+            for graph_id in self.order.drain(..) {
+                let lines = self.rows.remove(&graph_id).unwrap();
+                f(graph_id, lines)?;
+            }
+            return Ok(());
+        }
+        self.spill()?;
+        let dir = self.spill_dir.take().unwrap();
+        let result = (|| -> CLQResult<()> {
+            for partition in 0..NUM_SPILL_PARTITIONS {
+                let path = dir.join(format!("part_{}", partition));
+                if !path.exists() {
+                    continue;
+                }
+                let mut order: Vec<GraphId> = Vec::new();
+                let mut rows: HashMap<GraphId, Vec<String>> = HashMap::new();
+                for line in BufReader::new(File::open(&path)?).lines() {
+                    let line = line?;
+                    let (graph_id, line) = Self::parse_spilled_line(&line)?;
+                    rows.entry(graph_id)
+                        .or_insert_with(|| {
+                            order.push(graph_id);
+                            Vec::new()
+                        })
+                        .push(line.to_owned());
+                }
+                for graph_id in order {
+                    let lines = rows.remove(&graph_id).unwrap();
+                    f(graph_id, lines)?;
+                }
+            }
+            Ok(())
+        })();
+        fs::remove_dir_all(&dir)?;
+        result
+    }
+}
+impl Drop for GraphRowBuffer {
+    fn drop(&mut self) {
+        if let Some(dir) = &self.spill_dir {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
@@ -11,13 +11,14 @@ use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::OutputRecord;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_transformer::GraphStatsTransformerBase;
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
 use crate::dachshund::transformer_base::TransformerBase;
 use std::collections::HashMap;
-use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use crate::dachshund::algorithms::k_peaks::KPeaks;
 
@@ -62,7 +63,7 @@ impl TransformerBase for KPeakTransformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = SimpleUndirectedGraphBuilder {};
@@ -74,15 +75,13 @@ impl TransformerBase for KPeakTransformer {
         for (mountain_id, m_nodes) in mountain_assignments {
             for (node_id, coreness) in m_nodes {
                 let peak_number = *peaks.get(&node_id).unwrap();
-                let line: String = format!(
-                    "{}\t{}\t{}\t{}\t{}",
-                    original_id,
-                    node_id.value(),
-                    coreness,
-                    peak_number,
-                    mountain_id
-                );
-                output.send((Some(line), false)).unwrap();
+                let record = OutputRecord::new()
+                    .field("graph_id", original_id.as_str())
+                    .field("node_id", node_id.value())
+                    .field("coreness", coreness)
+                    .field("peak_number", peak_number)
+                    .field("mountain_id", mountain_id);
+                output.send_record(record)?;
             }
         }
         Ok(())
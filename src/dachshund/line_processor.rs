@@ -7,58 +7,95 @@
 extern crate clap;
 extern crate serde_json;
 
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::row::{Row, SimpleEdgeRow, WeightedEdgeRow};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::RwLock;
 
 pub trait LineProcessorBase {
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>>;
+    /// names of the (tab-separated) columns process_line expects, in order. Used
+    /// to map columns of other input formats onto the native one.
+    fn get_column_names(&self) -> Vec<&'static str>;
+}
+
+/// returns a `CLQError::FieldCount` if a split line does not have exactly
+/// `expected` fields.
+pub fn check_field_count(fields: &[&str], expected: usize) -> CLQResult<()> {
+    if fields.len() != expected {
+        return Err(CLQError::FieldCount {
+            expected,
+            found: fields.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Keeps track of the graph keys seen so far: maps each original key (an
+/// arbitrary string) to a local `GraphId`, in order of appearance, and back.
+pub struct GraphKeyMap {
+    ids: RwLock<HashMap<String, i64>>,
+    reverse_ids: RwLock<Vec<String>>,
+}
+impl GraphKeyMap {
+    pub fn new() -> Self {
+        Self {
+            ids: RwLock::new(HashMap::new()),
+            reverse_ids: RwLock::new(Vec::new()),
+        }
+    }
+    pub fn record_new_key_or_return_current_one(&self, key: &str) -> GraphId {
+        let mut ids = self.ids.write().unwrap();
+        if let Some(id) = ids.get(key) {
+            return GraphId::from(*id);
+        }
+        let id = ids.len() as i64;
+        ids.insert(key.to_string(), id);
+        self.reverse_ids.write().unwrap().push(key.to_string());
+        GraphId::from(id)
+    }
+    pub fn get_original_id(&self, local_id: usize) -> String {
+        self.reverse_ids.read().unwrap()[local_id].clone()
+    }
+}
+impl Default for GraphKeyMap {
+    fn default() -> Self {
+        GraphKeyMap::new()
+    }
 }
 
 /// deals with processing lines and turning them into rows.
-/// Can mutate ids and reverse_ids maps that keep track of
-/// graph_ids seen so far.
+/// Records the graph keys seen so far in a `GraphKeyMap`.
 pub struct LineProcessor {
-    ids: Arc<RwLock<HashMap<String, i64>>>,
-    reverse_ids: Arc<RwLock<Vec<String>>>,
+    graph_keys: GraphKeyMap,
 }
 impl LineProcessorBase for LineProcessor {
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
         let vec: Vec<&str> = line.split('\t').collect();
-        assert!(vec.len() == 3);
-        let key = vec[0].to_string();
-        let graph_id = self.record_new_key_or_return_current_one(key);
+        check_field_count(&vec, 3)?;
         let source_id: NodeId = vec[1].parse::<i64>()?.into();
         let target_id: NodeId = vec[2].parse::<i64>()?.into();
+        // only record keys for rows that parsed successfully
+        let graph_id = self.graph_keys.record_new_key_or_return_current_one(vec[0]);
         Ok(Box::new(SimpleEdgeRow {
             graph_id,
             source_id,
             target_id,
         }))
     }
+    fn get_column_names(&self) -> Vec<&'static str> {
+        vec!["graph_id", "source_id", "target_id"]
+    }
 }
 impl LineProcessor {
     pub fn new() -> Self {
         Self {
-            ids: Arc::new(RwLock::new(HashMap::new())),
-            reverse_ids: Arc::new(RwLock::new(Vec::new())),
-        }
-    }
-    fn record_new_key_or_return_current_one(&self, key: String) -> GraphId {
-        let mut ids = self.ids.write().unwrap();
-        let mut reverse_ids = self.reverse_ids.write().unwrap();
-        let num_items: usize = ids.len();
-        if !ids.contains_key(&key) {
-            ids.insert(key.clone(), num_items as i64);
-            reverse_ids.push(key.clone());
+            graph_keys: GraphKeyMap::new(),
         }
-        let id = ids.get(&key).unwrap();
-        GraphId::from(*id)
     }
     pub fn get_original_id(&self, local_id: usize) -> String {
-        self.reverse_ids.read().unwrap()[local_id].clone()
+        self.graph_keys.get_original_id(local_id)
     }
 }
 impl Default for LineProcessor {
@@ -68,18 +105,17 @@ impl Default for LineProcessor {
 }
 
 pub struct WeightedLineProcessor {
-    ids: Arc<RwLock<HashMap<String, i64>>>,
-    reverse_ids: Arc<RwLock<Vec<String>>>,
+    graph_keys: GraphKeyMap,
 }
 impl LineProcessorBase for WeightedLineProcessor {
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
         let vec: Vec<&str> = line.split('\t').collect();
-        assert!(vec.len() == 4);
-        let key = vec[0].to_string();
-        let graph_id = self.record_new_key_or_return_current_one(key);
+        check_field_count(&vec, 4)?;
         let source_id: NodeId = vec[1].parse::<i64>()?.into();
         let target_id: NodeId = vec[2].parse::<i64>()?.into();
         let weight: f64 = vec[3].parse::<f64>()?.into();
+        // only record keys for rows that parsed successfully
+        let graph_id = self.graph_keys.record_new_key_or_return_current_one(vec[0]);
         Ok(Box::new(WeightedEdgeRow {
             graph_id,
             source_id,
@@ -87,27 +123,18 @@ impl LineProcessorBase for WeightedLineProcessor {
             weight
         }))
     }
+    fn get_column_names(&self) -> Vec<&'static str> {
+        vec!["graph_id", "source_id", "target_id", "weight"]
+    }
 }
 impl WeightedLineProcessor {
     pub fn new() -> Self {
         Self {
-            ids: Arc::new(RwLock::new(HashMap::new())),
-            reverse_ids: Arc::new(RwLock::new(Vec::new())),
+            graph_keys: GraphKeyMap::new(),
         }
     }
-    fn record_new_key_or_return_current_one(&self, key: String) -> GraphId {
-        let mut ids = self.ids.write().unwrap();
-        let mut reverse_ids = self.reverse_ids.write().unwrap();
-        let num_items: usize = ids.len();
-        if !ids.contains_key(&key) {
-            ids.insert(key.clone(), num_items as i64);
-            reverse_ids.push(key.clone());
-        }
-        let id = ids.get(&key).unwrap();
-        GraphId::from(*id)
-    }
     pub fn get_original_id(&self, local_id: usize) -> String {
-        self.reverse_ids.read().unwrap()[local_id].clone()
+        self.graph_keys.get_original_id(local_id)
     }
 }
 impl Default for WeightedLineProcessor {
@@ -5,23 +5,34 @@
  * LICENSE file in the root directory of this source tree.
  */
 pub mod algorithms;
+pub mod bad_rows;
 pub mod beam;
 pub mod candidate;
+pub mod compression;
 pub mod connected_components_transformer;
 pub mod strongly_connected_components_transformer;
 pub mod core_transformer;
+pub mod csr_undirected_graph;
+pub mod csr_undirected_graph_builder;
+pub mod edge_list_formats;
 pub mod error;
 pub mod graph_base;
 pub mod graph_builder_base;
+pub mod graph_export;
+pub mod graph_snapshot;
 pub mod id_types;
 pub mod input;
+pub mod input_format;
+pub mod input_ordering;
 pub mod line_processor;
 pub mod node;
 pub mod non_core_type_ids;
 pub mod output;
+pub mod output_format;
 pub mod row;
 pub mod scorer;
 pub mod search_problem;
+pub mod seed;
 pub mod simple_directed_graph;
 pub mod simple_directed_graph_builder;
 pub mod simple_transformer;
@@ -7,7 +7,9 @@
 use std::cmp::{Eq, PartialEq};
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::ptr::NonNull;
 
+use crate::dachshund::csr_undirected_graph::{CsrArrays, CsrStorage};
 use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::id_types::{EdgeTypeId, NodeId, NodeTypeId};
 
@@ -300,6 +302,27 @@ pub struct WeightedNode {
     pub edges: Vec<WeightedNodeEdge>,
     pub neighbors: BTreeSet<NodeId>,
 }
+impl WeightedNode {
+    /// adds or re-weights the edge to `target_id`. Returns true if the edge is new.
+    pub fn set_edge(&mut self, target_id: NodeId, weight: f64) -> bool {
+        if self.neighbors.insert(target_id) {
+            self.edges.push(WeightedNodeEdge { target_id, weight });
+            return true;
+        }
+        for edge in self.edges.iter_mut().filter(|x| x.target_id == target_id) {
+            edge.weight = weight;
+        }
+        false
+    }
+    /// removes the edge to `target_id`. Returns false if there was no such edge.
+    pub fn remove_edge(&mut self, target_id: NodeId) -> bool {
+        if !self.neighbors.remove(&target_id) {
+            return false;
+        }
+        self.edges.retain(|x| x.target_id != target_id);
+        true
+    }
+}
 impl WeightedNodeBase for WeightedNode {
     fn weight(&self) -> f64 {
         self.edges.iter().map(|x| x.get_weight()).sum()
@@ -336,3 +359,82 @@ impl NodeBase for WeightedNode {
         ids.iter().filter(|x| self.neighbors.contains(x)).count()
     }
 }
+
+/// A node of a CSR graph: its position in the graph's CSR storage, owned or
+/// borrowed. Neighbors are stored as positions, sorted, and only turned into
+/// `NodeId`s when they are handed out.
+pub struct CsrNode<S: CsrStorage = CsrArrays> {
+    pub node_id: NodeId,
+    position: usize,
+    storage: NonNull<S>,
+}
+// nodes only read their storage, which is `Sync`.
+unsafe impl<S: CsrStorage> Send for CsrNode<S> {}
+unsafe impl<S: CsrStorage> Sync for CsrNode<S> {}
+impl<S: CsrStorage> CsrNode<S> {
+    /// `storage` must stay in place for as long as the node lives: a `CsrGraph`
+    /// only builds nodes over storage it keeps behind an `Arc` or a borrow.
+    pub(crate) fn new(node_id: NodeId, position: usize, storage: &S) -> Self {
+        Self {
+            node_id,
+            position,
+            storage: NonNull::from(storage),
+        }
+    }
+    fn get_storage(&self) -> &S {
+        // SAFETY: see `new`; nodes are only handed out by reference from the
+        // graph holding their storage.
+        unsafe { self.storage.as_ref() }
+    }
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+    /// neighbors as positions in the graph's (sorted) ids.
+    pub fn get_neighbor_positions(&self) -> &[u32] {
+        self.get_storage().get_neighbor_positions(self.position)
+    }
+    pub fn get_neighbors(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.get_edges().copied()
+    }
+    pub fn has_neighbor(&self, node_id: NodeId) -> bool {
+        let ids = self.get_storage().get_ids();
+        self.get_neighbor_positions()
+            .binary_search_by(|x| ids[*x as usize].cmp(&node_id))
+            .is_ok()
+    }
+}
+impl<S: CsrStorage> Hash for CsrNode<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node_id.hash(state);
+    }
+}
+impl<S: CsrStorage> PartialEq for CsrNode<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_id == other.node_id
+    }
+}
+impl<S: CsrStorage> Eq for CsrNode<S> {}
+impl<S: CsrStorage> NodeBase for CsrNode<S> {
+    type NodeEdgeType = NodeId;
+
+    fn get_id(&self) -> NodeId {
+        self.node_id
+    }
+    fn get_edges(&self) -> Box<dyn Iterator<Item = &NodeId> + '_> {
+        let ids = self.get_storage().get_ids();
+        Box::new(
+            self.get_neighbor_positions()
+                .iter()
+                .map(move |x| &ids[*x as usize]),
+        )
+    }
+    fn get_outgoing_edges(&self) -> Box<dyn Iterator<Item = &NodeId> + '_> {
+        self.get_edges()
+    }
+    fn degree(&self) -> usize {
+        self.get_neighbor_positions().len()
+    }
+    fn count_ties_with_ids(&self, ids: &HashSet<NodeId>) -> usize {
+        self.get_edges().filter(|x| ids.contains(x)).count()
+    }
+}
@@ -5,39 +5,172 @@
  * LICENSE file in the root directory of this source tree.
  */
 // see https://stackoverflow.com/questions/36088116/how-to-do-polymorphic-io-from-either-a-file-or-stdin-in-rust
-use std::io::Error;
-use std::io::Write;
+extern crate clap;
 
-use crate::dachshund::error::CLQResult;
+use clap::{Arg, ArgMatches};
+use std::fs::File;
+use std::io::{self, BufWriter, Error, Write};
+use std::sync::mpsc::{channel, Receiver, Sender};
 
+use crate::dachshund::compression::{CompressedWriter, Compression};
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::output_format::{OutputFormat, OutputRecord};
+
+/// What transformers send to the thread writing their `Output`.
+pub enum OutputMessage {
+    /// one line of output, without the trailing newline.
+    Line(String),
+    /// an error hit while processing a batch away from the main loop; the run
+    /// stops writing and fails with it once the channel closes.
+    Error(CLQError),
+}
+
+/// Sending half of the output channel. A batch may send any number of lines;
+/// the writer finishes once every clone of the sender has been dropped, so
+/// work handed to other threads only needs to keep its clone alive until done.
+#[derive(Clone)]
+pub struct OutputSender {
+    sender: Sender<OutputMessage>,
+    format: OutputFormat,
+}
+impl OutputSender {
+    pub fn get_format(&self) -> OutputFormat {
+        self.format
+    }
+    /// sends a record, laid out in the output format.
+    pub fn send_record(&self, record: OutputRecord) -> CLQResult<()> {
+        self.send_line(record.render(self.format))
+    }
+    pub fn send_line(&self, line: String) -> CLQResult<()> {
+        self.sender
+            .send(OutputMessage::Line(line))
+            .map_err(|_| CLQError::from("Output channel closed"))
+    }
+    /// if the writer has already gone away, there is nobody left to tell.
+    pub fn send_error(&self, error: CLQError) {
+        let _ = self.sender.send(OutputMessage::Error(error));
+    }
+}
+
+pub fn output_channel() -> (OutputSender, Receiver<OutputMessage>) {
+    output_channel_with_format(OutputFormat::default())
+}
+
+pub fn output_channel_with_format(format: OutputFormat) -> (OutputSender, Receiver<OutputMessage>) {
+    let (sender, receiver) = channel();
+    (OutputSender { sender, format }, receiver)
+}
+
+/// Where transformer output ends up: standard output, a file, or any other
+/// writer (a `Vec<u8>` in tests, ...), optionally compressed. Writes go through
+/// a buffer, which is flushed once all output has been written.
 pub struct Output<'a> {
-    pub destination: &'a mut Vec<u8>,
-    is_stdout: bool,
+    destination: CompressedWriter<'a>,
+    format: OutputFormat,
 }
 
 impl<'a> Output<'a> {
-    pub fn console(text: &'a mut Vec<u8>) -> Output<'a> {
-        Output {
-            destination: text,
-            is_stdout: true,
-        }
+    pub fn console() -> Output<'a> {
+        Output::writer(BufWriter::new(io::stdout()))
+    }
+    /// creates the file, compressing its contents if the name ends in ".gz" or ".zst".
+    pub fn file(path: &str) -> io::Result<Output<'a>> {
+        Output::file_with_compression(path, Compression::from_path(path))
+    }
+    pub fn file_with_compression(path: &str, compression: Compression) -> io::Result<Output<'a>> {
+        Output::compressed(BufWriter::new(File::create(path)?), compression)
+    }
+    pub fn string(text: &'a mut Vec<u8>) -> Output<'a> {
+        Output::writer(text)
     }
-    pub fn string(text: &'a mut Vec<u8>) -> Output {
+    pub fn writer<W: Write + Send + 'a>(writer: W) -> Output<'a> {
         Output {
-            destination: text,
-            is_stdout: false,
+            destination: CompressedWriter::Plain(Box::new(writer)),
+            format: OutputFormat::default(),
         }
     }
+    pub fn compressed<W: Write + Send + 'a>(
+        writer: W,
+        compression: Compression,
+    ) -> io::Result<Output<'a>> {
+        Ok(Output {
+            destination: compression.encode(writer)?,
+            format: OutputFormat::default(),
+        })
+    }
+    /// sets how records are laid out (tab-separated by default).
+    pub fn with_format(mut self, format: OutputFormat) -> Output<'a> {
+        self.format = format;
+        self
+    }
+    pub fn get_format(&self) -> OutputFormat {
+        self.format
+    }
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![
+            Arg::with_name("output")
+                .long("output")
+                .short("o")
+                .takes_value(true)
+                .help("File to write output to (stdout by default)."),
+            Arg::with_name("output_compression")
+                .long("output_compression")
+                .takes_value(true)
+                .possible_values(&["none", "gzip", "zstd"])
+                .help(
+                    "Compress output. By default, output files ending in .gz or .zst \
+                     are compressed accordingly, and stdout is not compressed.",
+                ),
+        ]
+    }
+    /// opens the output named on the command line, or standard output.
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Output<'static>> {
+        let compression = match matches.value_of("output_compression") {
+            Some(value) => Some(Compression::parse(value)?),
+            None => None,
+        };
+        let output = match matches.value_of("output") {
+            Some(path) => Output::file_with_compression(
+                path,
+                compression.unwrap_or_else(|| Compression::from_path(path)),
+            ),
+            None => Output::compressed(
+                BufWriter::new(io::stdout()),
+                compression.unwrap_or(Compression::None),
+            ),
+        };
+        output.map_err(|e| CLQError::from(format!("Could not open output: {}", e)))
+    }
     pub fn print(&mut self, text: String) -> CLQResult<()> {
-        if !self.is_stdout {
-            self.write_all(text.as_bytes())?;
-            self.write_all(b"\n")?;
-            self.flush()?;
-            return Ok(());
-        }
-        println!("{}", text);
+        self.write_all(text.as_bytes())?;
+        self.write_all(b"\n")?;
         Ok(())
     }
+    /// flushes everything written so far, ending the compressed stream if any.
+    /// Nothing should be written afterwards.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.destination.finish()
+    }
+    /// writes lines as they arrive until all senders are dropped, then reports
+    /// the first error received, if any.
+    pub fn write_messages(&mut self, receiver: Receiver<OutputMessage>) -> CLQResult<()> {
+        let mut first_error: Option<CLQError> = None;
+        for message in receiver {
+            match message {
+                OutputMessage::Line(line) if first_error.is_none() => self.print(line)?,
+                OutputMessage::Line(_) => {}
+                OutputMessage::Error(error) => {
+                    first_error.get_or_insert(error);
+                }
+            }
+        }
+        self.finish()?;
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
 }
 impl<'a> Write for Output<'a> {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
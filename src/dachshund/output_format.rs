@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+extern crate serde_json;
+
+use clap::{Arg, ArgMatches};
+
+use crate::dachshund::error::{CLQError, CLQResult};
+
+/// How output records are laid out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// field values separated by tabs, in a fixed order per transformer.
+    #[default]
+    Tsv,
+    /// one JSON object per line, keyed by field name.
+    Jsonl,
+}
+impl OutputFormat {
+    pub fn parse(s: &str) -> CLQResult<Self> {
+        match s {
+            "tsv" => Ok(OutputFormat::Tsv),
+            "jsonl" => Ok(OutputFormat::Jsonl),
+            _ => Err(CLQError::from(format!("Invalid output format: {}", s))),
+        }
+    }
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![Arg::with_name("format")
+            .long("format")
+            .takes_value(true)
+            .possible_values(&["tsv", "jsonl"])
+            .help(
+                "Output format: tab-separated columns (tsv, default) or one JSON object \
+                 per record (jsonl).",
+            )]
+    }
+    /// constructs a format from an ArgMatches object (to help with command line arguments).
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        match matches.value_of("format") {
+            Some(value) => OutputFormat::parse(value),
+            None => Ok(OutputFormat::default()),
+        }
+    }
+}
+
+/// The value of one output field, kept in its textual form so that tab-separated
+/// output is exactly what `Display` (or the JSON encoder used) produces.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Field {
+    /// a number, written as is. Non-finite values become null in JSON.
+    Number(String),
+    /// free text, quoted in JSON.
+    Text(String),
+    /// an already encoded JSON value (array, object), written as is.
+    Json(String),
+}
+impl Field {
+    fn to_json(&self) -> String {
+        match self {
+            Field::Number(n) => match n.parse::<f64>() {
+                Ok(value) if value.is_finite() => n.clone(),
+                _ => "null".to_owned(),
+            },
+            Field::Text(s) => serde_json::Value::from(s.as_str()).to_string(),
+            Field::Json(s) => s.clone(),
+        }
+    }
+    fn to_tsv(&self) -> &str {
+        match self {
+            Field::Number(s) | Field::Text(s) | Field::Json(s) => s,
+        }
+    }
+}
+macro_rules! number_field_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Field {
+            fn from(value: $t) -> Self {
+                Field::Number(value.to_string())
+            }
+        })*
+    };
+}
+number_field_from!(i32, i64, usize, f32, f64);
+impl From<String> for Field {
+    fn from(value: String) -> Self {
+        Field::Text(value)
+    }
+}
+impl From<&str> for Field {
+    fn from(value: &str) -> Self {
+        Field::Text(value.to_owned())
+    }
+}
+
+/// A single output record: named fields, in the order they are written.
+#[derive(Clone, Debug, Default)]
+pub struct OutputRecord {
+    fields: Vec<(&'static str, Field)>,
+}
+impl OutputRecord {
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+    pub fn field<T: Into<Field>>(mut self, name: &'static str, value: T) -> Self {
+        self.fields.push((name, value.into()));
+        self
+    }
+    pub fn get_fields(&self) -> &Vec<(&'static str, Field)> {
+        &self.fields
+    }
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Tsv => self
+                .fields
+                .iter()
+                .map(|(_, value)| value.to_tsv())
+                .collect::<Vec<&str>>()
+                .join("\t"),
+            OutputFormat::Jsonl => {
+                let items: Vec<String> = self
+                    .fields
+                    .iter()
+                    .map(|(name, value)| format!("\"{}\":{}", name, value.to_json()))
+                    .collect();
+                format!("{{{}}}", items.join(","))
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate clap;
+extern crate rand;
+
+use clap::{Arg, ArgMatches};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::dachshund::error::{CLQError, CLQResult};
+use crate::dachshund::id_types::GraphId;
+
+/// Seeds all pseudorandom choices made while processing graphs, so that two
+/// identically configured runs produce identical output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Seed(u64);
+impl Seed {
+    pub fn new(value: u64) -> Self {
+        Seed(value)
+    }
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+    /// a pseudorandom sequence for one graph. It only depends on the seed and the
+    /// graph id, not on the order (or thread) in which graphs get processed.
+    ///
+    /// Seeds are mixed with splitmix64, which (unlike `DefaultHasher`) is fixed
+    /// across Rust releases. Note that this changes the sequences of earlier
+    /// releases, which hashed the graph id alone: runs with the default seed of 0
+    /// may find different (equally scored) cliques than before.
+    pub fn get_rng(&self, graph_id: GraphId) -> StdRng {
+        StdRng::seed_from_u64(splitmix64(self.0 ^ graph_id.value() as u64))
+    }
+    /// command line arguments shared by all binaries.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![Arg::with_name("seed")
+            .long("seed")
+            .takes_value(true)
+            .help("Seed for all pseudorandom choices (default = 0).")]
+    }
+    /// constructs a seed from an ArgMatches object (to help with command line arguments).
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        match matches.value_of("seed") {
+            Some(value) => value
+                .parse::<u64>()
+                .map(Seed)
+                .map_err(|_| CLQError::from(format!("Invalid --seed: {}", value))),
+            None => Ok(Seed::default()),
+        }
+    }
+}
+
+/// the output function of the splitmix64 generator.
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -5,17 +5,20 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate fxhash;
+use crate::dachshund::algorithms::betweenness::Betweenness;
 use crate::dachshund::algorithms::brokerage::Brokerage;
 use crate::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsDirected,
 };
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityDirected};
-use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::algorithms::directed_centrality::DirectedCentrality;
+use crate::dachshund::edge_list_formats::EdgeList;
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{DirectedNodeBase, NodeBase, SimpleDirectedNode};
 use fxhash::FxHashMap;
 use std::collections::hash_map::{Keys, Values};
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 pub trait DirectedGraph
 where
@@ -48,6 +51,9 @@ pub struct SimpleDirectedGraph {
 }
 impl GraphBase for SimpleDirectedGraph {
     type NodeType = SimpleDirectedNode;
+    type IdsIter<'a> = Keys<'a, NodeId, SimpleDirectedNode>;
+    type NodesIter<'a> = Values<'a, NodeId, SimpleDirectedNode>;
+    type NodeMap = FxHashMap<NodeId, SimpleDirectedNode>;
 
     /// core and non-core IDs are the same for a `SimpleDirectedGraph`.
     fn get_core_ids(&self) -> &Vec<NodeId> {
@@ -57,10 +63,10 @@ impl GraphBase for SimpleDirectedGraph {
     fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
         Some(&self.ids)
     }
-    fn get_ids_iter(&self) -> Keys<NodeId, SimpleDirectedNode> {
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, SimpleDirectedNode> {
         self.nodes.keys()
     }
-    fn get_nodes_iter(&self) -> Values<NodeId, SimpleDirectedNode> {
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, SimpleDirectedNode> {
         self.nodes.values()
     }
     fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, SimpleDirectedNode> {
@@ -90,9 +96,118 @@ impl GraphBase for SimpleDirectedGraph {
         }
     }
 }
+impl SimpleDirectedGraph {
+    /// edges sorted by (source, target).
+    pub fn as_edge_list(&self) -> EdgeList {
+        let mut edge_list = EdgeList::new(false, true);
+        edge_list.node_ids = self.nodes.keys().map(|x| x.value()).collect();
+        for (id, node) in &self.nodes {
+            for target_id in &node.out_neighbors {
+                edge_list.edges.push((id.value(), target_id.value(), 1.0));
+            }
+        }
+        edge_list.edges.sort_by_key(|x| (x.0, x.1));
+        edge_list
+    }
+    pub fn as_snap_rows(&self) -> String {
+        self.as_edge_list().to_snap()
+    }
+    pub fn as_matrix_market(&self) -> String {
+        self.as_edge_list().to_matrix_market()
+    }
+    /// adds a node without edges. Returns false if the node was already present.
+    pub fn add_node(&mut self, id: NodeId) -> bool {
+        if self.nodes.contains_key(&id) {
+            return false;
+        }
+        self.nodes.insert(
+            id,
+            SimpleDirectedNode {
+                node_id: id,
+                in_neighbors: BTreeSet::new(),
+                out_neighbors: BTreeSet::new(),
+            },
+        );
+        self.ids.push(id);
+        true
+    }
+    /// adds an edge from source to target, and any missing endpoint. Returns
+    /// false if the edge was already present.
+    pub fn add_edge(&mut self, source_id: NodeId, target_id: NodeId) -> bool {
+        self.add_node(source_id);
+        self.add_node(target_id);
+        let added = self
+            .nodes
+            .get_mut(&source_id)
+            .unwrap()
+            .out_neighbors
+            .insert(target_id);
+        self.nodes
+            .get_mut(&target_id)
+            .unwrap()
+            .in_neighbors
+            .insert(source_id);
+        added
+    }
+    /// removes the edge from source to target, keeping its endpoints. Returns
+    /// false if there was no such edge.
+    pub fn remove_edge(&mut self, source_id: NodeId, target_id: NodeId) -> bool {
+        let removed = self
+            .nodes
+            .get_mut(&source_id)
+            .is_some_and(|node| node.out_neighbors.remove(&target_id));
+        if removed {
+            self.nodes
+                .get_mut(&target_id)
+                .unwrap()
+                .in_neighbors
+                .remove(&source_id);
+        }
+        removed
+    }
+    /// removes a node along with its in- and out-edges. Returns false if there
+    /// was no such node.
+    pub fn remove_node(&mut self, id: NodeId) -> bool {
+        let node = match self.nodes.remove(&id) {
+            Some(node) => node,
+            None => return false,
+        };
+        for target_id in node.out_neighbors {
+            if let Some(target) = self.nodes.get_mut(&target_id) {
+                target.in_neighbors.remove(&id);
+            }
+        }
+        for source_id in node.in_neighbors {
+            if let Some(source) = self.nodes.get_mut(&source_id) {
+                source.out_neighbors.remove(&id);
+            }
+        }
+        self.ids.retain(|x| *x != id);
+        true
+    }
+}
 impl DirectedGraph for SimpleDirectedGraph {}
+impl Betweenness for SimpleDirectedGraph {
+    fn get_dense_successors(&self, index: &DenseIndex) -> Vec<Vec<usize>> {
+        index
+            .ids()
+            .iter()
+            .map(|id| {
+                self.nodes[id]
+                    .out_neighbors
+                    .iter()
+                    .map(|target_id| index.get_index(*target_id))
+                    .collect()
+            })
+            .collect()
+    }
+    fn follows_edge_directions(&self) -> bool {
+        true
+    }
+}
 impl Brokerage for SimpleDirectedGraph {}
 impl ConnectedComponents for SimpleDirectedGraph {}
 impl ConnectedComponentsDirected for SimpleDirectedGraph {}
 impl Connectivity for SimpleDirectedGraph {}
 impl ConnectivityDirected for SimpleDirectedGraph {}
+impl DirectedCentrality for SimpleDirectedGraph {}
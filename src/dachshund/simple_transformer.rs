@@ -8,49 +8,121 @@ extern crate clap;
 extern crate fxhash;
 extern crate serde_json;
 
-use crate::dachshund::algorithms::betweenness::Betweenness;
+use crate::dachshund::algorithms::betweenness::{Betweenness, BetweennessOptions};
 use crate::dachshund::algorithms::clustering::Clustering;
 use crate::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
 use crate::dachshund::algorithms::coreness::Coreness;
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::{Field, OutputRecord};
 use crate::dachshund::row::{Row, SimpleEdgeRow};
+use crate::dachshund::seed::Seed;
 use crate::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use crate::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
 use crate::dachshund::transformer_base::TransformerBase;
+use clap::{Arg, ArgMatches};
 use fxhash::FxHashSet;
-use rand::seq::SliceRandom;
+use rand::Rng;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use serde_json::json;
-use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
+pub const DEFAULT_BETWEENNESS_EPSILON: f64 = 0.05;
+pub const DEFAULT_BETWEENNESS_DELTA: f64 = 0.1;
+
+/// How graph stats compute betweenness centrality (`bet_cent`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BetweennessMode {
+    #[default]
+    /// Brandes betweenness of shortest paths within the largest connected component.
+    Exact,
+    /// estimates from a sample of shortest paths, within `epsilon` of the exact
+    /// (pair-normalized) values with probability 1 - `delta`. Much faster on large
+    /// graphs, but the values depend on the seed.
+    Approximate { epsilon: f64, delta: f64 },
+}
+impl BetweennessMode {
+    /// command line arguments of the featurizer.
+    pub fn get_args() -> Vec<Arg<'static, 'static>> {
+        vec![
+            Arg::with_name("approximate_betweenness")
+                .long("approximate_betweenness")
+                .help("Estimate betweenness from a sample of shortest paths instead of computing it exactly."),
+            Arg::with_name("betweenness_epsilon")
+                .long("betweenness_epsilon")
+                .takes_value(true)
+                .help("With --approximate_betweenness, error allowed on (pair-normalized) estimates (default = 0.05)."),
+            Arg::with_name("betweenness_delta")
+                .long("betweenness_delta")
+                .takes_value(true)
+                .help("With --approximate_betweenness, probability of exceeding that error (default = 0.1)."),
+        ]
+    }
+    /// constructs a mode from an ArgMatches object (to help with command line arguments).
+    pub fn from_argmatches(matches: &ArgMatches) -> CLQResult<Self> {
+        if !matches.is_present("approximate_betweenness") {
+            return Ok(BetweennessMode::Exact);
+        }
+        let parse = |name: &str, default: f64| -> CLQResult<f64> {
+            match matches.value_of(name) {
+                Some(value) => value
+                    .parse::<f64>()
+                    .map_err(|_| CLQError::from(format!("Invalid --{}: {}", name, value))),
+                None => Ok(default),
+            }
+        };
+        Ok(BetweennessMode::Approximate {
+            epsilon: parse("betweenness_epsilon", DEFAULT_BETWEENNESS_EPSILON)?,
+            delta: parse("betweenness_delta", DEFAULT_BETWEENNESS_DELTA)?,
+        })
+    }
+}
+
 pub struct SimpleTransformer {
     batch: Vec<SimpleEdgeRow>,
     line_processor: Arc<LineProcessor>,
+    seed: Seed,
+    betweenness: BetweennessMode,
 }
 pub struct SimpleParallelTransformer {
     batch: Vec<SimpleEdgeRow>,
     pool: ThreadPool,
     line_processor: Arc<LineProcessor>,
+    seed: Seed,
+    betweenness: BetweennessMode,
 }
 pub trait GraphStatsTransformerBase: TransformerBase {
-    fn compute_graph_stats_json(graph: &SimpleUndirectedGraph) -> String {
+    /// `rng` drives the sampling behind approximate stats (e.g. betweenness).
+    fn compute_graph_stats_json<R: Rng>(
+        graph: &SimpleUndirectedGraph,
+        betweenness: BetweennessMode,
+        rng: &mut R,
+    ) -> CLQResult<String> {
         let conn_comp = graph.get_connected_components();
-        let largest_cc = conn_comp.iter().max_by_key(|x| x.len()).unwrap().to_vec();
+        let largest_cc = conn_comp.iter().max_by_key(|x| x.len()).unwrap();
         let size_of_largest_cc = largest_cc.len();
-        let sources: Vec<NodeId> = largest_cc
-            .choose_multiple(&mut rand::thread_rng(), 100)
-            .copied()
-            .collect();
-        let betcent = graph
-            .get_node_betweenness_starting_from_sources(&sources, false, Some(largest_cc))
-            .unwrap();
-        let evcent = graph.get_eigenvector_centrality(0.001, 1000);
+        let betcent = match betweenness {
+            BetweennessMode::Exact => graph.get_node_betweenness_brandes_parallel_from_sources(
+                largest_cc,
+                BetweennessOptions::default(),
+            )?,
+            BetweennessMode::Approximate { epsilon, delta } => {
+                graph
+                    .get_approximate_node_betweenness(
+                        epsilon,
+                        delta,
+                        BetweennessOptions::default(),
+                        rng,
+                    )?
+                    .values
+            }
+        };
+        let evcent = graph.get_eigenvector_centrality(0.001, 1000)?.value;
 
         let mut removed: FxHashSet<NodeId> = FxHashSet::default();
         let k_cores_2 = graph._get_k_cores(2, &mut removed);
@@ -62,7 +134,7 @@ pub trait GraphStatsTransformerBase: TransformerBase {
         let k_cores_16 = graph._get_k_cores(16, &mut removed);
         let k_trusses_17 = graph._get_k_trusses(17, &removed).1;
 
-        json!({
+        Ok(json!({
             "num_edges": graph.count_edges(),
             "num_2_cores": k_cores_2.len(),
             "num_4_cores": k_cores_4.len(),
@@ -80,7 +152,12 @@ pub trait GraphStatsTransformerBase: TransformerBase {
                 (evcent.len() as f64) * 1000.0).floor() / 1000.0,
             "clust_coef": (graph.get_avg_clustering() * 1000.0).floor() / 1000.0,
         })
-        .to_string()
+        .to_string())
+    }
+    fn get_stats_record(original_id: &str, stats: String) -> OutputRecord {
+        OutputRecord::new()
+            .field("graph_id", original_id)
+            .field("stats", Field::Json(stats))
     }
 }
 impl SimpleTransformer {
@@ -88,8 +165,19 @@ impl SimpleTransformer {
         Self {
             batch: Vec::new(),
             line_processor: Arc::new(LineProcessor::new()),
+            seed: Seed::default(),
+            betweenness: BetweennessMode::default(),
         }
     }
+    /// seeds the stats of each graph with `seed` (and the graph id).
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = seed;
+        self
+    }
+    pub fn with_betweenness(mut self, betweenness: BetweennessMode) -> Self {
+        self.betweenness = betweenness;
+        self
+    }
 }
 impl Default for SimpleTransformer {
     fn default() -> Self {
@@ -102,8 +190,19 @@ impl SimpleParallelTransformer {
             batch: Vec::new(),
             line_processor: Arc::new(LineProcessor::new()),
             pool: ThreadPoolBuilder::new().build().unwrap(),
+            seed: Seed::default(),
+            betweenness: BetweennessMode::default(),
         }
     }
+    /// seeds the stats of each graph with `seed` (and the graph id).
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = seed;
+        self
+    }
+    pub fn with_betweenness(mut self, betweenness: BetweennessMode) -> Self {
+        self.betweenness = betweenness;
+        self
+    }
 }
 impl Default for SimpleParallelTransformer {
     fn default() -> Self {
@@ -126,17 +225,20 @@ impl TransformerBase for SimpleTransformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = SimpleUndirectedGraphBuilder {};
         let graph = builder.from_vector(tuples)?;
-        let stats = Self::compute_graph_stats_json(&graph);
         let original_id = self
             .line_processor
             .get_original_id(graph_id.value() as usize);
-        let line: String = format!("{}\t{}", original_id, stats);
-        output.send((Some(line), false)).unwrap();
+        let stats = Self::compute_graph_stats_json(
+            &graph,
+            self.betweenness,
+            &mut self.seed.get_rng(GraphId::from_key(&original_id)),
+        )?;
+        output.send_record(Self::get_stats_record(&original_id, stats))?;
         Ok(())
     }
 }
@@ -155,18 +257,27 @@ impl TransformerBase for SimpleParallelTransformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
-        let output_clone = output.clone();
+        let output = output.clone();
         let line_processor = self.line_processor.clone();
+        let seed = self.seed;
+        let betweenness = self.betweenness;
         self.pool.spawn(move || {
             let mut builder = SimpleUndirectedGraphBuilder {};
-            let graph = builder.from_vector(tuples).unwrap();
-            let stats = Self::compute_graph_stats_json(&graph);
-            let original_id = line_processor.get_original_id(graph_id.value() as usize);
-            let line: String = format!("{}\t{}", original_id, stats);
-            output_clone.send((Some(line), false)).unwrap();
+            let result = builder.from_vector(tuples).and_then(|graph| {
+                let original_id = line_processor.get_original_id(graph_id.value() as usize);
+                let stats = Self::compute_graph_stats_json(
+                    &graph,
+                    betweenness,
+                    &mut seed.get_rng(GraphId::from_key(&original_id)),
+                )?;
+                output.send_record(Self::get_stats_record(&original_id, stats))
+            });
+            if let Err(error) = result {
+                output.send_error(error);
+            }
         });
         Ok(())
     }
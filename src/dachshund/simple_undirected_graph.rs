@@ -16,14 +16,19 @@ use crate::dachshund::algorithms::connected_components::{
 use crate::dachshund::algorithms::connectivity::{Connectivity, ConnectivityUndirected};
 use crate::dachshund::algorithms::coreness::Coreness;
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use crate::dachshund::algorithms::girvan_newman::GirvanNewman;
 use crate::dachshund::algorithms::laplacian::Laplacian;
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::spectral_clustering::SpectralClustering;
 use crate::dachshund::algorithms::transitivity::Transitivity;
+use crate::dachshund::edge_list_formats::EdgeList;
+use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_base::GraphBase;
 use crate::dachshund::id_types::NodeId;
 use crate::dachshund::node::{NodeBase, NodeEdgeBase, SimpleNode};
 use fxhash::FxHashMap;
 use std::collections::hash_map::{Keys, Values};
+use std::collections::BTreeSet;
 use crate::dachshund::algorithms::k_peaks::KPeaks;
 
 pub trait UndirectedGraph
@@ -39,6 +44,9 @@ pub struct SimpleUndirectedGraph {
 }
 impl GraphBase for SimpleUndirectedGraph {
     type NodeType = SimpleNode;
+    type IdsIter<'a> = Keys<'a, NodeId, SimpleNode>;
+    type NodesIter<'a> = Values<'a, NodeId, SimpleNode>;
+    type NodeMap = FxHashMap<NodeId, SimpleNode>;
 
     /// core and non-core IDs are the same for a `SimpleUndirectedGraph`.
     fn get_core_ids(&self) -> &Vec<NodeId> {
@@ -48,10 +56,10 @@ impl GraphBase for SimpleUndirectedGraph {
     fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
         Some(&self.ids)
     }
-    fn get_ids_iter(&self) -> Keys<NodeId, SimpleNode> {
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, SimpleNode> {
         self.nodes.keys()
     }
-    fn get_nodes_iter(&self) -> Values<NodeId, SimpleNode> {
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, SimpleNode> {
         self.nodes.values()
     }
     fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, SimpleNode> {
@@ -97,9 +105,82 @@ impl SimpleUndirectedGraph {
         }
         rows.join("\n")
     }
+    /// edges sorted by (source, target), each undirected edge listed once.
+    pub fn as_edge_list(&self) -> EdgeList {
+        let mut edge_list = EdgeList::new(false, false);
+        edge_list.node_ids = self.nodes.keys().map(|x| x.value()).collect();
+        for (id, node) in &self.nodes {
+            for e in node.get_edges() {
+                if *id <= e.get_neighbor_id() {
+                    edge_list
+                        .edges
+                        .push((id.value(), e.get_neighbor_id().value(), 1.0));
+                }
+            }
+        }
+        edge_list.edges.sort_by_key(|x| (x.0, x.1));
+        edge_list
+    }
+    pub fn as_snap_rows(&self) -> String {
+        self.as_edge_list().to_snap()
+    }
+    pub fn as_matrix_market(&self) -> String {
+        self.as_edge_list().to_matrix_market()
+    }
+    pub fn as_metis(&self) -> CLQResult<String> {
+        self.as_edge_list().to_metis()
+    }
     pub fn get_node_degree(&self, id: NodeId) -> usize {
         self.nodes[&id].degree()
     }
+    /// adds a node without edges. Returns false if the node was already present.
+    pub fn add_node(&mut self, id: NodeId) -> bool {
+        if self.nodes.contains_key(&id) {
+            return false;
+        }
+        self.nodes.insert(
+            id,
+            SimpleNode {
+                node_id: id,
+                neighbors: BTreeSet::new(),
+            },
+        );
+        self.ids.push(id);
+        true
+    }
+    /// adds an edge, and any missing endpoint. Returns false if the edge was already present.
+    pub fn add_edge(&mut self, id1: NodeId, id2: NodeId) -> bool {
+        self.add_node(id1);
+        self.add_node(id2);
+        let added = self.nodes.get_mut(&id1).unwrap().neighbors.insert(id2);
+        self.nodes.get_mut(&id2).unwrap().neighbors.insert(id1);
+        added
+    }
+    /// removes an edge, keeping its endpoints. Returns false if there was no such edge.
+    pub fn remove_edge(&mut self, id1: NodeId, id2: NodeId) -> bool {
+        let removed = self
+            .nodes
+            .get_mut(&id1)
+            .is_some_and(|node| node.neighbors.remove(&id2));
+        if removed {
+            self.nodes.get_mut(&id2).unwrap().neighbors.remove(&id1);
+        }
+        removed
+    }
+    /// removes a node along with its edges. Returns false if there was no such node.
+    pub fn remove_node(&mut self, id: NodeId) -> bool {
+        let node = match self.nodes.remove(&id) {
+            Some(node) => node,
+            None => return false,
+        };
+        for neighbor_id in node.neighbors {
+            if let Some(neighbor) = self.nodes.get_mut(&neighbor_id) {
+                neighbor.neighbors.remove(&id);
+            }
+        }
+        self.ids.retain(|x| *x != id);
+        true
+    }
 }
 impl UndirectedGraph for SimpleUndirectedGraph {}
 
@@ -114,8 +195,10 @@ impl Clustering for SimpleUndirectedGraph {}
 impl Connectivity for SimpleUndirectedGraph {}
 impl ConnectivityUndirected for SimpleUndirectedGraph {}
 impl Betweenness for SimpleUndirectedGraph {}
+impl GirvanNewman for SimpleUndirectedGraph {}
 impl Laplacian for SimpleUndirectedGraph {}
 impl Transitivity for SimpleUndirectedGraph {}
 impl ShortestPaths for SimpleUndirectedGraph {}
 impl AlgebraicConnectivity for SimpleUndirectedGraph {}
 impl EigenvectorCentrality for SimpleUndirectedGraph {}
+impl SpectralClustering for SimpleUndirectedGraph {}
@@ -61,9 +61,8 @@ pub trait TSimpleUndirectedGraphBuilder:
     //  probability p.)
     // [TODO] Switch to the faster implementation using geometric distributions
     // for sparse graphs.
-    fn get_er_graph(&mut self, n: u64, p: f64) -> CLQResult<Self::GraphType> {
+    fn get_er_graph<R: Rng>(&mut self, n: u64, p: f64, rng: &mut R) -> CLQResult<Self::GraphType> {
         let mut v = Vec::new();
-        let mut rng = rand::thread_rng();
 
         for i in 1..n {
             for j in i + 1..=n {
@@ -12,11 +12,12 @@ use crate::dachshund::algorithms::connected_components::ConnectedComponentsDirec
 use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::OutputRecord;
 use crate::dachshund::row::{Row, SimpleEdgeRow};
 use crate::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
 use crate::dachshund::transformer_base::TransformerBase;
 use crate::GraphId;
-use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 pub struct StronglyConnectedComponentsTransformer {
@@ -52,7 +53,7 @@ impl TransformerBase for StronglyConnectedComponentsTransformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = SimpleDirectedGraphBuilder {};
@@ -64,8 +65,11 @@ impl TransformerBase for StronglyConnectedComponentsTransformer {
             .get_original_id(graph_id.value() as usize);
         for (cid, nodes) in conn_comp.into_iter().enumerate() {
             for node_id in nodes {
-                let line = format!("{}\t{}\t{}", original_id, cid, node_id.value());
-                output.send((Some(line), false)).unwrap();
+                let record = OutputRecord::new()
+                    .field("graph_id", original_id.as_str())
+                    .field("component_id", cid)
+                    .field("node_id", node_id.value());
+                output.send_record(record)?;
             }
         }
         Ok(())
@@ -16,14 +16,16 @@ use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeTypeId};
 use crate::dachshund::line_processor::LineProcessorBase;
 use crate::dachshund::non_core_type_ids::NonCoreTypeIds;
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::OutputRecord;
 use crate::dachshund::row::{CliqueRow, EdgeRow, Row};
 use crate::dachshund::search_problem::SearchProblem;
+use crate::dachshund::seed::Seed;
 use crate::dachshund::transformer_base::TransformerBase;
 use crate::dachshund::typed_graph::TypedGraph;
 use crate::dachshund::typed_graph_builder::TypedGraphBuilder;
 use crate::dachshund::typed_graph_line_processor::TypedGraphLineProcessor;
 use std::rc::Rc;
-use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 /// Used to set up the typed graph clique mining algorithm.
@@ -37,6 +39,7 @@ pub struct Transformer {
     pub search_problem: Rc<SearchProblem>,
     pub debug: bool,
     pub long_format: bool,
+    pub seed: Seed,
 
     edge_rows: Vec<EdgeRow>,
     clique_rows: Vec<CliqueRow>,
@@ -62,7 +65,7 @@ impl TransformerBase for Transformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let drained_rows = self.edge_rows.drain(..).collect::<Vec<_>>();
         let graph: TypedGraph = self.build_pruned_graph(graph_id, drained_rows)?;
@@ -187,11 +190,17 @@ impl Transformer {
             search_problem,
             debug,
             long_format,
+            seed: Seed::default(),
             edge_rows: Vec::new(),
             clique_rows: Vec::new(),
         };
         Ok(transformer)
     }
+    /// seeds the beam search of each graph with `seed` (and the graph id).
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = seed;
+        self
+    }
 
     /// constructs a transformer from an ArgMatches object (to help with command line arguments).
     pub fn from_argmatches(matches: ArgMatches) -> CLQResult<Self> {
@@ -214,6 +223,7 @@ impl Transformer {
         let min_degree: usize = arg_value("min_degree")?.parse::<usize>()?;
         let core_type: String = arg_value("core_type")?.parse::<String>()?;
         let long_format: bool = arg_value("long_format")?.parse::<bool>()?;
+        let seed = Seed::from_argmatches(&matches)?;
 
         let transformer = Transformer::new(
             typespec,
@@ -228,7 +238,8 @@ impl Transformer {
             min_degree,
             core_type,
             long_format,
-        )?;
+        )?
+        .with_seed(seed);
         Ok(transformer)
     }
 
@@ -264,6 +275,7 @@ impl Transformer {
             self.num_non_core_types,
             self.search_problem.clone(),
             graph_id,
+            self.seed,
         )?;
         beam.run_search()
     }
@@ -275,32 +287,30 @@ impl Transformer {
         clique_rows: &'a Vec<CliqueRow>,
         graph_id: GraphId,
         verbose: bool,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<Option<BeamSearchResult<'a, TypedGraph>>> {
         if graph.get_core_ids().is_empty() || graph.get_non_core_ids().unwrap().is_empty() {
-            // still have to send an acknowledgement to the output channel
-            // that we have actually processed this graph, otherwise
-            // we lose track of how many graphs have been processed so
-            // far!
-            output.send((None, false)).unwrap();
             return Ok(None);
         }
+        let original_id = self
+            .line_processor
+            .get_original_id(graph_id.value() as usize);
+        // seed the search with the original key, so that results do not depend on
+        // the order in which graphs appear in the input.
         let result: BeamSearchResult<TypedGraph> =
-            self.process_graph(graph, clique_rows, graph_id, verbose)?;
+            self.process_graph(graph, clique_rows, GraphId::from_key(&original_id), verbose)?;
         // only print if this is a conforming clique
         if result.top_candidate.get_score()? > 0.0 {
             if !self.long_format {
-                let line: String = format!(
-                    "{}\t{}",
-                    graph_id.value(),
-                    result
-                        .top_candidate
-                        .to_printable_row(&self.non_core_types)?,
-                );
-                output.send((Some(line), false)).unwrap();
+                let record = OutputRecord::new().field("graph_id", original_id.as_str());
+                let record = result
+                    .top_candidate
+                    .add_output_fields(record, &self.non_core_types)?;
+                output.send_record(record)?;
             } else {
                 result.top_candidate.print(
                     graph_id,
+                    &original_id,
                     &self.non_core_types,
                     &self.core_type,
                     output,
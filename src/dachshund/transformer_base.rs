@@ -7,65 +7,105 @@
 extern crate clap;
 extern crate serde_json;
 
-use crate::dachshund::error::CLQResult;
+use crate::dachshund::bad_rows::{BadRowHandler, BadRowPolicy, RowSummary};
+use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::id_types::GraphId;
 use crate::dachshund::input::Input;
+use crate::dachshund::input_format::LineNormalizer;
+use crate::dachshund::input_ordering::{GraphRowBuffer, InputOrdering};
 use crate::dachshund::line_processor::LineProcessorBase;
-use crate::dachshund::output::Output;
+use crate::dachshund::output::{output_channel_with_format, Output, OutputSender};
 use crate::dachshund::row::Row;
+use std::collections::HashSet;
 use std::io::prelude::*;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
-use std::thread;
-use std::time::Duration;
 
 pub trait TransformerBase {
     fn get_line_processor(&self) -> Arc<dyn LineProcessorBase>;
     // logic for taking row and storing into self via side-effect
     fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()>;
     // logic for processing batch of rows, once all rows are ready
-    fn process_batch(
-        &mut self,
-        graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
-    ) -> CLQResult<()>;
+    fn process_batch(&mut self, graph_id: GraphId, output: &OutputSender) -> CLQResult<()>;
     // reset transformer state after processing;
     fn reset(&mut self) -> CLQResult<()>;
 
     // main loop, runs through lines ordered by graph_id, updates state accordingly
-    // and runs process_batch when graph_id changes
-    fn run(&mut self, input: Input, mut output: Output) -> CLQResult<()> {
+    // and runs process_batch when graph_id changes. Fails on the first malformed line,
+    // and on a graph_id reappearing, unless the input's ordering is `Unsorted`.
+    fn run(&mut self, input: Input, output: Output) -> CLQResult<()> {
+        self.run_with_bad_row_policy(input, output, BadRowPolicy::Fail)?;
+        Ok(())
+    }
+
+    // same as run, but malformed lines are handled as per bad_row_policy. Returns
+    // counts of processed and rejected rows.
+    fn run_with_bad_row_policy(
+        &mut self,
+        input: Input,
+        mut output: Output,
+        bad_row_policy: BadRowPolicy,
+    ) -> CLQResult<RowSummary> {
+        let mut bad_rows = BadRowHandler::new(bad_row_policy)?;
+        let line_processor = self.get_line_processor();
+        let mut normalizer = LineNormalizer::new(
+            input.get_format().clone(),
+            line_processor.get_column_names(),
+        )?;
+        let mut row_buffer: Option<GraphRowBuffer> = match input.get_ordering() {
+            InputOrdering::Grouped => None,
+            InputOrdering::Unsorted {
+                max_buffered_rows,
+                spill_dir,
+            } => Some(GraphRowBuffer::new(*max_buffered_rows, spill_dir.clone())),
+        };
         let ret = crossbeam::scope(|scope| {
-            let line_processor = self.get_line_processor();
-            let num_processed = Arc::new(AtomicUsize::new(0 as usize));
-            let (sender, receiver) = channel();
-            let num_processed_clone = num_processed.clone();
-            let writer = scope.spawn(move |_| loop {
-                match receiver.recv() {
-                    Ok((line, shutdown)) => {
-                        if shutdown {
-                            return;
-                        }
-                        if let Some(string) = line {
-                            output.print(string).unwrap();
-                        }
-                        num_processed_clone.fetch_add(1, Ordering::SeqCst);
-                    }
-                    Err(error) => panic!("{}", error),
-                }
-            });
+            let (sender, receiver) = output_channel_with_format(output.get_format());
+            let writer = scope.spawn(move |_| output.write_messages(receiver));
             let mut current_graph_id: Option<GraphId> = None;
-            let mut num_to_process: usize = 0;
-            for line in input.lines() {
+            // graphs already processed, to catch ids reappearing in grouped input.
+            let mut finished_graph_ids: HashSet<GraphId> = HashSet::new();
+            for (line_ix, line) in input.lines().enumerate() {
                 match line {
                     Ok(n) => {
-                        let row: Box<dyn Row> = line_processor.process_line(n)?;
+                        let normalized: String = match normalizer.normalize(&n) {
+                            Ok(Some(normalized)) => normalized,
+                            Ok(None) => continue,
+                            // a header we cannot make sense of is not just a bad row.
+                            Err(error) if normalizer.awaiting_header() => return Err(error),
+                            Err(error) => {
+                                bad_rows.record_bad_row(line_ix + 1, &n, error)?;
+                                continue;
+                            }
+                        };
+                        let buffered_line = row_buffer.as_ref().map(|_| normalized.clone());
+                        let row: Box<dyn Row> = match line_processor.process_line(normalized) {
+                            Ok(row) => row,
+                            Err(error) => {
+                                bad_rows.record_bad_row(line_ix + 1, &n, error)?;
+                                continue;
+                            }
+                        };
+                        bad_rows.record_good_row();
                         let new_graph_id: GraphId = row.get_graph_id();
+                        if let (Some(buffer), Some(buffered_line)) =
+                            (row_buffer.as_mut(), buffered_line)
+                        {
+                            buffer.push(new_graph_id, buffered_line)?;
+                            continue;
+                        }
                         if let Some(some_current_graph_id) = current_graph_id {
                             if new_graph_id != some_current_graph_id {
-                                self.process_batch(some_current_graph_id, &sender.clone())?;
-                                num_to_process += 1;
+                                finished_graph_ids.insert(some_current_graph_id);
+                                if finished_graph_ids.contains(&new_graph_id) {
+                                    return Err(CLQError::from(format!(
+                                        "Rows of graph {} reappear on line {}, after rows of \
+                                         other graphs. Input must be grouped by graph id \
+                                         (or use --unsorted).",
+                                        new_graph_id.value(),
+                                        line_ix + 1
+                                    )));
+                                }
+                                self.process_batch(some_current_graph_id, &sender)?;
                                 self.reset()?;
                             }
                         }
@@ -75,18 +115,35 @@ pub trait TransformerBase {
                     Err(error) => eprintln!("I/O error: {}", error),
                 }
             }
-            if let Some(some_current_graph_id) = current_graph_id {
-                self.process_batch(some_current_graph_id, &sender)?;
-                num_to_process += 1;
-                while num_to_process > num_processed.load(Ordering::SeqCst) {
-                    thread::sleep(Duration::from_millis(100));
+            match row_buffer {
+                Some(buffer) => {
+                    buffer.drain(|graph_id, lines| {
+                        if current_graph_id.is_some() {
+                            self.reset()?;
+                        }
+                        for line in lines {
+                            self.process_row(line_processor.process_line(line)?)?;
+                        }
+                        self.process_batch(graph_id, &sender)?;
+                        current_graph_id = Some(graph_id);
+                        Ok(())
+                    })?;
+                }
+                None => {
+                    if let Some(some_current_graph_id) = current_graph_id {
+                        self.process_batch(some_current_graph_id, &sender)?;
+                    }
                 }
-                sender.send((None, true)).unwrap();
-                writer.join().unwrap();
-                return Ok(());
             }
-            Err("No input rows!".into())
+            if current_graph_id.is_none() {
+                return Err("No input rows!".into());
+            }
+            // the writer is done once batches still running elsewhere drop their senders.
+            drop(sender);
+            writer.join().unwrap()
         });
-        ret.unwrap()
+        let result: CLQResult<()> = ret.unwrap();
+        result?;
+        bad_rows.finish()
     }
 }
@@ -25,6 +25,9 @@ pub struct TypedGraph {
 }
 impl GraphBase for TypedGraph {
     type NodeType = Node;
+    type IdsIter<'a> = Keys<'a, NodeId, Node>;
+    type NodesIter<'a> = Values<'a, NodeId, Node>;
+    type NodeMap = FxHashMap<NodeId, Node>;
 
     fn get_core_ids(&self) -> &Vec<NodeId> {
         &self.core_ids
@@ -32,15 +35,15 @@ impl GraphBase for TypedGraph {
     fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
         Some(&self.non_core_ids)
     }
-    fn get_ids_iter(&self) -> Keys<NodeId, Node> {
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, Node> {
         self.nodes.keys()
     }
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, Node> {
+        self.nodes.values()
+    }
     fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, Node> {
         &mut self.nodes
     }
-    fn get_nodes_iter(&self) -> Values<NodeId, Node> {
-        self.nodes.values()
-    }
     fn has_node(&self, node_id: NodeId) -> bool {
         self.nodes.contains_key(&node_id)
     }
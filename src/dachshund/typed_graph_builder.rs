@@ -205,7 +205,7 @@ pub trait TypedGraphBuilderBase {
         min_degree: usize,
         rows: &Vec<EdgeRow>,
     ) -> (Vec<NodeId>, Vec<NodeId>, Vec<EdgeRow>) {
-        let exclude_nodes: HashSet<NodeId> = Self::trim_edges(graph.get_mut_nodes(), &min_degree);
+        let exclude_nodes: HashSet<NodeId> = Self::trim_edges(&mut graph.nodes, &min_degree);
         let filtered_source_ids: Vec<NodeId> = graph
             .get_core_ids()
             .iter()
@@ -9,20 +9,20 @@ extern crate serde_json;
 
 use crate::dachshund::error::{CLQError, CLQResult};
 use crate::dachshund::id_types::{EdgeTypeId, GraphId, NodeId, NodeTypeId};
-use crate::dachshund::line_processor::LineProcessorBase;
+use crate::dachshund::line_processor::{check_field_count, GraphKeyMap, LineProcessorBase};
 use crate::dachshund::non_core_type_ids::NonCoreTypeIds;
 use crate::dachshund::row::Row;
 use crate::dachshund::row::{CliqueRow, EdgeRow};
 use std::rc::Rc;
 
 /// Processing lines for typed graphs
-/// Can mutate ids and reverse_ids maps that keep track of
-/// graph_ids seen so far.
+/// Records the graph keys seen so far in a `GraphKeyMap`.
 pub struct TypedGraphLineProcessor {
     pub core_type: String,
     pub non_core_type_ids: Rc<NonCoreTypeIds>,
     pub non_core_types: Rc<Vec<String>>,
     pub edge_types: Rc<Vec<String>>,
+    graph_keys: GraphKeyMap,
 }
 impl LineProcessorBase for TypedGraphLineProcessor {
     /// processes a line of (tab-separated) input, of the form:
@@ -32,6 +32,9 @@ impl LineProcessorBase for TypedGraphLineProcessor {
     ///
     /// graph_id\tnode_id\tnode_type
     ///
+    /// graph_id may be an arbitrary string key: it is mapped to a local GraphId,
+    /// which can be translated back via `get_original_id`.
+    ///
     /// Note that core_type is not used in the first row type. The second
     /// row type is used to initialize the beam search with a single existing
     /// clique, the best identified by some other search process. This existing
@@ -39,11 +42,10 @@ impl LineProcessorBase for TypedGraphLineProcessor {
     /// as per the current search process.
     fn process_line(&self, line: String) -> CLQResult<Box<dyn Row>> {
         let vec: Vec<&str> = line.split('\t').collect();
+        check_field_count(&vec, 6)?;
         // this is an edge row if we have something on column 3
-        assert!(vec.len() == 6);
         let is_edge_row: bool = !vec[3].is_empty();
         if is_edge_row {
-            let graph_id: GraphId = vec[0].parse::<i64>()?.into();
             let core_id: NodeId = vec[1].parse::<i64>()?.into();
             let non_core_id: NodeId = vec[2].parse::<i64>()?.into();
             let edge_type: &str = vec[4].trim_end();
@@ -53,9 +55,10 @@ impl LineProcessorBase for TypedGraphLineProcessor {
                 .edge_types
                 .iter()
                 .position(|r| r == edge_type)
-                .ok_or_else(CLQError::err_none)?
+                .ok_or_else(|| CLQError::from(format!("No mapping for edge type: {}", edge_type)))?
                 .into();
             let core_type_id: NodeTypeId = *self.non_core_type_ids.require(&self.core_type)?;
+            let graph_id: GraphId = self.graph_keys.record_new_key_or_return_current_one(vec[0]);
             return Ok(Box::new(EdgeRow {
                 graph_id,
                 source_id: core_id,
@@ -65,7 +68,6 @@ impl LineProcessorBase for TypedGraphLineProcessor {
                 edge_type_id,
             }));
         }
-        let graph_id: GraphId = vec[0].parse::<i64>()?.into();
         let node_id: NodeId = vec[1].parse::<i64>()?.into();
         let node_type: &str = vec[2].trim_end();
         let non_core_type: Option<NodeTypeId>;
@@ -75,12 +77,24 @@ impl LineProcessorBase for TypedGraphLineProcessor {
             let non_core_type_id: NodeTypeId = *self.non_core_type_ids.require(node_type)?;
             non_core_type = Some(non_core_type_id);
         }
+        let graph_id: GraphId = self.graph_keys.record_new_key_or_return_current_one(vec[0]);
         Ok(Box::new(CliqueRow {
             graph_id,
             node_id,
             target_type: non_core_type,
         }))
     }
+    /// clique rows use core_id and non_core_id for node_id and node_type.
+    fn get_column_names(&self) -> Vec<&'static str> {
+        vec![
+            "graph_id",
+            "core_id",
+            "non_core_id",
+            "core_type",
+            "edge_type",
+            "non_core_type",
+        ]
+    }
 }
 impl TypedGraphLineProcessor {
     pub fn new(
@@ -94,6 +108,10 @@ impl TypedGraphLineProcessor {
             non_core_type_ids,
             non_core_types,
             edge_types,
+            graph_keys: GraphKeyMap::new(),
         }
     }
+    pub fn get_original_id(&self, local_id: usize) -> String {
+        self.graph_keys.get_original_id(local_id)
+    }
 }
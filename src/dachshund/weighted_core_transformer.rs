@@ -13,10 +13,11 @@ use crate::dachshund::error::CLQResult;
 use crate::dachshund::graph_builder_base::GraphBuilderBase;
 use crate::dachshund::id_types::{GraphId, NodeId};
 use crate::dachshund::line_processor::{WeightedLineProcessor, LineProcessorBase};
+use crate::dachshund::output::OutputSender;
+use crate::dachshund::output_format::OutputRecord;
 use crate::dachshund::row::{Row, WeightedEdgeRow};
 use crate::dachshund::transformer_base::TransformerBase;
 use crate::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
-use std::sync::mpsc::Sender;
 use std::sync::Arc;
 
 use ordered_float::NotNan;
@@ -56,7 +57,7 @@ impl TransformerBase for WeightedCoreTransformer {
     fn process_batch(
         &mut self,
         graph_id: GraphId,
-        output: &Sender<(Option<String>, bool)>,
+        output: &OutputSender,
     ) -> CLQResult<()> {
         let tuples: Vec<(i64, i64, f64)> = self.batch.iter().map(|x| x.as_tuple()).collect();
         let mut builder = WeightedUndirectedGraphBuilder {};
@@ -69,14 +70,12 @@ impl TransformerBase for WeightedCoreTransformer {
         coreness.sort_by_key(|(_node_id, coreness)| NotNan::new(*coreness).unwrap());
         for (node_id, node_coreness) in coreness {
             let degree = graph.get_node_degree(node_id);
-            let line: String = format!(
-                "{}\t{}\t{}\t{}",
-                original_id,
-                node_id.value(),
-                node_coreness,
-                degree
-            );
-            output.send((Some(line), false)).unwrap();
+            let record = OutputRecord::new()
+                .field("graph_id", original_id.as_str())
+                .field("node_id", node_id.value())
+                .field("fractional_coreness", node_coreness)
+                .field("degree", degree);
+            output.send_record(record)?;
         }
         Ok(())
     }
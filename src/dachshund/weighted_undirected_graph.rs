@@ -20,15 +20,21 @@ use crate::dachshund::algorithms::coreness::{Coreness, FractionalCoreness};
 use crate::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
 use crate::dachshund::algorithms::laplacian::Laplacian;
 use crate::dachshund::algorithms::shortest_paths::ShortestPaths;
+use crate::dachshund::algorithms::spectral_clustering::SpectralClustering;
 use crate::dachshund::algorithms::transitivity::Transitivity;
-use crate::dachshund::graph_base::GraphBase;
+use crate::dachshund::edge_list_formats::EdgeList;
+use crate::dachshund::error::CLQResult;
+use crate::dachshund::graph_base::{DenseIndex, GraphBase};
 use crate::dachshund::id_types::NodeId;
-use crate::dachshund::node::{NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase};
+use crate::dachshund::node::{
+    NodeBase, NodeEdgeBase, WeightedNode, WeightedNodeBase, WeightedNodeEdge, WeightedNodeEdgeBase,
+};
 use crate::dachshund::simple_undirected_graph::UndirectedGraph;
 
 
 use fxhash::FxHashMap;
 use std::collections::hash_map::{Keys, Values};
+use std::collections::BTreeSet;
 
 
 
@@ -39,6 +45,9 @@ pub struct WeightedUndirectedGraph {
 }
 impl GraphBase for WeightedUndirectedGraph {
     type NodeType = WeightedNode;
+    type IdsIter<'a> = Keys<'a, NodeId, WeightedNode>;
+    type NodesIter<'a> = Values<'a, NodeId, WeightedNode>;
+    type NodeMap = FxHashMap<NodeId, WeightedNode>;
 
     /// core and non-core IDs are the same for a `SimpleUndirectedGraph`.
     fn get_core_ids(&self) -> &Vec<NodeId> {
@@ -48,10 +57,10 @@ impl GraphBase for WeightedUndirectedGraph {
     fn get_non_core_ids(&self) -> Option<&Vec<NodeId>> {
         Some(&self.ids)
     }
-    fn get_ids_iter(&self) -> Keys<NodeId, WeightedNode> {
+    fn get_ids_iter(&self) -> Keys<'_, NodeId, WeightedNode> {
         self.nodes.keys()
     }
-    fn get_nodes_iter(&self) -> Values<NodeId, WeightedNode> {
+    fn get_nodes_iter(&self) -> Values<'_, NodeId, WeightedNode> {
         self.nodes.values()
     }
     fn get_mut_nodes(&mut self) -> &mut FxHashMap<NodeId, WeightedNode> {
@@ -98,12 +107,87 @@ impl WeightedUndirectedGraph {
         }
         rows.join("\n")
     }
+    /// edges sorted by (source, target), each undirected edge listed once.
+    pub fn as_edge_list(&self) -> EdgeList {
+        let mut edge_list = EdgeList::new(true, false);
+        edge_list.node_ids = self.nodes.keys().map(|x| x.value()).collect();
+        for (id, node) in &self.nodes {
+            for e in node.get_edges() {
+                if *id <= e.get_neighbor_id() {
+                    edge_list
+                        .edges
+                        .push((id.value(), e.get_neighbor_id().value(), e.weight));
+                }
+            }
+        }
+        edge_list.edges.sort_by_key(|x| (x.0, x.1));
+        edge_list
+    }
+    pub fn as_snap_rows(&self) -> String {
+        self.as_edge_list().to_snap()
+    }
+    pub fn as_matrix_market(&self) -> String {
+        self.as_edge_list().to_matrix_market()
+    }
+    pub fn as_metis(&self) -> CLQResult<String> {
+        self.as_edge_list().to_metis()
+    }
     pub fn get_node_degree(&self, id: NodeId) -> usize {
         self.nodes[&id].degree()
     }
     pub fn get_node_weight(&self, id: NodeId) -> f64 {
         self.nodes[&id].weight()
     }
+    /// adds a node without edges. Returns false if the node was already present.
+    pub fn add_node(&mut self, id: NodeId) -> bool {
+        if self.nodes.contains_key(&id) {
+            return false;
+        }
+        self.nodes.insert(
+            id,
+            WeightedNode {
+                node_id: id,
+                edges: Vec::new(),
+                neighbors: BTreeSet::new(),
+            },
+        );
+        self.ids.push(id);
+        true
+    }
+    /// adds an edge, and any missing endpoint. If the edge was already present,
+    /// its weight is replaced and false is returned.
+    pub fn add_edge(&mut self, id1: NodeId, id2: NodeId, weight: f64) -> bool {
+        self.add_node(id1);
+        self.add_node(id2);
+        let added = self.nodes.get_mut(&id1).unwrap().set_edge(id2, weight);
+        self.nodes.get_mut(&id2).unwrap().set_edge(id1, weight);
+        added
+    }
+    /// removes an edge, keeping its endpoints. Returns false if there was no such edge.
+    pub fn remove_edge(&mut self, id1: NodeId, id2: NodeId) -> bool {
+        let removed = self
+            .nodes
+            .get_mut(&id1)
+            .is_some_and(|node| node.remove_edge(id2));
+        if removed {
+            self.nodes.get_mut(&id2).unwrap().remove_edge(id1);
+        }
+        removed
+    }
+    /// removes a node along with its edges. Returns false if there was no such node.
+    pub fn remove_node(&mut self, id: NodeId) -> bool {
+        let node = match self.nodes.remove(&id) {
+            Some(node) => node,
+            None => return false,
+        };
+        for neighbor_id in node.neighbors {
+            if let Some(neighbor) = self.nodes.get_mut(&neighbor_id) {
+                neighbor.remove_edge(id);
+            }
+        }
+        self.ids.retain(|x| *x != id);
+        true
+    }
 }
 impl UndirectedGraph for WeightedUndirectedGraph {}
 
@@ -112,13 +196,30 @@ impl ConnectedComponentsUndirected for WeightedUndirectedGraph {}
 impl Coreness for WeightedUndirectedGraph {}
 impl FractionalCoreness for WeightedUndirectedGraph {}
 
-impl AdjacencyMatrix for WeightedUndirectedGraph {}
+impl AdjacencyMatrix for WeightedUndirectedGraph {
+    /// spectral measures (Laplacians, eigenvector centrality, algebraic
+    /// connectivity, spectral clustering) take edge weights into account.
+    fn get_edge_weight(&self, edge: &WeightedNodeEdge) -> f64 {
+        edge.get_weight()
+    }
+}
 impl Clustering for WeightedUndirectedGraph {}
 impl Connectivity for WeightedUndirectedGraph {}
 impl ConnectivityUndirected for WeightedUndirectedGraph {}
-impl Betweenness for WeightedUndirectedGraph {}
+impl Betweenness for WeightedUndirectedGraph {
+    fn get_dense_successor_weights(&self, index: &DenseIndex) -> Option<Vec<Vec<f64>>> {
+        Some(
+            index
+                .ids()
+                .iter()
+                .map(|id| self.get_node(*id).get_edges().map(|e| e.get_weight()).collect())
+                .collect(),
+        )
+    }
+}
 impl Laplacian for WeightedUndirectedGraph {}
 impl Transitivity for WeightedUndirectedGraph {}
 impl ShortestPaths for WeightedUndirectedGraph {}
 impl AlgebraicConnectivity for WeightedUndirectedGraph {}
 impl EigenvectorCentrality for WeightedUndirectedGraph {}
+impl SpectralClustering for WeightedUndirectedGraph {}
@@ -23,19 +23,33 @@ pub use dachshund::algorithms::clustering::Clustering;
 pub use dachshund::algorithms::cnm_communities::CNMCommunities;
 pub use dachshund::algorithms::connected_components::ConnectedComponents;
 pub use dachshund::algorithms::coreness::Coreness;
+pub use dachshund::algorithms::directed_centrality::DirectedCentrality;
+pub use dachshund::algorithms::girvan_newman::GirvanNewman;
+pub use dachshund::algorithms::incremental_coreness::IncrementalCoreness;
 pub use dachshund::algorithms::laplacian::Laplacian;
 pub use dachshund::algorithms::shortest_paths::ShortestPaths;
+pub use dachshund::algorithms::spectral_clustering::SpectralClustering;
 pub use dachshund::algorithms::transitivity::Transitivity;
+pub use dachshund::bad_rows::BadRowPolicy;
 pub use dachshund::beam::Beam;
 pub use dachshund::candidate::Candidate;
+pub use dachshund::compression::Compression;
 pub use dachshund::core_transformer::CoreTransformer;
+pub use dachshund::csr_undirected_graph::{CsrSnapshotGraph, CsrUndirectedGraph};
+pub use dachshund::csr_undirected_graph_builder::CsrUndirectedGraphBuilder;
+pub use dachshund::edge_list_formats::EdgeList;
 pub use dachshund::graph_base::GraphBase;
 pub use dachshund::graph_builder_base::GraphBuilderBase;
+pub use dachshund::graph_export::GraphExport;
+pub use dachshund::graph_snapshot::{GraphSnapshot, WriteSnapshot};
 pub use dachshund::id_types::{EdgeTypeId, GraphId, NodeId, NodeTypeId};
 pub use dachshund::input::Input;
+pub use dachshund::input_format::InputFormat;
+pub use dachshund::input_ordering::InputOrdering;
 pub use dachshund::line_processor::LineProcessor;
 pub use dachshund::node::{Node, SimpleDirectedNode};
 pub use dachshund::output::Output;
+pub use dachshund::output_format::OutputFormat;
 pub use dachshund::row::EdgeRow;
 pub use dachshund::scorer::Scorer;
 pub use dachshund::search_problem::SearchProblem;
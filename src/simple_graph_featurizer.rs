@@ -8,14 +8,17 @@
 extern crate clap;
 extern crate lib_dachshund;
 
-use std::io;
-
 use clap::{App, ArgMatches};
 
+use lib_dachshund::dachshund::bad_rows::BadRowPolicy;
 use lib_dachshund::dachshund::error::CLQResult;
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::input_format::InputFormat;
+use lib_dachshund::dachshund::input_ordering::InputOrdering;
 use lib_dachshund::dachshund::output::Output;
-use lib_dachshund::dachshund::simple_transformer::SimpleTransformer;
+use lib_dachshund::dachshund::output_format::OutputFormat;
+use lib_dachshund::dachshund::seed::Seed;
+use lib_dachshund::dachshund::simple_transformer::{BetweennessMode, SimpleTransformer};
 use lib_dachshund::dachshund::transformer_base::TransformerBase;
 
 fn get_command_line_args() -> ArgMatches<'static> {
@@ -31,18 +34,35 @@ fn get_command_line_args() -> ArgMatches<'static> {
                 Pär Winzell <zell@fb.com>",
         )
         .about("Featurizes simple undirected graphs specified from stdin.")
+        .args(&BadRowPolicy::get_args())
+        .args(&InputFormat::get_args())
+        .args(&InputOrdering::get_args())
+        .args(&Input::get_args())
+        .args(&Output::get_args())
+        .args(&OutputFormat::get_args())
+        .args(&Seed::get_args())
+        .args(&BetweennessMode::get_args())
         .get_matches();
     matches
 }
 
 fn main() -> CLQResult<()> {
     // TODO: add proper command line args
-    let _matches: ArgMatches = get_command_line_args();
-    let mut transformer = SimpleTransformer::new();
-    let stdio: io::Stdin = io::stdin();
-    let input: Input = Input::console(&stdio);
-    let mut dummy: Vec<u8> = Vec::new();
-    let output: Output = Output::console(&mut dummy);
-    transformer.run(input, output)?;
+    let matches: ArgMatches = get_command_line_args();
+    let bad_row_policy = BadRowPolicy::from_argmatches(&matches)?;
+    let input_format = InputFormat::from_argmatches(&matches)?;
+    let input_ordering = InputOrdering::from_argmatches(&matches)?;
+    let output_format = OutputFormat::from_argmatches(&matches)?;
+    let seed = Seed::from_argmatches(&matches)?;
+    let betweenness = BetweennessMode::from_argmatches(&matches)?;
+    let mut transformer = SimpleTransformer::new()
+        .with_seed(seed)
+        .with_betweenness(betweenness);
+    let input: Input = Input::from_argmatches(&matches)?
+        .with_format(input_format)
+        .with_ordering(input_ordering);
+    let output: Output = Output::from_argmatches(&matches)?.with_format(output_format);
+    let summary = transformer.run_with_bad_row_policy(input, output, bad_row_policy)?;
+    summary.report();
     Ok(())
 }
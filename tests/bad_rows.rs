@@ -0,0 +1,107 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::bad_rows::BadRowPolicy;
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::test_utils::{gen_test_transformer, gen_test_typespec};
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+
+fn get_raw_input() -> String {
+    [
+        "a\t1\t2",
+        "a\t2\tthree",
+        "a\t2\t3",
+        "a\t3",
+        "b\t4\t5",
+    ]
+    .join("\n")
+}
+
+#[test]
+fn test_field_count_error() {
+    let line_processor = LineProcessor::new();
+    match line_processor.process_line("0\t1".to_string()) {
+        Err(CLQError::FieldCount { expected, found }) => {
+            assert_eq!(expected, 3);
+            assert_eq!(found, 2);
+        }
+        _ => panic!("Expected a FieldCount error."),
+    }
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string()).unwrap();
+    assert!(transformer
+        .line_processor
+        .process_line("0\t1\t2\tauthor\tunknown_edge\tjournal".to_string())
+        .is_err());
+}
+
+#[test]
+fn test_fail_on_bad_row() {
+    let text = get_raw_input();
+    let bytes = text.as_bytes();
+    let input = Input::string(bytes);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    let mut transformer = ConnectedComponentsTransformer::new();
+    match transformer.run(input, output) {
+        Err(CLQError::MalformedRow {
+            line_number,
+            source,
+        }) => {
+            assert_eq!(line_number, 2);
+            assert!(matches!(*source, CLQError::ParseInt(_)));
+        }
+        _ => panic!("Expected a MalformedRow error."),
+    }
+}
+
+#[test]
+fn test_skip_bad_rows() -> CLQResult<()> {
+    let text = get_raw_input();
+    let bytes = text.as_bytes();
+    let input = Input::string(bytes);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    let mut transformer = ConnectedComponentsTransformer::new();
+    let summary = transformer.run_with_bad_row_policy(input, output, BadRowPolicy::Skip)?;
+    assert_eq!(summary.num_rows, 5);
+    assert_eq!(summary.num_rejected, 2);
+    let output_str = String::from_utf8(buffer)?;
+    let mut lines: Vec<&str> = output_str.lines().collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        vec!["a\t0\t1", "a\t0\t2", "a\t0\t3", "b\t0\t4", "b\t0\t5"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_write_rejects() -> CLQResult<()> {
+    let path = std::env::temp_dir().join("dachshund_test_write_rejects.txt");
+    let path_str = path.to_str().unwrap().to_string();
+    let text = get_raw_input();
+    let bytes = text.as_bytes();
+    let input = Input::string(bytes);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    let mut transformer = ConnectedComponentsTransformer::new();
+    let summary = transformer.run_with_bad_row_policy(
+        input,
+        output,
+        BadRowPolicy::WriteRejects(path_str),
+    )?;
+    assert_eq!(summary.num_rejected, 2);
+    let rejects = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(rejects, "a\t2\tthree\na\t3\n");
+    Ok(())
+}
@@ -8,15 +8,18 @@ extern crate lib_dachshund;
 
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use rand::RngCore;
 
 use lib_dachshund::dachshund::beam::Beam;
 use lib_dachshund::dachshund::candidate::Candidate;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::id_types::{GraphId, NodeId, NodeTypeId};
 use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_processor::GraphKeyMap;
 use lib_dachshund::dachshund::output::Output;
 use lib_dachshund::dachshund::row::CliqueRow;
 use lib_dachshund::dachshund::row::EdgeRow;
+use lib_dachshund::dachshund::seed::Seed;
 use lib_dachshund::dachshund::test_utils::{
     assert_nodes_have_ids, gen_test_transformer, process_raw_vector,
 };
@@ -64,6 +67,7 @@ fn test_init_beam_with_clique_rows() -> CLQResult<()> {
         1,
         transformer.search_problem.clone(),
         graph_id,
+        Seed::default(),
     )?;
     let init_candidate: &Candidate<TypedGraph> = &beam.candidates[0];
     assert_nodes_have_ids(&graph, &init_candidate.core_ids, vec![1], true);
@@ -107,6 +111,7 @@ fn test_init_beam_with_partially_overlapping_clique_rows() -> CLQResult<()> {
         1,
         transformer.search_problem.clone(),
         graph_id,
+        Seed::default(),
     )?;
     let init_candidate: &Candidate<TypedGraph> = &beam.candidates[0];
     assert_nodes_have_ids(&graph, &init_candidate.core_ids, vec![1], true);
@@ -260,3 +265,121 @@ fn test_beam_with_empty_graph_after_pruning() -> CLQResult<()> {
     assert_eq!(output_str, "");
     Ok(())
 }
+
+#[test]
+fn test_string_graph_keys_in_output() -> CLQResult<()> {
+    let typespec: Vec<Vec<String>> = vec![
+        vec!["author".to_string(), "published".into(), "article".into()],
+        vec!["author".to_string(), "cited".into(), "article".into()],
+    ];
+    let raw = [
+        "graph_a\t1\t3\tauthor\tpublished\tarticle".to_string(),
+        "graph_a\t1\tauthor\t\t\t".into(),
+        "graph_a\t3\tarticle\t\t\t".into(),
+        "graph_b\t5\t7\tauthor\tpublished\tarticle".into(),
+        "graph_b\t5\tauthor\t\t\t".into(),
+        "graph_b\t7\tarticle\t\t\t".into(),
+    ];
+    let expected = [
+        "graph_a\t1\tauthor".to_string(),
+        "graph_a\t3\tarticle".into(),
+        "graph_b\t5\tauthor".into(),
+        "graph_b\t7\tarticle".into(),
+    ];
+    let mut transformer = Transformer::new(
+        typespec,
+        20,
+        1.0,
+        Some(0.5),
+        Some(0.5),
+        20,
+        0,
+        3,
+        false,
+        0,
+        "author".to_string(),
+        true,
+    )?;
+    let text = raw.join("\n");
+    let bytes = text.as_bytes();
+    let input = Input::string(bytes);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    transformer.run(input, output)?;
+    let output_str: String = String::from_utf8(buffer)?;
+    assert_eq!(output_str, expected.join("\n") + "\n");
+    assert_eq!(transformer.line_processor.get_original_id(1), "graph_b");
+    Ok(())
+}
+
+#[test]
+fn test_numeric_graph_keys_seed_search() -> CLQResult<()> {
+    // keys seed the search by their raw bytes, whatever their position.
+    assert_ne!(GraphId::from_key("7"), GraphId::from_key("007"));
+    assert_ne!(GraphId::from_key("7"), GraphId::from_key("+7"));
+    assert_eq!(GraphId::from_key("graph_a"), GraphId::from_key("graph_a"));
+    assert_ne!(GraphId::from_key("graph_a"), GraphId::from_key("graph_b"));
+    let run = |raw: &[String]| -> CLQResult<String> {
+        let typespec: Vec<Vec<String>> = vec![vec![
+            "author".to_string(),
+            "published".into(),
+            "article".into(),
+        ]];
+        let mut transformer = Transformer::new(
+            typespec,
+            1,
+            1.0,
+            Some(0.5),
+            Some(0.5),
+            1,
+            20,
+            3,
+            false,
+            0,
+            "author".to_string(),
+            false,
+        )?;
+        let text = raw.join("\n");
+        let mut buffer: Vec<u8> = Vec::new();
+        transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer))?;
+        Ok(String::from_utf8(buffer)?)
+    };
+    // three equally good bicliques: which one gets found depends on the seed.
+    let mut graph_7 = Vec::new();
+    for i in 0..3 {
+        for core_id in &[2 * i + 1, 2 * i + 2] {
+            for non_core_id in &[2 * i + 10, 2 * i + 11] {
+                graph_7.push(format!(
+                    "7\t{}\t{}\tauthor\tpublished\tarticle",
+                    core_id, non_core_id
+                ));
+            }
+        }
+    }
+    let mut graph_3_then_7 = vec!["3\t1\t2\tauthor\tpublished\tarticle".to_string()];
+    graph_3_then_7.extend(graph_7.iter().cloned());
+    let expected = run(&graph_7)?;
+    assert!(!expected.is_empty());
+    assert!(run(&graph_3_then_7)?.ends_with(&expected));
+    Ok(())
+}
+
+#[test]
+fn test_graph_key_map() {
+    let keys = GraphKeyMap::new();
+    assert_eq!(keys.record_new_key_or_return_current_one("7"), GraphId::from(0));
+    assert_eq!(keys.record_new_key_or_return_current_one("007"), GraphId::from(1));
+    assert_eq!(keys.record_new_key_or_return_current_one("7"), GraphId::from(0));
+    assert_eq!(keys.get_original_id(1), "007");
+}
+
+#[test]
+fn test_seed_rng_is_fixed() {
+    // sequences must not change across Rust releases.
+    let mut rng = Seed::default().get_rng(GraphId::from(0));
+    assert_eq!(rng.next_u64(), 14431105169052685908);
+    let first = |seed: u64, graph_id: i64| Seed::new(seed).get_rng(GraphId::from(graph_id)).next_u64();
+    assert_ne!(first(0, 1), first(1, 1));
+    assert_ne!(first(0, 1), first(0, 2));
+    assert_eq!(first(5, 7), first(5, 7));
+}
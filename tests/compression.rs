@@ -0,0 +1,96 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::compression::Compression;
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use std::io::{Read, Write};
+
+const EDGES: &str = "0\t1\t2\n0\t2\t3\n1\t5\t6\n";
+
+fn get_expected() -> Vec<&'static str> {
+    vec!["0\t0\t1", "0\t0\t2", "0\t0\t3", "1\t0\t5", "1\t0\t6"]
+}
+
+fn get_sorted_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text.lines().map(|x| x.to_owned()).collect();
+    lines.sort();
+    lines
+}
+
+fn compress(text: &str, compression: Compression) -> CLQResult<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut output = Output::compressed(&mut buffer, compression)?;
+    output.write_all(text.as_bytes())?;
+    output.finish()?;
+    drop(output);
+    Ok(buffer)
+}
+
+#[test]
+fn test_compression_from_name_and_magic() -> CLQResult<()> {
+    assert_eq!(Compression::from_path("edges.tsv.gz"), Compression::Gzip);
+    assert_eq!(Compression::from_path("edges.zst"), Compression::Zstd);
+    assert_eq!(Compression::from_path("edges.tsv"), Compression::None);
+    assert_eq!(
+        Compression::from_magic(&compress(EDGES, Compression::Gzip)?),
+        Compression::Gzip
+    );
+    assert_eq!(
+        Compression::from_magic(&compress(EDGES, Compression::Zstd)?),
+        Compression::Zstd
+    );
+    assert_eq!(Compression::from_magic(EDGES.as_bytes()), Compression::None);
+    assert!(Compression::parse("lz4").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_compressed_input() -> CLQResult<()> {
+    for compression in &[Compression::None, Compression::Gzip, Compression::Zstd] {
+        let bytes = compress(EDGES, *compression)?;
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut transformer = ConnectedComponentsTransformer::new();
+        transformer.run(Input::reader(&bytes[..])?, Output::string(&mut buffer))?;
+        assert_eq!(
+            get_sorted_lines(&String::from_utf8(buffer)?),
+            get_expected()
+        );
+    }
+    // input shorter than any magic number is passed through.
+    let mut text = String::new();
+    Input::reader(&b"1"[..])?.read_to_string(&mut text)?;
+    assert_eq!(text, "1");
+    Ok(())
+}
+
+#[test]
+fn test_compressed_file_output() -> CLQResult<()> {
+    for (suffix, compression) in &[("gz", Compression::Gzip), ("zst", Compression::Zstd)] {
+        let path = std::env::temp_dir().join(format!(
+            "dachshund_compression_test_{}.{}",
+            std::process::id(),
+            suffix
+        ));
+        let path = path.to_str().unwrap();
+        let mut transformer = ConnectedComponentsTransformer::new();
+        transformer.run(Input::string(EDGES.as_bytes()), Output::file(path)?)?;
+        let mut raw: Vec<u8> = Vec::new();
+        std::fs::File::open(path)?.read_to_end(&mut raw)?;
+        assert_eq!(Compression::from_magic(&raw), *compression);
+
+        let mut text = String::new();
+        Input::file(path)?.read_to_string(&mut text)?;
+        assert_eq!(get_sorted_lines(&text), get_expected());
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::connected_components::ConnectedComponentsUndirected;
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
+use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
+use lib_dachshund::dachshund::csr_undirected_graph::{CsrSnapshotGraph, CsrUndirectedGraph};
+use lib_dachshund::dachshund::csr_undirected_graph_builder::CsrUndirectedGraphBuilder;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::graph_snapshot::{GraphSnapshot, ReadSnapshot, WriteSnapshot};
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::node::NodeBase;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn sorted_components(mut components: Vec<Vec<NodeId>>) -> Vec<Vec<NodeId>> {
+    for component in components.iter_mut() {
+        component.sort();
+    }
+    components.sort();
+    components
+}
+
+#[test]
+fn test_csr_builder() -> CLQResult<()> {
+    let graph = CsrUndirectedGraphBuilder {}.from_vector(vec![
+        (30, 10),
+        (10, 20),
+        (20, 10),
+        (10, 30),
+        (40, 30),
+    ])?;
+    assert_eq!(graph.count_nodes(), 4);
+    assert_eq!(graph.count_edges(), 3);
+    assert_eq!(
+        graph.ids(),
+        vec![10, 20, 30, 40]
+            .into_iter()
+            .map(NodeId::from)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        graph.get_neighbors(NodeId::from(10)).collect::<Vec<_>>(),
+        vec![NodeId::from(20), NodeId::from(30)]
+    );
+    assert_eq!(graph.get_neighbor_positions(0), &[1, 2]);
+    assert_eq!(
+        graph.get_node(NodeId::from(40)).get_neighbor_positions(),
+        &[2]
+    );
+    assert_eq!(graph.get_node_degree(NodeId::from(40)), 1);
+    assert!(graph.has_node(NodeId::from(30)));
+    assert!(!graph.has_node(NodeId::from(25)));
+    assert!(!graph.has_node(NodeId::from(50)));
+    assert_eq!(graph.get_index(NodeId::from(30)), Some(2));
+    assert!(graph
+        .get_node(NodeId::from(20))
+        .has_neighbor(NodeId::from(10)));
+
+    let contiguous = CsrUndirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2)])?;
+    assert_eq!(contiguous.get_index(NodeId::from(2)), Some(2));
+    assert_eq!(contiguous.get_index(NodeId::from(3)), None);
+    assert_eq!(contiguous.get_index(NodeId::from(-1)), None);
+    assert_eq!(
+        CsrUndirectedGraph::create_empty().get_index(NodeId::from(0)),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_csr_matches_simple_graph() -> CLQResult<()> {
+    let mut builder = SimpleUndirectedGraphBuilder {};
+    let mut graphs = vec![
+        builder.get_complete_graph(6)?,
+        builder.get_cycle_graph(9)?,
+        builder.get_er_graph(60, 0.08, &mut StdRng::seed_from_u64(0))?,
+    ];
+    graphs.push(builder.from_vector(vec![(1, 2), (2, 3), (1, 3), (3, 4), (7, 8)])?);
+    for simple in &graphs {
+        let csr = CsrUndirectedGraph::from(simple);
+        assert_eq!(csr.count_nodes(), simple.count_nodes());
+        assert_eq!(csr.count_edges(), simple.count_edges());
+        for id in simple.get_ids_iter() {
+            assert_eq!(csr.get_node(*id).degree(), simple.get_node(*id).degree());
+        }
+        assert_eq!(csr.get_coreness_values(), simple.get_coreness_values());
+        let index = simple.get_dense_index();
+        assert_eq!(
+            csr.get_dense_adjacency(&index),
+            simple.get_dense_adjacency(&index)
+        );
+        assert_eq!(
+            sorted_components(csr.get_connected_components()),
+            sorted_components(simple.get_connected_components())
+        );
+        for k in 1..4 {
+            assert_eq!(
+                sorted_components(csr.get_k_cores(k)),
+                sorted_components(simple.get_k_cores(k))
+            );
+        }
+        let csr_cores: Vec<_> = csr
+            .get_coreness()
+            .0
+            .into_iter()
+            .map(sorted_components)
+            .collect();
+        let simple_cores: Vec<_> = simple
+            .get_coreness()
+            .0
+            .into_iter()
+            .map(sorted_components)
+            .collect();
+        assert_eq!(csr_cores, simple_cores);
+        if simple.count_edges() > 0 {
+            assert_eq!(csr.get_transitivity(), simple.get_transitivity());
+            for id in simple.get_ids_iter() {
+                assert_eq!(csr.triangle_count(*id), simple.triangle_count(*id));
+            }
+            let source = csr.ids()[0];
+            let (csr_stack, csr_counts, mut csr_preds) = csr.get_shortest_paths_bfs(source);
+            let (simple_stack, simple_counts, mut simple_preds) =
+                simple.get_shortest_paths_bfs(source);
+            assert_eq!(csr_stack.len(), simple_stack.len());
+            assert_eq!(csr_counts, simple_counts);
+            for preds in csr_preds.values_mut().chain(simple_preds.values_mut()) {
+                preds.sort();
+            }
+            assert_eq!(csr_preds, simple_preds);
+            let component = csr
+                .get_connected_components()
+                .into_iter()
+                .find(|x| x.contains(&source));
+            assert_eq!(
+                csr.get_shortest_paths(source, &component),
+                simple.get_shortest_paths(source, &component)
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_csr_from_snapshot() -> CLQResult<()> {
+    let simple = SimpleUndirectedGraphBuilder {}.get_path_graph(5)?;
+    let mut bytes: Vec<u8> = Vec::new();
+    simple.write_snapshot(&mut bytes)?;
+    let csr = CsrUndirectedGraph::from_snapshot(&GraphSnapshot::from_bytes(&bytes)?)?;
+    assert_eq!(csr.count_edges(), 5);
+    assert_eq!(
+        csr.get_neighbors(NodeId::from(3)).collect::<Vec<_>>(),
+        vec![NodeId::from(2), NodeId::from(4)]
+    );
+
+    let mut round_trip: Vec<u8> = Vec::new();
+    csr.write_snapshot(&mut round_trip)?;
+    assert_eq!(round_trip, bytes);
+
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2)])?;
+    let mut bytes: Vec<u8> = Vec::new();
+    directed.write_snapshot(&mut bytes)?;
+    assert!(CsrUndirectedGraph::from_snapshot(&GraphSnapshot::from_bytes(&bytes)?).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_csr_snapshot_view() -> CLQResult<()> {
+    let simple = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (1, 2),
+        (2, 3),
+        (1, 3),
+        (3, 4),
+        (7, 8),
+    ])?;
+    let mut bytes: Vec<u8> = Vec::new();
+    simple.write_snapshot(&mut bytes)?;
+    let snapshot = GraphSnapshot::from_bytes(&bytes)?;
+    let view = CsrSnapshotGraph::view(&snapshot)?;
+    // neighbor lists are read from the snapshot, not copied.
+    assert_eq!(
+        view.get_neighbor_positions(2).as_ptr(),
+        snapshot.neighbors(2).as_ptr()
+    );
+    assert_eq!(view.count_nodes(), 6);
+    assert_eq!(view.count_edges(), 5);
+    assert_eq!(
+        view.get_neighbors(NodeId::from(3)).collect::<Vec<_>>(),
+        vec![NodeId::from(1), NodeId::from(2), NodeId::from(4)]
+    );
+    assert!(view.get_node(NodeId::from(7)).has_neighbor(NodeId::from(8)));
+    assert!(!view.get_node(NodeId::from(7)).has_neighbor(NodeId::from(4)));
+    assert_eq!(view.get_coreness_values(), simple.get_coreness_values());
+    assert_eq!(
+        sorted_components(view.get_connected_components()),
+        sorted_components(simple.get_connected_components())
+    );
+    assert_eq!(view.get_transitivity(), simple.get_transitivity());
+    assert_eq!(
+        view.get_shortest_paths_bfs(NodeId::from(1)).1,
+        simple.get_shortest_paths_bfs(NodeId::from(1)).1
+    );
+
+    let mut round_trip: Vec<u8> = Vec::new();
+    view.write_snapshot(&mut round_trip)?;
+    assert_eq!(round_trip, bytes);
+    assert_eq!(CsrSnapshotGraph::create_empty().count_edges(), 0);
+
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2)])?;
+    let mut bytes: Vec<u8> = Vec::new();
+    directed.write_snapshot(&mut bytes)?;
+    assert!(CsrSnapshotGraph::view(&GraphSnapshot::from_bytes(&bytes)?).is_err());
+    Ok(())
+}
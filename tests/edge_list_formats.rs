@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::edge_list_formats::EdgeList;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+#[test]
+fn test_read_snap() -> CLQResult<()> {
+    let text = "# Directed graph\n# FromNodeId\tToNodeId\n1\t2\n2 3\n\n3\t1\n";
+    let graph = EdgeList::from_snap(text.as_bytes(), true)?.into_simple_directed_graph()?;
+    assert_eq!(graph.count_nodes(), 3);
+    assert_eq!(graph.count_edges(), 3);
+    assert!(graph.nodes[&NodeId::from(1)]
+        .out_neighbors
+        .contains(&NodeId::from(2)));
+    let graph = EdgeList::from_snap(text.as_bytes(), false)?.into_simple_undirected_graph()?;
+    assert_eq!(graph.count_edges(), 3);
+    assert!(EdgeList::from_snap("1\tx\n".as_bytes(), false).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_matrix_market() -> CLQResult<()> {
+    let text = [
+        "%%MatrixMarket matrix coordinate real symmetric",
+        "% a triangle and a pendant",
+        "4 4 4",
+        "2 1 1.5",
+        "3 1 2.0",
+        "3 2 1.0",
+        "4 3 0.5",
+    ]
+    .join("\n");
+    let edge_list = EdgeList::from_matrix_market(text.as_bytes())?;
+    assert!(edge_list.weighted);
+    assert!(!edge_list.directed);
+    let graph = edge_list.into_weighted_undirected_graph()?;
+    assert_eq!(graph.count_nodes(), 4);
+    assert_eq!(graph.count_edges(), 4);
+    assert_eq!(graph.get_node_weight(NodeId::from(3)), 3.5);
+
+    // node 3 is isolated.
+    let isolated = "%%MatrixMarket matrix coordinate pattern general\n3 3 1\n1 2\n";
+    let graph = EdgeList::from_matrix_market(isolated.as_bytes())?.into_simple_directed_graph()?;
+    assert_eq!(graph.count_nodes(), 3);
+    assert!(graph.nodes[&NodeId::from(3)].in_neighbors.is_empty());
+
+    let bad_count = "%%MatrixMarket matrix coordinate pattern general\n2 2 2\n1 2\n";
+    assert!(EdgeList::from_matrix_market(bad_count.as_bytes()).is_err());
+    let array = "%%MatrixMarket matrix array real general\n1 1\n1.0\n";
+    assert!(EdgeList::from_matrix_market(array.as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_metis() -> CLQResult<()> {
+    // node 4 is isolated, node 2 carries a vertex weight.
+    let text = "% comment\n4 1 011\n1 2 3\n5 1 3\n\n\n";
+    let edge_list = EdgeList::from_metis(text.as_bytes())?;
+    assert!(edge_list.weighted);
+    assert_eq!(edge_list.node_ids.len(), 4);
+    assert_eq!(edge_list.edges, vec![(1, 2, 3.0)]);
+    let graph = edge_list.clone().into_weighted_undirected_graph()?;
+    assert_eq!(graph.count_nodes(), 4);
+    assert_eq!(graph.get_node_degree(NodeId::from(4)), 0);
+    let graph = edge_list.into_simple_undirected_graph()?;
+    assert_eq!(graph.count_nodes(), 4);
+    assert_eq!(graph.ids.len(), 4);
+    match EdgeList::from_metis("2 1\n2\n1 2\n".as_bytes()) {
+        Err(CLQError::MalformedRow { line_number, .. }) => assert_eq!(line_number, 3),
+        _ => panic!("Expected a MalformedRow error."),
+    }
+    assert!(EdgeList::from_metis("3 2\n2\n1 3\n2\n".as_bytes()).is_ok());
+    assert!(EdgeList::from_metis("3 3\n2\n1 3\n2\n".as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_round_trips() -> CLQResult<()> {
+    let simple = SimpleUndirectedGraphBuilder {}.get_cycle_graph(5)?;
+    let edge_list = simple.as_edge_list();
+    assert_eq!(
+        EdgeList::from_snap(simple.as_snap_rows().as_bytes(), false)?,
+        edge_list
+    );
+    // ids 0..4 become 1..5 in Matrix Market and METIS files.
+    let shifted: Vec<(i64, i64, f64)> = edge_list
+        .edges
+        .iter()
+        .map(|(s, t, w)| (s + 1, t + 1, *w))
+        .collect();
+    let from_mtx = EdgeList::from_matrix_market(simple.as_matrix_market().as_bytes())?;
+    let mut mtx_edges: Vec<(i64, i64, f64)> = from_mtx
+        .edges
+        .iter()
+        .map(|(s, t, w)| (*std::cmp::min(s, t), *std::cmp::max(s, t), *w))
+        .collect();
+    mtx_edges.sort_by_key(|a| (a.0, a.1));
+    assert_eq!(mtx_edges, shifted);
+    assert_eq!(
+        EdgeList::from_metis(simple.as_metis()?.as_bytes())?.edges,
+        shifted
+    );
+
+    let weighted = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (1, 2, 0.5),
+        (2, 3, 2.0),
+        (1, 3, 4.0),
+    ])?;
+    let edge_list = weighted.as_edge_list();
+    assert_eq!(
+        EdgeList::from_snap(weighted.as_snap_rows().as_bytes(), false)?,
+        edge_list
+    );
+    assert_eq!(
+        EdgeList::from_metis(weighted.as_metis()?.as_bytes())?,
+        edge_list
+    );
+
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 1), (2, 3)])?;
+    let edge_list = directed.as_edge_list();
+    assert_eq!(
+        EdgeList::from_snap(directed.as_snap_rows().as_bytes(), true)?,
+        edge_list
+    );
+    assert_eq!(
+        EdgeList::from_matrix_market(directed.as_matrix_market().as_bytes())?,
+        edge_list
+    );
+    assert!(directed.as_edge_list().to_metis().is_err());
+    Ok(())
+}
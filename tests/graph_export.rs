@@ -0,0 +1,115 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::graph_export::{GraphExport, NamedTypedGraph, NodeAttribute};
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::test_utils::{
+    gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::typed_graph::TypedGraph;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use std::collections::BTreeSet;
+
+#[test]
+fn test_graphml_with_attributes() -> CLQResult<()> {
+    let graph =
+        SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3), (1, 3), (3, 4)])?;
+    let coreness = graph.get_coreness_values();
+    let clique: BTreeSet<NodeId> = vec![1, 2, 3].into_iter().map(NodeId::from).collect();
+    let graphml = graph.as_graphml(&[
+        NodeAttribute::new("coreness", &coreness),
+        NodeAttribute::membership("in <clique>", &clique),
+    ]);
+    let expected = [
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">",
+        "  <key id=\"a0\" for=\"node\" attr.name=\"coreness\" attr.type=\"long\"/>",
+        "  <key id=\"a1\" for=\"node\" attr.name=\"in &lt;clique&gt;\" attr.type=\"boolean\">\
+         <default>false</default></key>",
+        "  <graph id=\"G\" edgedefault=\"undirected\">",
+        "    <node id=\"1\"><data key=\"a0\">2</data><data key=\"a1\">true</data></node>",
+        "    <node id=\"2\"><data key=\"a0\">2</data><data key=\"a1\">true</data></node>",
+        "    <node id=\"3\"><data key=\"a0\">2</data><data key=\"a1\">true</data></node>",
+        "    <node id=\"4\"><data key=\"a0\">1</data></node>",
+        "    <edge source=\"1\" target=\"2\"/>",
+        "    <edge source=\"1\" target=\"3\"/>",
+        "    <edge source=\"2\" target=\"3\"/>",
+        "    <edge source=\"3\" target=\"4\"/>",
+        "  </graph>",
+        "</graphml>",
+    ]
+    .join("\n");
+    assert_eq!(graphml, expected);
+    Ok(())
+}
+
+#[test]
+fn test_gexf_weighted_and_directed() -> CLQResult<()> {
+    let weighted = WeightedUndirectedGraphBuilder {}.from_vector(vec![(1, 2, 0.5), (2, 3, 2.0)])?;
+    let gexf = weighted.as_gexf(&[]);
+    assert!(gexf.contains("<graph mode=\"static\" defaultedgetype=\"undirected\">"));
+    assert!(gexf.contains("<edge id=\"0\" source=\"1\" target=\"2\" weight=\"0.5\"/>"));
+    assert!(gexf.contains("<edge id=\"1\" source=\"2\" target=\"3\" weight=\"2\"/>"));
+    assert!(!gexf.contains("<attributes"));
+
+    let directed = SimpleDirectedGraphBuilder {}.from_vector(vec![(2, 1), (1, 2), (2, 3)])?;
+    let gexf = directed.as_gexf(&[]);
+    assert!(gexf.contains("defaultedgetype=\"directed\""));
+    assert_eq!(gexf.matches("<edge ").count(), 3);
+    assert!(directed
+        .as_graphml(&[])
+        .contains("edgedefault=\"directed\""));
+
+    let mut gexf_file: Vec<u8> = Vec::new();
+    directed.write_gexf(&mut gexf_file, &[])?;
+    assert_eq!(String::from_utf8(gexf_file)?, gexf + "\n");
+    let mut graphml_file: Vec<u8> = Vec::new();
+    weighted.write_graphml(&mut graphml_file, &[])?;
+    assert_eq!(
+        String::from_utf8(graphml_file)?,
+        weighted.as_graphml(&[]) + "\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_typed_graph_export() -> CLQResult<()> {
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished_at\tconference".to_string(),
+        "0\t2\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t2\t4\tauthor\tpublished_at\tjournal".into(),
+    ];
+    let rows = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(GraphId::from(0), rows)?;
+
+    let unnamed = graph.as_gexf(&[]);
+    assert!(unnamed
+        .contains("<node id=\"1\" label=\"1\"><attvalues><attvalue for=\"0\" value=\"core\"/>"));
+    assert_eq!(unnamed.matches("<edge ").count(), 3);
+
+    let named = NamedTypedGraph::new(
+        &graph,
+        &transformer.core_type,
+        &transformer.non_core_type_ids,
+        &transformer.edge_types,
+    );
+    let graphml = named.as_graphml(&[]);
+    assert!(graphml.contains("<node id=\"1\"><data key=\"node_type\">author</data></node>"));
+    assert!(graphml.contains("<node id=\"3\"><data key=\"node_type\">conference</data></node>"));
+    assert!(graphml.contains("<node id=\"4\"><data key=\"node_type\">journal</data></node>"));
+    assert!(graphml.contains(
+        "<edge source=\"2\" target=\"4\"><data key=\"edge_type\">published_at</data></edge>"
+    ));
+    Ok(())
+}
@@ -0,0 +1,181 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::graph_snapshot::{
+    GraphSnapshot, ReadSnapshot, WriteSnapshot, CORE_NODE_TYPE,
+};
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
+use lib_dachshund::dachshund::node::{DirectedNodeBase, NodeBase, WeightedNodeBase};
+use lib_dachshund::dachshund::simple_directed_graph::SimpleDirectedGraph;
+use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use lib_dachshund::dachshund::test_utils::{
+    gen_test_transformer, gen_test_typespec, process_raw_vector,
+};
+use lib_dachshund::dachshund::typed_graph::TypedGraph;
+use lib_dachshund::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+
+fn to_snapshot<G: WriteSnapshot>(graph: &G) -> CLQResult<GraphSnapshot> {
+    let mut bytes: Vec<u8> = Vec::new();
+    graph.write_snapshot(&mut bytes)?;
+    GraphSnapshot::from_bytes(&bytes)
+}
+
+/// reads a graph back from its snapshot, and checks it writes the same bytes.
+fn round_trip<G: ReadSnapshot>(graph: &G) -> CLQResult<G> {
+    let mut bytes: Vec<u8> = Vec::new();
+    graph.write_snapshot(&mut bytes)?;
+    let read = G::from_snapshot(&GraphSnapshot::from_bytes(&bytes)?)?;
+    let mut round_trip_bytes: Vec<u8> = Vec::new();
+    read.write_snapshot(&mut round_trip_bytes)?;
+    assert_eq!(round_trip_bytes, bytes);
+    Ok(read)
+}
+
+#[test]
+fn test_simple_undirected_snapshot() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(10, 20), (20, 30), (10, 30)])?;
+    let snapshot = to_snapshot(&graph)?;
+    assert!(!snapshot.is_directed());
+    assert!(!snapshot.has_weights());
+    assert_eq!(snapshot.num_nodes(), 3);
+    assert_eq!(snapshot.count_edges(), graph.count_edges());
+    assert_eq!(snapshot.node_ids(), &[10, 20, 30]);
+    assert_eq!(snapshot.get_index(NodeId::from(20)), Some(1));
+    assert_eq!(snapshot.get_index(NodeId::from(25)), None);
+    assert_eq!(snapshot.neighbors(0), &[1, 2]);
+    assert_eq!(snapshot.neighbors(1), &[0, 2]);
+    assert_eq!(snapshot.weights(0), None);
+    Ok(())
+}
+
+#[test]
+fn test_weighted_and_directed_snapshots() -> CLQResult<()> {
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(1, 2, 0.5), (2, 3, 2.0)])?;
+    let snapshot = to_snapshot(&graph)?;
+    assert_eq!(snapshot.neighbors(1), &[0, 2]);
+    assert_eq!(snapshot.weights(1), Some(&[0.5, 2.0][..]));
+
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2), (1, 3), (3, 2)])?;
+    let snapshot = to_snapshot(&graph)?;
+    assert!(snapshot.is_directed());
+    assert_eq!(snapshot.count_edges(), 3);
+    assert_eq!(snapshot.neighbors(0), &[1, 2]);
+    assert_eq!(snapshot.degree(1), 0);
+    assert_eq!(snapshot.neighbors(2), &[1]);
+    Ok(())
+}
+
+#[test]
+fn test_typed_snapshot() -> CLQResult<()> {
+    let transformer = gen_test_transformer(gen_test_typespec(), "author".to_string())?;
+    let raw = vec![
+        "0\t1\t3\tauthor\tpublished_at\tconference".to_string(),
+        "0\t2\t3\tauthor\tpublished_at\tconference".into(),
+        "0\t2\t4\tauthor\tpublished_at\tjournal".into(),
+    ];
+    let rows = process_raw_vector(&transformer, raw)?;
+    let graph: TypedGraph = transformer.build_pruned_graph(GraphId::from(0), rows)?;
+    let snapshot = to_snapshot(&graph)?;
+    assert!(snapshot.has_node_types() && snapshot.has_edge_types());
+    assert_eq!(snapshot.node_type(0), Some(CORE_NODE_TYPE));
+    let conference = snapshot.get_index(NodeId::from(3)).unwrap();
+    assert_ne!(snapshot.node_type(conference), Some(CORE_NODE_TYPE));
+    assert_eq!(snapshot.neighbors(conference), &[0, 1]);
+    assert_eq!(snapshot.edge_types(conference).unwrap().len(), 2);
+
+    let read = round_trip(&graph)?;
+    assert_eq!(read.get_core_ids(), &[NodeId::from(1), NodeId::from(2)]);
+    assert_eq!(read.get_non_core_ids().unwrap().len(), 2);
+    let node = read.get_node(NodeId::from(3));
+    assert!(!node.is_core());
+    assert_eq!(
+        node.non_core_type.map(|x| x.value()),
+        graph
+            .get_node(NodeId::from(3))
+            .non_core_type
+            .map(|x| x.value())
+    );
+    assert_eq!(node.degree(), 2);
+    assert_eq!(node.neighbors.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_read_snapshots() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(10, 20), (20, 30), (10, 30)])?;
+    let read: SimpleUndirectedGraph = round_trip(&graph)?;
+    assert_eq!(read.get_ordered_node_ids(), graph.get_ordered_node_ids());
+    assert_eq!(read.count_edges(), 3);
+
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(1, 2, 0.5), (2, 3, 2.0)])?;
+    let read: WeightedUndirectedGraph = round_trip(&graph)?;
+    assert_eq!(read.get_node(NodeId::from(2)).weight(), 2.5);
+    assert_eq!(read.count_edges(), 2);
+
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2), (1, 3), (3, 2)])?;
+    let read: SimpleDirectedGraph = round_trip(&graph)?;
+    let node = read.get_node(NodeId::from(2));
+    assert_eq!(node.get_in_neighbors().count(), 2);
+    assert_eq!(node.get_out_neighbors().count(), 0);
+
+    // snapshots only read back into graphs of the kind that wrote them.
+    let snapshot = to_snapshot(&graph)?;
+    assert!(SimpleUndirectedGraph::from_snapshot(&snapshot).is_err());
+    let snapshot = to_snapshot(&SimpleUndirectedGraphBuilder {}.get_path_graph(3)?)?;
+    assert!(SimpleDirectedGraph::from_snapshot(&snapshot).is_err());
+    assert!(WeightedUndirectedGraph::from_snapshot(&snapshot).is_err());
+    assert!(TypedGraph::from_snapshot(&snapshot).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_memory_mapped_snapshot() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_path_graph(100)?;
+    let path = std::env::temp_dir().join(format!("dachshund_snapshot_{}.csr", std::process::id()));
+    let path = path.to_str().unwrap();
+    graph.save_snapshot(path)?;
+    let snapshot = GraphSnapshot::open(path)?;
+    assert_eq!(snapshot.num_nodes(), 101);
+    assert_eq!(snapshot.count_edges(), 100);
+    for i in 1..100 {
+        assert_eq!(snapshot.neighbors(i), &[i as u32 - 1, i as u32 + 1]);
+    }
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_corrupt_snapshots() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.get_cycle_graph(4)?;
+    let mut bytes: Vec<u8> = Vec::new();
+    graph.write_snapshot(&mut bytes)?;
+    assert!(GraphSnapshot::from_bytes(&bytes[..bytes.len() - 8]).is_err());
+    assert!(GraphSnapshot::from_bytes(b"not a snapshot, not even close.").is_err());
+    let mut bad_offsets = bytes.clone();
+    // first CSR offset sits right after the header and the 4 node ids.
+    bad_offsets[32 + 4 * 8] = 1;
+    assert!(GraphSnapshot::from_bytes(&bad_offsets).is_err());
+    let mut bad_neighbors = bytes.clone();
+    // neighbor indices follow the 5 CSR offsets; node 4 does not exist.
+    bad_neighbors[32 + 4 * 8 + 5 * 8] = 4;
+    assert!(GraphSnapshot::from_bytes(&bad_neighbors).is_err());
+    let mut unsorted_ids = bytes.clone();
+    // node ids 0 and 1, swapped.
+    unsorted_ids[32] = 1;
+    unsorted_ids[40] = 0;
+    assert!(GraphSnapshot::from_bytes(&unsorted_ids).is_err());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate rand;
+
+use lib_dachshund::dachshund::algorithms::coreness::Coreness;
+use lib_dachshund::dachshund::algorithms::incremental_coreness::IncrementalCoreness;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+fn get_changes(before: &HashMap<NodeId, usize>, after: &HashMap<NodeId, usize>) -> HashSet<NodeId> {
+    after
+        .iter()
+        .filter(|(id, value)| before.get(id) != Some(value))
+        .map(|(id, _)| *id)
+        .collect()
+}
+
+#[test]
+fn test_insert_and_remove_edges() -> CLQResult<()> {
+    // a triangle with a pendant node.
+    let graph =
+        SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3), (1, 3), (3, 4)])?;
+    let mut cores = IncrementalCoreness::new(graph);
+    assert_eq!(cores.get_coreness(NodeId::from(4)), 1);
+
+    // closing a second triangle doesn't make a 3-core.
+    let changed = cores.insert_edge(NodeId::from(2), NodeId::from(4));
+    assert_eq!(changed, vec![NodeId::from(4)].into_iter().collect());
+    assert_eq!(cores.get_coreness(NodeId::from(4)), 2);
+    let changed = cores.insert_edge(NodeId::from(1), NodeId::from(4));
+    assert_eq!(changed.len(), 4);
+    assert_eq!(cores.get_coreness(NodeId::from(1)), 3);
+    assert!(cores
+        .insert_edge(NodeId::from(1), NodeId::from(4))
+        .is_empty());
+
+    let changed = cores.insert_edge(NodeId::from(4), NodeId::from(5));
+    assert_eq!(changed, vec![NodeId::from(5)].into_iter().collect());
+    assert_eq!(cores.get_coreness(NodeId::from(5)), 1);
+
+    let changed = cores.remove_edge(NodeId::from(1), NodeId::from(2));
+    assert_eq!(changed.len(), 4);
+    assert!(!changed.contains(&NodeId::from(5)));
+    assert!(cores
+        .remove_edge(NodeId::from(1), NodeId::from(2))
+        .is_empty());
+
+    // what's left is the path 1-3-2 and an isolated node 5.
+    let changed = cores.remove_node(NodeId::from(4));
+    assert_eq!(changed.len(), 4);
+    assert_eq!(cores.get_coreness(NodeId::from(3)), 1);
+    assert_eq!(cores.get_coreness(NodeId::from(5)), 0);
+    assert!(!cores.get_coreness_values().contains_key(&NodeId::from(4)));
+    Ok(())
+}
+
+#[test]
+fn test_matches_recomputed_coreness() -> CLQResult<()> {
+    let mut rng = StdRng::seed_from_u64(7);
+    let graph = SimpleUndirectedGraphBuilder {}.get_er_graph(40, 0.1, &mut rng)?;
+    let mut cores = IncrementalCoreness::new(graph);
+    for _ in 0..400 {
+        let id1 = NodeId::from(rng.gen_range(0, 45) as i64);
+        let id2 = NodeId::from(rng.gen_range(0, 45) as i64);
+        let before = cores.get_coreness_values().clone();
+        let changed = if rng.gen_bool(0.6) {
+            cores.insert_edge(id1, id2)
+        } else {
+            cores.remove_edge(id1, id2)
+        };
+        let expected = cores.get_graph().get_coreness_values();
+        assert_eq!(cores.get_coreness_values(), &expected);
+        assert_eq!(changed, get_changes(&before, &expected));
+    }
+    Ok(())
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::bad_rows::BadRowPolicy;
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::input_format::{Delimiter, InputFormat};
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+
+fn run_connected_components(text: &str, format: InputFormat) -> CLQResult<Vec<String>> {
+    let bytes = text.as_bytes();
+    let input = Input::string(bytes).with_format(format);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    let mut transformer = ConnectedComponentsTransformer::new();
+    transformer.run(input, output)?;
+    let mut lines: Vec<String> = String::from_utf8(buffer)?
+        .lines()
+        .map(|x| x.to_owned())
+        .collect();
+    lines.sort();
+    Ok(lines)
+}
+
+#[test]
+fn test_split_fields() {
+    let csv = InputFormat {
+        delimiter: Delimiter::Char(','),
+        quote_char: Some('"'),
+        ..InputFormat::default()
+    };
+    assert_eq!(
+        csv.split_fields("\"a,b\",1,\"say \"\"hi\"\"\",\r"),
+        vec!["a,b", "1", "say \"hi\"", ""]
+    );
+    let whitespace = InputFormat {
+        delimiter: Delimiter::Whitespace,
+        ..InputFormat::default()
+    };
+    assert_eq!(whitespace.split_fields("  a \t 1  2 "), vec!["a", "1", "2"]);
+}
+
+#[test]
+fn test_csv_with_header_and_comments() -> CLQResult<()> {
+    let text = [
+        "# edges, target first",
+        "dst,src,graph",
+        "2,1,\"x,y\"",
+        "",
+        "3,2,\"x,y\"",
+        "# another comment",
+        "5,4,z",
+    ]
+    .join("\n");
+    let format = InputFormat {
+        delimiter: Delimiter::Char(','),
+        has_header: true,
+        comment_char: Some('#'),
+        quote_char: Some('"'),
+        column_map: vec![("graph_id", "graph"), ("source_id", "src"), ("target_id", "dst")]
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect(),
+    };
+    assert_eq!(
+        run_connected_components(&text, format)?,
+        vec!["x,y\t0\t1", "x,y\t0\t2", "x,y\t0\t3", "z\t0\t4", "z\t0\t5"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_header_matched_by_name() -> CLQResult<()> {
+    let text = "target_id source_id graph_id\n2 1 a\n4  3  b\n";
+    let format = InputFormat {
+        delimiter: Delimiter::Whitespace,
+        has_header: true,
+        ..InputFormat::default()
+    };
+    assert_eq!(
+        run_connected_components(text, format)?,
+        vec!["a\t0\t1", "a\t0\t2", "b\t0\t3", "b\t0\t4"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_column_indices_without_header() -> CLQResult<()> {
+    let text = "ignored;1;2;a\nignored;2;3;a\n";
+    let format = InputFormat {
+        delimiter: Delimiter::Char(';'),
+        column_map: vec![("graph_id", "3"), ("source_id", "1"), ("target_id", "2")]
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect(),
+        ..InputFormat::default()
+    };
+    assert_eq!(
+        run_connected_components(text, format)?,
+        vec!["a\t0\t1", "a\t0\t2", "a\t0\t3"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unresolvable_header_is_an_error() {
+    let text = "g,s,t\na,1,2\n";
+    let format = InputFormat {
+        delimiter: Delimiter::Char(','),
+        has_header: true,
+        column_map: vec![("source_id".to_owned(), "from".to_owned())]
+            .into_iter()
+            .collect(),
+        ..InputFormat::default()
+    };
+    assert!(run_connected_components(text, format).is_err());
+}
+
+#[test]
+fn test_quoted_tab_is_a_bad_row() -> CLQResult<()> {
+    let text = "a,1,2\n\"a\tb\",3,4\nc,5,6\n";
+    let format = InputFormat {
+        delimiter: Delimiter::Char(','),
+        quote_char: Some('"'),
+        ..InputFormat::default()
+    };
+    assert!(run_connected_components(text, format.clone()).is_err());
+    let input = Input::string(text.as_bytes()).with_format(format);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    let mut transformer = ConnectedComponentsTransformer::new();
+    let summary = transformer.run_with_bad_row_policy(input, output, BadRowPolicy::Skip)?;
+    assert_eq!(summary.num_rejected, 1);
+    let mut lines: Vec<String> = String::from_utf8(buffer)?
+        .lines()
+        .map(|x| x.to_owned())
+        .collect();
+    lines.sort();
+    assert_eq!(lines, vec!["a\t0\t1", "a\t0\t2", "c\t0\t5", "c\t0\t6"]);
+    Ok(())
+}
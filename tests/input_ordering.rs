@@ -0,0 +1,111 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::input_ordering::{GraphRowBuffer, InputOrdering};
+use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+
+const INTERLEAVED: &str = "0\t1\t2\n1\t5\t6\n0\t2\t3\n2\t8\t9\n1\t6\t7\n0\t10\t11\n";
+
+fn run_connected_components(text: &str, ordering: InputOrdering) -> CLQResult<Vec<String>> {
+    let bytes = text.as_bytes();
+    let input = Input::string(bytes).with_ordering(ordering);
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer);
+    let mut transformer = ConnectedComponentsTransformer::new();
+    transformer.run(input, output)?;
+    let mut lines: Vec<String> = String::from_utf8(buffer)?
+        .lines()
+        .map(|x| x.to_owned())
+        .collect();
+    lines.sort();
+    Ok(lines)
+}
+
+fn get_expected() -> Vec<&'static str> {
+    vec![
+        "0\t0\t1", "0\t0\t2", "0\t0\t3", "0\t1\t10", "0\t1\t11", "1\t0\t5", "1\t0\t6", "1\t0\t7",
+        "2\t0\t8", "2\t0\t9",
+    ]
+}
+
+#[test]
+fn test_reappearing_graph_id_fails() -> CLQResult<()> {
+    let error = run_connected_components(INTERLEAVED, InputOrdering::Grouped)
+        .err()
+        .unwrap();
+    assert!(format!("{}", error).contains("Rows of graph 0 reappear on line 3"));
+    // grouped input is unaffected.
+    let grouped = "0\t1\t2\n0\t2\t3\n0\t10\t11\n1\t5\t6\n1\t6\t7\n2\t8\t9\n";
+    assert_eq!(
+        run_connected_components(grouped, InputOrdering::Grouped)?,
+        get_expected()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unsorted_in_memory() -> CLQResult<()> {
+    assert_eq!(
+        run_connected_components(INTERLEAVED, InputOrdering::unsorted())?,
+        get_expected()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unsorted_with_spilling() -> CLQResult<()> {
+    let spill_dir =
+        std::env::temp_dir().join(format!("dachshund_ordering_test_{}", std::process::id()));
+    std::fs::create_dir_all(&spill_dir)?;
+    let ordering = InputOrdering::Unsorted {
+        max_buffered_rows: 2,
+        spill_dir: spill_dir.clone(),
+    };
+    assert_eq!(
+        run_connected_components(INTERLEAVED, ordering)?,
+        get_expected()
+    );
+    // spill files are cleaned up afterwards.
+    assert_eq!(std::fs::read_dir(&spill_dir)?.count(), 0);
+    std::fs::remove_dir(&spill_dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_spilled_rows_keep_graph_ids() -> CLQResult<()> {
+    let spill_dir =
+        std::env::temp_dir().join(format!("dachshund_row_buffer_test_{}", std::process::id()));
+    std::fs::create_dir_all(&spill_dir)?;
+    let mut buffer = GraphRowBuffer::new(1, spill_dir.clone());
+    // lines are opaque to the buffer: they need not even carry their graph id.
+    buffer.push(GraphId::from(-5), "a\tb".to_string())?;
+    buffer.push(GraphId::from(3), "c".to_string())?;
+    buffer.push(GraphId::from(-5), "d".to_string())?;
+    assert!(buffer.has_spilled());
+    let mut graphs: Vec<(i64, Vec<String>)> = Vec::new();
+    buffer.drain(|graph_id, lines| {
+        graphs.push((graph_id.value(), lines));
+        Ok(())
+    })?;
+    graphs.sort();
+    assert_eq!(
+        graphs,
+        vec![
+            (-5, vec!["a\tb".to_string(), "d".to_string()]),
+            (3, vec!["c".to_string()])
+        ]
+    );
+    assert_eq!(std::fs::read_dir(&spill_dir)?.count(), 0);
+    std::fs::remove_dir(&spill_dir)?;
+    Ok(())
+}
@@ -10,7 +10,7 @@ extern crate lib_dachshund;
 extern crate test;
 use lib_dachshund::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
 use lib_dachshund::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
-use lib_dachshund::dachshund::algorithms::betweenness::Betweenness;
+use lib_dachshund::dachshund::algorithms::betweenness::{Betweenness, BetweennessOptions};
 use lib_dachshund::dachshund::algorithms::brokerage::Brokerage;
 use lib_dachshund::dachshund::algorithms::clustering::Clustering;
 use lib_dachshund::dachshund::algorithms::cnm_communities::CNMCommunities;
@@ -22,6 +22,7 @@ use lib_dachshund::dachshund::algorithms::connectivity::{
 };
 use lib_dachshund::dachshund::algorithms::coreness::Coreness;
 use lib_dachshund::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use lib_dachshund::dachshund::algorithms::girvan_newman::GirvanNewman;
 use lib_dachshund::dachshund::algorithms::laplacian::Laplacian;
 use lib_dachshund::dachshund::algorithms::shortest_paths::ShortestPaths;
 use lib_dachshund::dachshund::algorithms::transitivity::Transitivity;
@@ -36,6 +37,8 @@ use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use lib_dachshund::dachshund::simple_undirected_graph_builder::{
     SimpleUndirectedGraphBuilder, SimpleUndirectedGraphBuilderWithCliques,
 };
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use test::Bencher;
 
@@ -406,6 +409,140 @@ fn test_betweenness_brandes() -> CLQResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_normalized_betweenness() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    let options = BetweennessOptions {
+        normalized: true,
+        endpoints: false,
+        weighted: false,
+    };
+    let bet = graph.get_node_betweenness_brandes_with_options(options)?;
+    assert!((bet[&NodeId::from(1)] - 0.4376352814).abs() <= 1e-9);
+    assert!((bet[&NodeId::from(34)] - 0.3040749759).abs() <= 1e-9);
+    let options = BetweennessOptions {
+        normalized: true,
+        endpoints: true,
+        weighted: false,
+    };
+    let bet_with_endpoints = graph.get_node_betweenness_brandes_with_options(options)?;
+    for (id, value) in &bet {
+        // over n(n - 1) ordered pairs, each node is an endpoint of 2(n - 1).
+        let expected = (value * 32.0 * 33.0 + 2.0 * 33.0) / (34.0 * 33.0);
+        assert!((bet_with_endpoints[id] - expected).abs() <= 1e-9);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_parallel_betweenness() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    for normalized in &[false, true] {
+        for endpoints in &[false, true] {
+            let options = BetweennessOptions {
+                normalized: *normalized,
+                endpoints: *endpoints,
+                weighted: false,
+            };
+            let bet = graph.get_node_betweenness_brandes_with_options(options)?;
+            let parallel_bet = graph.get_node_betweenness_brandes_parallel(options)?;
+            for (id, value) in &bet {
+                assert!((parallel_bet[id] - value).abs() <= 1e-9);
+            }
+        }
+    }
+    // restricted to some sources, this matches the path-enumerating version.
+    let sources: Vec<NodeId> = (1..=34).step_by(3).map(NodeId::from).collect();
+    let bet = graph.get_node_betweenness_starting_from_sources(&sources, false, None)?;
+    let parallel_bet = graph.get_node_betweenness_brandes_parallel_from_sources(
+        &sources,
+        BetweennessOptions::default(),
+    )?;
+    for (id, value) in &bet {
+        assert!((parallel_bet[id] - value).abs() <= 1e-9);
+    }
+    assert!(graph
+        .get_node_betweenness_brandes_parallel_from_sources(
+            &[NodeId::from(35)],
+            BetweennessOptions::default()
+        )
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_betweenness_disconnected() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (3, 4),
+        (4, 5),
+        (4, 6),
+    ])?;
+    let bet = graph.get_node_betweenness_brandes()?;
+    assert_eq!(bet[&NodeId::from(1)], 1.0);
+    assert_eq!(bet[&NodeId::from(4)], 3.0);
+    assert_eq!(bet[&NodeId::from(5)], 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_edge_betweenness() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    let edge_bet = graph.get_edge_betweenness()?;
+    assert_eq!(edge_bet.len(), graph.count_edges());
+    assert!(edge_bet.keys().all(|(x, y)| x < y));
+    // each pair's shortest paths cross as many edges as the pair is far apart.
+    let mut total_distance: f64 = 0.0;
+    for id in graph.get_ids_iter() {
+        let (dist, _parents) = graph.get_shortest_paths(*id, &None);
+        total_distance += dist.values().map(|d| d.unwrap() as f64).sum::<f64>();
+    }
+    assert!((edge_bet.values().sum::<f64>() - total_distance / 2.0).abs() < 1e-6);
+    // paths through a node use two of its edges, paths to it one.
+    let bet = graph.get_node_betweenness_brandes()?;
+    for (id, value) in &bet {
+        let incident: f64 = edge_bet
+            .iter()
+            .filter(|((x, y), _)| x == id || y == id)
+            .map(|(_, b)| b)
+            .sum();
+        assert!((incident - 2.0 * value - 33.0).abs() < 1e-6);
+    }
+    assert!(SimpleUndirectedGraph::create_empty()
+        .get_edge_betweenness()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_girvan_newman() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    let dendrogram = graph.get_girvan_newman_communities()?;
+    assert_eq!(dendrogram.levels.len(), graph.count_nodes());
+    for (i, level) in dendrogram.levels.iter().enumerate() {
+        assert_eq!(level.communities.len(), i + 1);
+    }
+    assert_eq!(
+        dendrogram
+            .levels
+            .iter()
+            .map(|x| x.removed_edges.len())
+            .sum::<usize>(),
+        graph.count_edges()
+    );
+    // the first split is into the two factions of the club, up to node 3.
+    let first_split = &dendrogram.levels[1].communities;
+    assert!(first_split[0].contains(&NodeId::from(1)));
+    assert!(first_split[1].contains(&NodeId::from(34)));
+    assert!((dendrogram.get_best_modularity() - 0.4013).abs() < 1e-4);
+    assert_eq!(dendrogram.get_best_communities().len(), 5);
+    assert!(SimpleUndirectedGraph::create_empty()
+        .get_girvan_newman_communities()
+        .is_err());
+    Ok(())
+}
+
 #[bench]
 fn bench_betweenness(b: &mut Bencher) -> CLQResult<()> {
     b.iter(|| {
@@ -415,6 +552,15 @@ fn bench_betweenness(b: &mut Bencher) -> CLQResult<()> {
     Ok(())
 }
 
+#[bench]
+fn bench_betweenness_brandes_parallel(b: &mut Bencher) -> CLQResult<()> {
+    b.iter(|| {
+        let graph = get_karate_club_graph().unwrap();
+        let _bet = graph.get_node_betweenness_brandes_parallel(BetweennessOptions::default());
+    });
+    Ok(())
+}
+
 #[bench]
 fn bench_betweenness_brandes(b: &mut Bencher) -> CLQResult<()> {
     b.iter(|| {
@@ -449,17 +595,54 @@ fn test_matrices() -> CLQResult<()> {
 #[test]
 fn test_eigen() -> CLQResult<()> {
     let graph = get_karate_club_graph()?;
-    let fiedler = graph.get_algebraic_connectivity();
+    let fiedler = graph.get_algebraic_connectivity()?.value;
     assert!((fiedler - 0.469).abs() <= 0.001);
 
     let eps = 0.001;
-    let ev = graph.get_eigenvector_centrality(eps, 1000);
+    let ev = graph.get_eigenvector_centrality(eps, 1000)?.value;
     assert!((ev[&NodeId::from(34 as i64)] - 1.0).abs() <= eps);
     assert!((ev[&NodeId::from(1 as i64)] - 0.95213237).abs() <= eps);
     assert!((ev[&NodeId::from(19 as i64)] - 0.27159396).abs() <= eps);
     Ok(())
 }
 
+#[test]
+fn test_sparse_matrices() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    let (adj_mat, ids) = graph.get_adjacency_matrix();
+    let (sparse_adj_mat, sparse_ids) = graph.get_sparse_adjacency_matrix();
+    assert_eq!(ids, sparse_ids);
+    assert_eq!(sparse_adj_mat.num_nonzeros(), 156);
+    assert_eq!(sparse_adj_mat.get(6, 16), 1.0);
+    assert_eq!(sparse_adj_mat.get(6, 17), 0.0);
+    assert_eq!(sparse_adj_mat.to_dense(), adj_mat);
+    let (laplacian, _ids) = graph.get_laplacian_matrix();
+    let (sparse_laplacian, _ids) = graph.get_sparse_laplacian_matrix();
+    assert_eq!(sparse_laplacian.to_dense(), laplacian);
+    let (normalized, _ids) = graph.get_normalized_laplacian_matrix();
+    assert_eq!(normalized, normalized.transpose());
+    assert!(normalized
+        .diagonal()
+        .iter()
+        .all(|x| (x - 1.0).abs() < 1e-12));
+    assert!((normalized.row(0)[1] + 1.0 / (16.0_f64 * 9.0).sqrt()).abs() < 1e-12);
+    Ok(())
+}
+
+#[test]
+fn test_sparse_eigen() -> CLQResult<()> {
+    let graph = get_karate_club_graph()?;
+    let fiedler = graph.get_algebraic_connectivity_sparse(1e-10)?.value;
+    assert!((fiedler - graph.get_algebraic_connectivity_dense()?.value).abs() <= 1e-8);
+
+    let eps = 0.001;
+    let ev = graph.get_eigenvector_centrality_sparse(1e-10, 1000)?.value;
+    assert!((ev[&NodeId::from(34)] - 1.0).abs() <= eps);
+    assert!((ev[&NodeId::from(1)] - 0.95213237).abs() <= eps);
+    assert!((ev[&NodeId::from(19)] - 0.27159396).abs() <= eps);
+    Ok(())
+}
+
 #[test]
 fn test_k_cores() -> CLQResult<()> {
     let graph = get_karate_club_graph()?;
@@ -510,7 +693,7 @@ fn test_transitivity() -> CLQResult<()> {
     println!("{}", trans);
     assert!((trans - 0.2556818181818182).abs() <= f64::EPSILON);
 
-    let approx_trans = graph.get_approx_transitivity(1000);
+    let approx_trans = graph.get_approx_transitivity(1000, &mut StdRng::seed_from_u64(0));
     println!("{}", approx_trans);
     assert!((approx_trans - trans).abs() <= 0.05);
     Ok(())
@@ -0,0 +1,163 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+extern crate serde_json;
+
+use lib_dachshund::dachshund::connected_components_transformer::ConnectedComponentsTransformer;
+use lib_dachshund::dachshund::core_transformer::CoreTransformer;
+use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::id_types::GraphId;
+use lib_dachshund::dachshund::input::Input;
+use lib_dachshund::dachshund::line_processor::{LineProcessor, LineProcessorBase};
+use lib_dachshund::dachshund::output::{Output, OutputSender};
+use lib_dachshund::dachshund::output_format::{Field, OutputFormat, OutputRecord};
+use lib_dachshund::dachshund::row::Row;
+use lib_dachshund::dachshund::transformer_base::TransformerBase;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// emits one line per edge of each graph, from a separate thread which takes
+/// its time, and fails on graphs without any edge into node 0.
+struct EdgeEchoTransformer {
+    line_processor: Arc<LineProcessor>,
+    batch: Vec<(i64, i64)>,
+    handles: Vec<thread::JoinHandle<()>>,
+}
+impl TransformerBase for EdgeEchoTransformer {
+    fn get_line_processor(&self) -> Arc<dyn LineProcessorBase> {
+        self.line_processor.clone()
+    }
+    fn process_row(&mut self, row: Box<dyn Row>) -> CLQResult<()> {
+        self.batch
+            .push(row.as_simple_edge_row().unwrap().as_tuple());
+        Ok(())
+    }
+    fn reset(&mut self) -> CLQResult<()> {
+        self.batch.clear();
+        Ok(())
+    }
+    fn process_batch(&mut self, graph_id: GraphId, output: &OutputSender) -> CLQResult<()> {
+        let output = output.clone();
+        let edges = self.batch.clone();
+        self.handles.push(thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            if edges.iter().all(|(_, target)| *target != 0) {
+                output.send_error(CLQError::from(format!("graph {}", graph_id.value())));
+                return;
+            }
+            for (source, target) in edges {
+                output
+                    .send_line(format!("{}\t{}\t{}", graph_id.value(), source, target))
+                    .unwrap();
+            }
+        }));
+        Ok(())
+    }
+}
+
+fn run_echo(text: &str) -> (CLQResult<()>, Vec<String>) {
+    let mut transformer = EdgeEchoTransformer {
+        line_processor: Arc::new(LineProcessor::new()),
+        batch: Vec::new(),
+        handles: Vec::new(),
+    };
+    let mut buffer: Vec<u8> = Vec::new();
+    let result = transformer.run(Input::string(text.as_bytes()), Output::string(&mut buffer));
+    let mut lines: Vec<String> = String::from_utf8(buffer)
+        .unwrap()
+        .lines()
+        .map(|x| x.to_owned())
+        .collect();
+    lines.sort();
+    (result, lines)
+}
+
+#[test]
+fn test_output_waits_for_all_senders() {
+    let (result, lines) = run_echo("0\t1\t0\n0\t2\t0\n0\t3\t0\n1\t5\t0\n");
+    assert!(result.is_ok());
+    assert_eq!(lines, vec!["0\t1\t0", "0\t2\t0", "0\t3\t0", "1\t5\t0"]);
+}
+
+#[test]
+fn test_output_reports_batch_errors() {
+    let (result, lines) = run_echo("0\t1\t0\n1\t5\t6\n");
+    assert_eq!(format!("{}", result.err().unwrap()), "graph 1");
+    assert!(lines.len() <= 1);
+}
+
+#[test]
+fn test_output_to_file() -> CLQResult<()> {
+    let path = std::env::temp_dir().join(format!("dachshund_output_test_{}", std::process::id()));
+    let output = Output::file(path.to_str().unwrap())?;
+    let mut transformer = ConnectedComponentsTransformer::new();
+    transformer.run(Input::string(b"0\t1\t2\n0\t2\t3\n1\t5\t6\n"), output)?;
+    let mut lines: Vec<String> = std::fs::read_to_string(&path)?
+        .lines()
+        .map(|x| x.to_owned())
+        .collect();
+    lines.sort();
+    assert_eq!(
+        lines,
+        vec!["0\t0\t1", "0\t0\t2", "0\t0\t3", "1\t0\t5", "1\t0\t6"]
+    );
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_output_to_writer() -> CLQResult<()> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut output = Output::writer(std::io::BufWriter::new(&mut buffer));
+    output.print("a".to_owned())?;
+    output.print("b".to_owned())?;
+    drop(output);
+    assert_eq!(buffer, b"a\nb\n");
+    Ok(())
+}
+
+#[test]
+fn test_output_record_formats() {
+    let record = OutputRecord::new()
+        .field("graph_id", "a\"b")
+        .field("node_id", 3_i64)
+        .field("score", 0.5_f64)
+        .field("anomaly", f64::NAN)
+        .field("ids", Field::Json("[1,2]".to_owned()));
+    assert_eq!(record.render(OutputFormat::Tsv), "a\"b\t3\t0.5\tNaN\t[1,2]");
+    assert_eq!(
+        record.render(OutputFormat::Jsonl),
+        "{\"graph_id\":\"a\\\"b\",\"node_id\":3,\"score\":0.5,\"anomaly\":null,\"ids\":[1,2]}"
+    );
+    assert!(OutputFormat::parse("xml").is_err());
+}
+
+#[test]
+fn test_jsonl_output() -> CLQResult<()> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let output = Output::string(&mut buffer).with_format(OutputFormat::Jsonl);
+    let mut transformer = CoreTransformer::new();
+    transformer.run(Input::string(b"g1\t1\t2\ng1\t2\t3\ng1\t1\t3\n"), output)?;
+    let mut lines: Vec<serde_json::Value> = String::from_utf8(buffer)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    lines.sort_by_key(|x| x["node_id"].as_i64());
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[0],
+        serde_json::json!({
+            "graph_id": "g1",
+            "node_id": 1,
+            "coreness": 2,
+            "degree": 2,
+            "coreness_anomaly": 0
+        })
+    );
+    Ok(())
+}
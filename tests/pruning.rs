@@ -16,7 +16,7 @@ use lib_dachshund::dachshund::transformer::Transformer;
 use lib_dachshund::dachshund::typed_graph::TypedGraph;
 use lib_dachshund::dachshund::typed_graph_builder::TypedGraphBuilder;
 use std::collections::HashSet;
-use std::sync::mpsc::channel;
+use lib_dachshund::dachshund::output::output_channel;
 
 pub fn gen_test_typespec() -> Vec<Vec<String>> {
     return vec![
@@ -133,7 +133,7 @@ fn test_full_prune_small_clique() -> CLQResult<()> {
     let ts = gen_test_typespec();
 
     // with pruning at degree < 3
-    let (sender_prune, _receiver_prune) = channel();
+    let (sender_prune, _receiver_prune) = output_channel();
 
     let transformer_prune = Transformer::new(
         ts.clone(),
@@ -156,13 +156,12 @@ fn test_full_prune_small_clique() -> CLQResult<()> {
     let result_prune = transformer_prune
         .process_clique_rows(&graph, &v_prune, graph_id, false, &sender_prune)?
         .ok_or_else(CLQError::err_none)?;
-    sender_prune.send((None, true)).unwrap();
     let candidate_prune = result_prune.top_candidate;
     assert_nodes_have_ids(&graph, &candidate_prune.core_ids, vec![1, 2], true);
     assert_nodes_have_ids(&graph, &candidate_prune.non_core_ids, vec![3], false);
 
     // without any pruning
-    let (sender, _receiver) = channel();
+    let (sender, _receiver) = output_channel();
     let transformer = Transformer::new(
         ts,
         20,
@@ -184,7 +183,6 @@ fn test_full_prune_small_clique() -> CLQResult<()> {
     let result = transformer
         .process_clique_rows(&graph, &v, graph_id, false, &sender)?
         .ok_or_else(CLQError::err_none)?;
-    sender.send((None, true)).unwrap();
     let candidate = result.top_candidate;
     assert_nodes_have_ids(&graph, &candidate.core_ids, vec![1, 2], true);
     assert_nodes_have_ids(&graph, &candidate.non_core_ids, vec![3], false);
@@ -5,9 +5,12 @@
  * LICENSE file in the root directory of this source tree.
  */
 extern crate lib_dachshund;
+use lib_dachshund::dachshund::algorithms::betweenness::{Betweenness, BetweennessOptions};
+use lib_dachshund::dachshund::algorithms::directed_centrality::DirectedCentrality;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
 use lib_dachshund::dachshund::simple_directed_graph::SimpleDirectedGraph;
 use lib_dachshund::dachshund::simple_directed_graph_builder::SimpleDirectedGraphBuilder;
 use std::collections::HashSet;
@@ -153,3 +156,145 @@ fn test_build_graph() -> CLQResult<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_directed_graph_mutation() -> CLQResult<()> {
+    let mut graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3)])?;
+    assert!(graph.add_edge(NodeId::from(3), NodeId::from(1)));
+    assert!(graph.add_edge(NodeId::from(1), NodeId::from(3)));
+    assert!(!graph.add_edge(NodeId::from(3), NodeId::from(1)));
+    assert_eq!(graph.count_edges(), 4);
+    assert!(graph.nodes[&NodeId::from(1)]
+        .in_neighbors
+        .contains(&NodeId::from(3)));
+
+    assert!(graph.remove_edge(NodeId::from(1), NodeId::from(2)));
+    assert!(!graph.remove_edge(NodeId::from(1), NodeId::from(2)));
+    assert!(graph.nodes[&NodeId::from(2)].in_neighbors.is_empty());
+    assert!(graph.remove_node(NodeId::from(3)));
+    assert_eq!(graph.ids.len(), 2);
+    assert_eq!(graph.count_edges(), 0);
+    assert!(graph.nodes[&NodeId::from(1)].out_neighbors.is_empty());
+    assert!(graph.nodes[&NodeId::from(2)].out_neighbors.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_katz_centrality() -> CLQResult<()> {
+    let star = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 0), (2, 0), (3, 0)])?;
+    let katz = star.get_katz_centrality(0.1, 1.0, 1e-9, 100);
+    assert!(katz.convergence.converged);
+    assert_eq!(katz.convergence.iterations, 2);
+    assert_eq!(katz.convergence.residual, 0.0);
+    assert!((katz.value[&NodeId::from(0)] - 1.3).abs() < 1e-12);
+    assert_eq!(katz.value[&NodeId::from(1)], 1.0);
+
+    // walks around a cycle are not discounted enough for alpha = 2.
+    let cycle = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 0)])?;
+    let katz = cycle.get_katz_centrality(2.0, 1.0, 1e-9, 20);
+    assert!(!katz.convergence.converged);
+    assert_eq!(katz.convergence.iterations, 20);
+    assert!(katz.convergence.residual > 1.0);
+    Ok(())
+}
+
+#[test]
+fn test_directed_eigenvector_centrality() -> CLQResult<()> {
+    let edges = vec![(0, 1), (1, 2), (2, 0), (0, 2), (2, 3), (3, 0)];
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(edges.clone())?;
+    let in_centrality = graph.get_in_eigenvector_centrality(1e-12, 1000);
+    let out_centrality = graph.get_out_eigenvector_centrality(1e-12, 1000);
+    assert!(in_centrality.convergence.converged);
+    assert!(out_centrality.convergence.converged);
+    assert!(in_centrality.convergence.residual <= 1e-12);
+    // check x_i = (sum of x_j over j -> i) / lambda, and likewise for out-edges.
+    let x = |scores: &std::collections::HashMap<NodeId, f64>, i: i64| scores[&NodeId::from(i)];
+    let in_sums: Vec<f64> = (0..4)
+        .map(|i| {
+            edges
+                .iter()
+                .filter(|e| e.1 == i)
+                .map(|e| x(&in_centrality.value, e.0))
+                .sum()
+        })
+        .collect();
+    let out_sums: Vec<f64> = (0..4)
+        .map(|i| {
+            edges
+                .iter()
+                .filter(|e| e.0 == i)
+                .map(|e| x(&out_centrality.value, e.1))
+                .sum()
+        })
+        .collect();
+    for i in 0..4 {
+        assert!(
+            (in_sums[i as usize] / x(&in_centrality.value, i)
+                - in_sums[0] / x(&in_centrality.value, 0))
+            .abs()
+                < 1e-9
+        );
+        assert!(
+            (out_sums[i as usize] / x(&out_centrality.value, i)
+                - out_sums[0] / x(&out_centrality.value, 0))
+            .abs()
+                < 1e-9
+        );
+    }
+    // 2 has the most incoming edges and 0 the most outgoing ones.
+    assert_eq!(x(&in_centrality.value, 2), 1.0);
+    assert_eq!(x(&out_centrality.value, 0), 1.0);
+    Ok(())
+}
+
+#[test]
+fn test_hits_scores() -> CLQResult<()> {
+    let graph = SimpleDirectedGraphBuilder {}.from_vector(vec![(1, 0), (2, 0), (3, 0), (3, 4)])?;
+    let hits = graph.get_hits_scores(1e-12, 1000);
+    assert!(hits.convergence.converged);
+    assert!(hits.convergence.iterations < 1000);
+    assert_eq!(hits.value.authorities[&NodeId::from(0)], 1.0);
+    assert_eq!(hits.value.authorities[&NodeId::from(1)], 0.0);
+    // the leading eigenvector of A^T A restricted to {0, 4} is (1, sqrt(2) - 1).
+    let sqrt2 = 2.0_f64.sqrt();
+    assert!((hits.value.authorities[&NodeId::from(4)] - (sqrt2 - 1.0)).abs() < 1e-9);
+    assert_eq!(hits.value.hubs[&NodeId::from(3)], 1.0);
+    assert!((hits.value.hubs[&NodeId::from(1)] - 1.0 / sqrt2).abs() < 1e-9);
+    assert_eq!(hits.value.hubs[&NodeId::from(0)], 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_directed_betweenness() -> CLQResult<()> {
+    // only 0 -> 2 goes through 1; in the undirected path, 2 -> 0 would too.
+    let path = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2)])?;
+    let bet = path.get_node_betweenness_brandes()?;
+    assert_eq!(bet[&NodeId::from(0)], 0.0);
+    assert_eq!(bet[&NodeId::from(1)], 1.0);
+    assert_eq!(bet[&NodeId::from(2)], 0.0);
+
+    // 1 lies on the path 0 -> 2, and is an endpoint of 0 -> 1 and 1 -> 2.
+    let options = BetweennessOptions {
+        normalized: false,
+        endpoints: true,
+        weighted: false,
+    };
+    let bet = path.get_node_betweenness_brandes_with_options(options)?;
+    assert_eq!(bet[&NodeId::from(0)], 2.0);
+    assert_eq!(bet[&NodeId::from(1)], 3.0);
+    assert_eq!(bet[&NodeId::from(2)], 2.0);
+
+    // each node of a directed cycle lies on the path between its neighbors.
+    let cycle = SimpleDirectedGraphBuilder {}.from_vector(vec![(0, 1), (1, 2), (2, 3), (3, 0)])?;
+    let options = BetweennessOptions {
+        normalized: true,
+        endpoints: false,
+        weighted: false,
+    };
+    let bet = cycle.get_node_betweenness_brandes_with_options(options)?;
+    for i in 0..4 {
+        // each node is inside the paths of 3 of the 3 * 2 pairs of other nodes.
+        assert!((bet[&NodeId::from(i)] - 3.0 / 6.0).abs() < 1e-12);
+    }
+    Ok(())
+}
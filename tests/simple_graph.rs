@@ -7,6 +7,7 @@
 extern crate lib_dachshund;
 
 use crate::lib_dachshund::TransformerBase;
+use lib_dachshund::dachshund::algorithms::betweenness::{Betweenness, BetweennessOptions};
 use lib_dachshund::dachshund::algorithms::cnm_communities::CNMCommunities;
 use lib_dachshund::dachshund::algorithms::connected_components::{
     ConnectedComponents, ConnectedComponentsUndirected,
@@ -15,15 +16,19 @@ use lib_dachshund::dachshund::algorithms::coreness::averaged_ties_ranking;
 use lib_dachshund::dachshund::algorithms::coreness::Coreness;
 use lib_dachshund::dachshund::algorithms::k_peaks::KPeaks;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
+use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
-use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::id_types::{GraphId, NodeId};
 use lib_dachshund::dachshund::input::Input;
 use lib_dachshund::dachshund::output::Output;
+use lib_dachshund::dachshund::seed::Seed;
 use lib_dachshund::dachshund::simple_transformer::{
-    GraphStatsTransformerBase, SimpleParallelTransformer, SimpleTransformer,
+    BetweennessMode, GraphStatsTransformerBase, SimpleParallelTransformer, SimpleTransformer,
 };
 use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::iter::FromIterator;
 
@@ -365,6 +370,11 @@ fn test_averaged_ties_ranking() {
     }
 }
 
+// the pseudorandom sequence transformers use for the i-th graph of their input.
+fn get_rng(i: usize) -> StdRng {
+    Seed::default().get_rng(GraphId::from(i as i64))
+}
+
 #[test]
 fn test_simple_transformer() {
     let mut transformer = SimpleTransformer::new();
@@ -380,7 +390,7 @@ fn test_simple_transformer() {
     let expected = graphs
         .iter()
         .enumerate()
-        .map(|(i, x)| format!("{}\t{}", i, SimpleTransformer::compute_graph_stats_json(x)))
+        .map(|(i, x)| format!("{}\t{}", i, SimpleTransformer::compute_graph_stats_json(x, BetweennessMode::Exact, &mut get_rng(i)).unwrap()))
         .collect::<Vec<String>>()
         .join("\n");
 
@@ -414,7 +424,12 @@ fn test_parallel_transformer() {
             format!(
                 "{}\t{}",
                 i,
-                SimpleParallelTransformer::compute_graph_stats_json(x)
+                SimpleParallelTransformer::compute_graph_stats_json(
+                    x,
+                    BetweennessMode::Exact,
+                    &mut get_rng(i)
+                )
+                .unwrap()
             )
         })
         .collect::<Vec<String>>()
@@ -488,3 +503,163 @@ fn test_k_peaks() {
     assert_eq!(mountain_assignments[&1].contains_key(&NodeId::from(14 as i64)), true);
 
 }
+
+#[test]
+fn test_dense_index() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(30, 10), (10, 20), (20, 40)])?;
+    let index = graph.get_dense_index();
+    assert_eq!(index.len(), 4);
+    assert_eq!(index.get_index(NodeId::from(30)), 2);
+    assert_eq!(index.get_id(3), NodeId::from(40));
+    assert_eq!(index.try_get_index(NodeId::from(50)), None);
+    assert_eq!(
+        graph.get_dense_adjacency(&index),
+        vec![vec![1, 2], vec![0, 3], vec![0], vec![1]]
+    );
+    let degrees = index.to_node_map(vec![2, 2, 1, 1]);
+    assert_eq!(degrees[&NodeId::from(10)], 2);
+    assert_eq!(degrees[&NodeId::from(40)], 1);
+    Ok(())
+}
+
+#[test]
+fn test_graph_mutation() -> CLQResult<()> {
+    let mut graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 2), (2, 3), (3, 1)])?;
+    assert!(graph.add_edge(NodeId::from(3), NodeId::from(4)));
+    assert!(!graph.add_edge(NodeId::from(4), NodeId::from(3)));
+    assert!(graph.add_node(NodeId::from(5)));
+    assert!(!graph.add_node(NodeId::from(5)));
+    assert_eq!(graph.count_nodes(), 5);
+    assert_eq!(graph.ids.len(), 5);
+    assert_eq!(graph.count_edges(), 4);
+    assert_eq!(graph.get_node_degree(NodeId::from(4)), 1);
+
+    assert!(graph.remove_edge(NodeId::from(2), NodeId::from(1)));
+    assert!(!graph.remove_edge(NodeId::from(1), NodeId::from(2)));
+    assert!(!graph.get_node(NodeId::from(1)).neighbors.contains(&NodeId::from(2)));
+    assert!(graph.remove_node(NodeId::from(3)));
+    assert!(!graph.remove_node(NodeId::from(3)));
+    assert!(!graph.ids.contains(&NodeId::from(3)));
+    assert_eq!(graph.count_edges(), 0);
+    for node in graph.get_nodes_iter() {
+        assert!(!node.neighbors.contains(&NodeId::from(3)));
+    }
+    assert_eq!(graph.get_connected_components().len(), 4);
+    Ok(())
+}
+
+// a side x side grid.
+fn get_grid_graph(side: i64) -> CLQResult<SimpleUndirectedGraph> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for i in 0..side {
+        for j in 0..side {
+            if i + 1 < side {
+                edges.push((i * side + j, (i + 1) * side + j));
+            }
+            if j + 1 < side {
+                edges.push((i * side + j, i * side + j + 1));
+            }
+        }
+    }
+    SimpleUndirectedGraphBuilder {}.from_vector(edges)
+}
+
+#[test]
+fn test_approximate_betweenness() -> CLQResult<()> {
+    // 900 nodes, but shortest paths of at most 59 nodes.
+    let graph = get_grid_graph(30)?;
+    for normalized in &[false, true] {
+        let options = BetweennessOptions {
+            normalized: *normalized,
+            endpoints: false,
+            weighted: false,
+        };
+        let exact = graph.get_node_betweenness_brandes_with_options(options)?;
+        let mut rng = StdRng::seed_from_u64(0);
+        let approx = graph.get_approximate_node_betweenness(0.1, 0.1, options, &mut rng)?;
+        // the vertex diameter bound is 58 + 57 + 1 (from a corner), so there are
+        // 0.5 / 0.1^2 * (floor(log2(116 - 2)) + 1 + ln(10)) samples.
+        assert_eq!(approx.num_samples, 466);
+        for (id, value) in &exact {
+            assert!((approx.values[id] - value).abs() <= approx.max_error);
+        }
+        let sum: f64 = approx.values.values().sum();
+        let exact_sum: f64 = exact.values().sum();
+        assert!((sum - exact_sum).abs() <= 0.1 * exact_sum);
+    }
+    // asking for more precision than the graph has nodes gives exact values.
+    let mut rng = StdRng::seed_from_u64(0);
+    let approx =
+        graph.get_approximate_node_betweenness(0.01, 0.1, BetweennessOptions::default(), &mut rng)?;
+    assert_eq!(approx.num_samples, 0);
+    assert_eq!(approx.max_error, 0.0);
+    let options = BetweennessOptions {
+        normalized: false,
+        endpoints: true,
+        weighted: false,
+    };
+    assert!(graph
+        .get_approximate_node_betweenness(0.1, 0.1, options, &mut rng)
+        .is_err());
+    assert!(graph
+        .get_approximate_node_betweenness(0.0, 0.1, BetweennessOptions::default(), &mut rng)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_seeded_transformers() -> CLQResult<()> {
+    // large enough for betweenness to be sampled (2061 sources for 2500 nodes).
+    let text = get_grid_graph(50)?.as_input_rows(0);
+    let seed = Seed::new(5);
+    let betweenness = BetweennessMode::Approximate {
+        epsilon: 0.05,
+        delta: 0.1,
+    };
+    let run = |parallel: bool| -> CLQResult<String> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let input = Input::string(text.as_bytes());
+        let output = Output::string(&mut buffer);
+        if parallel {
+            SimpleParallelTransformer::new()
+                .with_seed(seed)
+                .with_betweenness(betweenness)
+                .run(input, output)?;
+        } else {
+            SimpleTransformer::new()
+                .with_seed(seed)
+                .with_betweenness(betweenness)
+                .run(input, output)?;
+        }
+        Ok(String::from_utf8(buffer)?)
+    };
+    let expected = run(false)?;
+    assert_eq!(run(false)?, expected);
+    assert_eq!(run(true)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_graph_stats_json() -> CLQResult<()> {
+    // stats of earlier releases, which computed betweenness exactly on graphs
+    // this small. Exact betweenness does not depend on the seed, only its last
+    // digit on the order in which sources get summed.
+    let expected = vec![
+        (0, 13.789, "{\"clust_coef\":0.089,\"evcent\":0.46,\"num_16_cores\":0,\"num_17_trusses\":0,\"num_2_cores\":1,\"num_3_trusses\":2,\"num_4_cores\":0,\"num_5_trusses\":0,\"num_8_cores\":0,\"num_9_trusses\":0,\"num_connected_components\":1,\"num_edges\":28,\"size_of_largest_cc\":19}"),
+        (6, 11.839, "{\"clust_coef\":0.233,\"evcent\":0.484,\"num_16_cores\":0,\"num_17_trusses\":0,\"num_2_cores\":1,\"num_3_trusses\":1,\"num_4_cores\":1,\"num_5_trusses\":0,\"num_8_cores\":0,\"num_9_trusses\":0,\"num_connected_components\":1,\"num_edges\":72,\"size_of_largest_cc\":25}"),
+    ];
+    for (i, bet_cent, stats) in expected {
+        let graph = get_graph(i)?;
+        let expected_stats: serde_json::Value = serde_json::from_str(stats)?;
+        for seed in &[Seed::default(), Seed::new(5)] {
+            let mut rng = seed.get_rng(GraphId::from(i as i64));
+            let json = SimpleTransformer::compute_graph_stats_json(&graph, BetweennessMode::Exact, &mut rng)?;
+            let mut actual: serde_json::Value = serde_json::from_str(&json)?;
+            let actual_bet_cent = actual["bet_cent"].as_f64().unwrap();
+            assert!((actual_bet_cent - bet_cent).abs() <= 0.0015);
+            actual.as_object_mut().unwrap().remove("bet_cent");
+            assert_eq!(actual, expected_stats);
+        }
+    }
+    Ok(())
+}
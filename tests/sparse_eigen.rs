@@ -0,0 +1,216 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+use lib_dachshund::dachshund::algorithms::eigenvector_centrality::EigenvectorCentrality;
+use lib_dachshund::dachshund::algorithms::lanczos::{
+    LanczosSolver, Spectrum, SPARSE_SOLVER_MIN_NODES,
+};
+use lib_dachshund::dachshund::algorithms::laplacian::Laplacian;
+use lib_dachshund::dachshund::algorithms::sparse_matrix::{LinearOperator, SparseGraphMatrix};
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_base::GraphBase;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::{
+    SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn get_cycle(n: i64, offset: i64) -> Vec<(i64, i64)> {
+    (0..n).map(|i| (offset + i, offset + (i + 1) % n)).collect()
+}
+
+#[test]
+fn test_sparse_matrix() {
+    let matrix = SparseGraphMatrix::from_rows(vec![
+        vec![(1, 1.0), (1, 1.0), (0, 3.0)],
+        vec![],
+        vec![(2, -1.0)],
+    ]);
+    assert_eq!(matrix.dim(), 3);
+    assert_eq!(matrix.num_nonzeros(), 3);
+    assert_eq!(
+        matrix.get_row(0).collect::<Vec<_>>(),
+        vec![(0, 3.0), (1, 2.0)]
+    );
+    assert_eq!(matrix.get(0, 1), 2.0);
+    assert_eq!(matrix.get(1, 1), 0.0);
+    let mut y = vec![0.0; 3];
+    matrix.apply(&[1.0, 2.0, 3.0], &mut y);
+    assert_eq!(y, vec![7.0, 0.0, -3.0]);
+}
+
+#[test]
+fn test_lanczos_cycle_spectrum() -> CLQResult<()> {
+    // the Laplacian of a cycle has eigenvalues 2 - 2cos(2 pi k / n).
+    let n = 101;
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_cycle(n, 0))?;
+    let (laplacian, _ids) = graph.get_sparse_laplacian_matrix();
+    let solver = LanczosSolver::new(1e-10);
+    let expected_max = 2.0 - 2.0 * (2.0 * std::f64::consts::PI * 50.0 / n as f64).cos();
+    let largest = solver.solve(&laplacian, 1, Spectrum::Largest, &[]);
+    assert!((largest.values[0] - expected_max).abs() < 1e-8);
+
+    let smallest = solver.solve(&laplacian, 1, Spectrum::Smallest, &[]);
+    assert!(smallest.values[0].abs() < 1e-8);
+    // the null space of a connected graph's Laplacian is the constant vector.
+    let entry = smallest.vectors[0][0];
+    assert!((entry - 1.0 / (n as f64).sqrt()).abs() < 1e-6);
+    assert!(smallest.vectors[0].iter().all(|x| (x - entry).abs() < 1e-6));
+    Ok(())
+}
+
+#[test]
+fn test_lanczos_restarts() -> CLQResult<()> {
+    // the Laplacian of a path has simple eigenvalues 2 - 2cos(pi k / n).
+    let n = 200;
+    let edges: Vec<(i64, i64)> = (0..n - 1).map(|i| (i, i + 1)).collect();
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    let (laplacian, _ids) = graph.get_sparse_laplacian_matrix();
+    let expected = |k: usize| 2.0 - 2.0 * (std::f64::consts::PI * k as f64 / n as f64).cos();
+    // a basis of 30 vectors must be restarted many times over.
+    let solver = LanczosSolver::new(1e-10).with_max_dim(30);
+    let largest = solver.solve(&laplacian, 3, Spectrum::Largest, &[]);
+    assert!(largest.convergence.converged);
+    assert!(largest.convergence.iterations > 30);
+    for (i, value) in largest.values.iter().enumerate() {
+        assert!((value - expected(n as usize - 1 - i)).abs() < 1e-8);
+    }
+    let mut y = vec![0.0; n as usize];
+    laplacian.apply(&largest.vectors[0], &mut y);
+    for (y_i, x_i) in y.iter().zip(&largest.vectors[0]) {
+        assert!((y_i - largest.values[0] * x_i).abs() < 1e-6);
+    }
+
+    // running out of steps is reported, rather than growing the basis.
+    let truncated = LanczosSolver::new(1e-10)
+        .with_max_dim(10)
+        .with_max_iter(25)
+        .solve(&laplacian, 1, Spectrum::Smallest, &[vec![1.0; n as usize]]);
+    assert!(!truncated.convergence.converged);
+    assert_eq!(truncated.convergence.iterations, 25);
+    assert!(truncated.convergence.residual > 1e-10);
+    assert_eq!(truncated.values.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_sparse_algebraic_connectivity() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_cycle(30, 0))?;
+    let expected = 2.0 - 2.0 * (2.0 * std::f64::consts::PI / 30.0).cos();
+    assert!((graph.get_algebraic_connectivity_sparse(1e-10)?.value - expected).abs() < 1e-8);
+    assert!((graph.get_algebraic_connectivity_dense()?.value - expected).abs() < 1e-8);
+
+    // two components: the second smallest eigenvalue is 0 as well.
+    let mut edges = get_cycle(30, 0);
+    edges.extend(get_cycle(20, 100));
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(edges)?;
+    assert!(graph.get_algebraic_connectivity_sparse(1e-10)?.value.abs() < 1e-8);
+    assert!(graph.get_algebraic_connectivity_dense()?.value.abs() < 1e-8);
+    Ok(())
+}
+
+#[test]
+fn test_sparse_matches_dense() -> CLQResult<()> {
+    let graph: SimpleUndirectedGraph =
+        SimpleUndirectedGraphBuilder {}.get_er_graph(150, 0.05, &mut StdRng::seed_from_u64(0))?;
+    let ev = graph.get_eigenvector_centrality_sparse(1e-10, 1000)?.value;
+    let dense_ev = graph.get_eigenvector_centrality_dense(1e-10, 10000)?.value;
+    for (id, value) in &dense_ev {
+        assert!((ev[id] - value).abs() < 1e-4);
+    }
+    let fiedler = graph.get_algebraic_connectivity_sparse(1e-10)?.value;
+    assert!((fiedler - graph.get_algebraic_connectivity_dense()?.value).abs() < 1e-6);
+    Ok(())
+}
+
+#[test]
+fn test_large_graph_uses_sparse_solver() -> CLQResult<()> {
+    let n = SPARSE_SOLVER_MIN_NODES as u64 + 50;
+    let graph: SimpleUndirectedGraph =
+        SimpleUndirectedGraphBuilder {}.get_er_graph(n, 0.02, &mut StdRng::seed_from_u64(0))?;
+    assert!(graph.count_nodes() >= SPARSE_SOLVER_MIN_NODES);
+    assert_eq!(
+        graph.get_eigenvector_centrality(1e-8, 1000)?.value,
+        graph.get_eigenvector_centrality_sparse(1e-8, 1000)?.value
+    );
+    assert_eq!(
+        graph.get_algebraic_connectivity()?.value,
+        graph.get_algebraic_connectivity_sparse(1e-8)?.value
+    );
+    Ok(())
+}
+
+#[test]
+fn test_degenerate_spectral_inputs() -> CLQResult<()> {
+    let mut graph = SimpleUndirectedGraph::create_empty();
+    assert!(graph.get_algebraic_connectivity().is_err());
+    graph.add_node(NodeId::from(0));
+    assert!(graph.get_algebraic_connectivity_dense().is_err());
+    assert!(graph.get_algebraic_connectivity_sparse(1e-8).is_err());
+    assert!(graph.get_fiedler_vector().is_err());
+    assert!(graph.get_eigenvector_centrality(1e-8, 100).is_err());
+    assert!(graph.get_eigenvector_centrality_sparse(1e-8, 100).is_err());
+
+    graph.add_node(NodeId::from(1));
+    assert_eq!(graph.get_algebraic_connectivity()?.value, 0.0);
+    graph.add_edge(NodeId::from(0), NodeId::from(1));
+    let connectivity = graph.get_algebraic_connectivity()?;
+    assert!((connectivity.value - 2.0).abs() < 1e-12);
+    assert!(connectivity.convergence.converged);
+    assert_eq!(connectivity.convergence.iterations, 0);
+    Ok(())
+}
+
+#[test]
+fn test_convergence_reporting() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 4),
+    ])?;
+    let ev = graph.get_eigenvector_centrality_dense(1e-10, 1000)?;
+    assert!(ev.convergence.converged);
+    assert!(ev.convergence.iterations > 1 && ev.convergence.iterations < 1000);
+    assert!(ev.convergence.residual <= 1e-10);
+    let truncated = graph.get_eigenvector_centrality_dense(1e-10, 3)?;
+    assert!(!truncated.convergence.converged);
+    assert_eq!(truncated.convergence.iterations, 3);
+    assert!(truncated.convergence.residual > 1e-10);
+    assert!(truncated.into_converged().is_err());
+
+    let sparse_ev = graph.get_eigenvector_centrality_sparse(1e-10, 1000)?;
+    assert!(sparse_ev.convergence.converged);
+    assert!(sparse_ev.convergence.residual <= 1e-10);
+    let sparse_ev = sparse_ev.into_converged()?;
+    for (id, x) in ev.value {
+        assert!((x - sparse_ev[&id]).abs() < 1e-6);
+    }
+    let connectivity = graph.get_algebraic_connectivity_sparse(1e-10)?;
+    assert!(connectivity.convergence.converged);
+    assert!(connectivity.convergence.iterations <= graph.count_nodes());
+    Ok(())
+}
+
+#[test]
+fn test_lanczos_nan_does_not_panic() {
+    let rows = (0..20)
+        .map(|i| vec![(i, if i == 3 { f64::NAN } else { 1.0 })])
+        .collect();
+    let matrix = SparseGraphMatrix::from_rows(rows);
+    let solver = LanczosSolver::new(1e-8).with_max_iter(50);
+    for which in [Spectrum::Smallest, Spectrum::Largest] {
+        let eigen = solver.solve(&matrix, 2, which, &[]);
+        assert_eq!(eigen.values.len(), 2);
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) Facebook, Inc. and its affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+extern crate lib_dachshund;
+
+use lib_dachshund::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+use lib_dachshund::dachshund::algorithms::spectral_clustering::SpectralClustering;
+use lib_dachshund::dachshund::error::CLQResult;
+use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
+use lib_dachshund::dachshund::id_types::NodeId;
+use lib_dachshund::dachshund::simple_undirected_graph_builder::SimpleUndirectedGraphBuilder;
+use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// `num_cliques` cliques of `size` nodes each, the last node of each clique
+/// linked to the first node of the next one.
+fn get_chain_of_cliques(num_cliques: i64, size: i64) -> Vec<(i64, i64)> {
+    let mut edges: Vec<(i64, i64)> = Vec::new();
+    for c in 0..num_cliques {
+        for i in 0..size {
+            for j in (i + 1)..size {
+                edges.push((c * size + i, c * size + j));
+            }
+        }
+        if c + 1 < num_cliques {
+            edges.push((c * size + size - 1, (c + 1) * size));
+        }
+    }
+    edges
+}
+
+fn get_cliques(num_cliques: i64, size: i64) -> Vec<Vec<NodeId>> {
+    (0..num_cliques)
+        .map(|c| (0..size).map(|i| NodeId::from(c * size + i)).collect())
+        .collect()
+}
+
+#[test]
+fn test_fiedler_vector() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_chain_of_cliques(2, 5))?;
+    let fiedler = graph.get_fiedler_vector()?.value;
+    assert_eq!(fiedler.len(), 10);
+    let sign = fiedler[&NodeId::from(0)].signum();
+    for i in 0..10 {
+        let expected = if i < 5 { sign } else { -sign };
+        assert_eq!(fiedler[&NodeId::from(i)].signum(), expected);
+    }
+    // the Fiedler vector is a unit eigenvector orthogonal to the constant vector.
+    assert!(fiedler.values().sum::<f64>().abs() < 1e-8);
+    assert!((fiedler.values().map(|x| x * x).sum::<f64>() - 1.0).abs() < 1e-8);
+    Ok(())
+}
+
+#[test]
+fn test_spectral_bisection() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_chain_of_cliques(2, 6))?;
+    let (first, second) = graph.get_spectral_bisection()?.value;
+    let cliques = get_cliques(2, 6);
+    assert_eq!(first, cliques[0]);
+    assert_eq!(second, cliques[1]);
+
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(vec![(1, 2)])?;
+    assert_eq!(
+        graph.get_spectral_bisection()?.value,
+        (vec![NodeId::from(1)], vec![NodeId::from(2)])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_recursive_spectral_partition() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_chain_of_cliques(4, 6))?;
+    assert_eq!(graph.get_spectral_partition(4)?.value, get_cliques(4, 6));
+    assert_eq!(graph.get_spectral_partition(1)?.value.len(), 1);
+    // parts can't be split beyond single nodes.
+    assert_eq!(graph.get_spectral_partition(100)?.value.len(), 24);
+    Ok(())
+}
+
+#[test]
+fn test_spectral_clusters() -> CLQResult<()> {
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_chain_of_cliques(3, 8))?;
+    // k-means finds the cliques whichever way it is seeded.
+    for seed in 0..5 {
+        let clusters = graph.get_spectral_clusters(3, &mut StdRng::seed_from_u64(seed))?;
+        assert_eq!(clusters.value, get_cliques(3, 8));
+    }
+
+    let weighted_edges: Vec<(i64, i64, f64)> = get_chain_of_cliques(3, 8)
+        .into_iter()
+        .map(|(source, target)| (source, target, 1.0))
+        .collect();
+    let weighted_graph = WeightedUndirectedGraphBuilder {}.from_vector(weighted_edges)?;
+    assert_eq!(
+        weighted_graph
+            .get_spectral_clusters(3, &mut StdRng::seed_from_u64(0))?
+            .value,
+        get_cliques(3, 8)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_spectral_clustering_large_graph() -> CLQResult<()> {
+    // large enough for the sparse solver to kick in.
+    let graph = SimpleUndirectedGraphBuilder {}.from_vector(get_chain_of_cliques(4, 130))?;
+    let partition = graph.get_spectral_partition(4)?;
+    assert!(partition.convergence.converged);
+    assert!(partition.convergence.iterations > 0);
+    assert_eq!(partition.value, get_cliques(4, 130));
+    let clusters = graph.get_spectral_clusters(4, &mut StdRng::seed_from_u64(0))?;
+    assert!(clusters.convergence.converged);
+    assert!(clusters.convergence.iterations > 0);
+    assert_eq!(clusters.into_converged()?, get_cliques(4, 130));
+    Ok(())
+}
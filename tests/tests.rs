@@ -17,7 +17,7 @@ use lib_dachshund::dachshund::test_utils::{
 };
 use lib_dachshund::dachshund::transformer::Transformer;
 use lib_dachshund::dachshund::typed_graph::TypedGraph;
-use std::sync::mpsc::channel;
+use lib_dachshund::dachshund::output::output_channel;
 
 #[cfg(test)]
 #[test]
@@ -121,14 +121,13 @@ where
     let rows = process_raw_vector(&transformer, raw).unwrap();
     let graph: TypedGraph = transformer.build_pruned_graph(graph_id, rows).unwrap();
     let clique_rows = Vec::new();
-    let (sender, _receiver) = channel();
+    let (sender, _receiver) = output_channel();
     let res: Candidate<TypedGraph> = transformer
         .process_clique_rows(&graph, &clique_rows, graph_id, true, &sender)
         .unwrap()
         .ok_or_else(CLQError::err_none)
         .unwrap()
         .top_candidate;
-    sender.send((None, true)).unwrap();
     f(&graph, &res);
     Ok(())
 }
@@ -18,6 +18,8 @@ use lib_dachshund::dachshund::simple_undirected_graph::SimpleUndirectedGraph;
 use lib_dachshund::dachshund::simple_undirected_graph_builder::{
     SimpleUndirectedGraphBuilder, TSimpleUndirectedGraphBuilder,
 };
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 use test::Bencher;
 
@@ -82,10 +84,14 @@ fn test_transitivity() -> CLQResult<()> {
 #[test]
 fn test_approx_avg_clustering() -> CLQResult<()> {
     let k4 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
-    assert_eq!(1.0, k4.get_approx_avg_clustering(10));
+    assert_eq!(
+        1.0,
+        k4.get_approx_avg_clustering(10, &mut StdRng::seed_from_u64(0))
+    );
 
     let almost_k4 = &get_almost_k4_graph()?;
-    let approx_clustering = almost_k4.get_approx_avg_clustering(100000);
+    let approx_clustering =
+        almost_k4.get_approx_avg_clustering(100000, &mut StdRng::seed_from_u64(0));
     assert!(((5 as f64 / 6 as f64) - approx_clustering).abs() <= 0.01);
     Ok(())
 }
@@ -93,10 +99,14 @@ fn test_approx_avg_clustering() -> CLQResult<()> {
 #[test]
 fn test_approx_transitivity() -> CLQResult<()> {
     let k4 = &SimpleUndirectedGraphBuilder {}.get_complete_graph(4)?;
-    assert_eq!(1.0, k4.get_approx_transitivity(10));
+    assert_eq!(
+        1.0,
+        k4.get_approx_transitivity(10, &mut StdRng::seed_from_u64(0))
+    );
 
     let almost_k4 = &get_almost_k4_graph()?;
-    let approx_transitivity = almost_k4.get_approx_transitivity(100000);
+    let approx_transitivity =
+        almost_k4.get_approx_transitivity(100000, &mut StdRng::seed_from_u64(0));
 
     println!("{}", approx_transitivity);
 
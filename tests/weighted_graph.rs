@@ -6,14 +6,20 @@
  */
 extern crate lib_dachshund;
 
+use lib_dachshund::dachshund::algorithms::adjacency_matrix::AdjacencyMatrix;
+use lib_dachshund::dachshund::algorithms::algebraic_connectivity::AlgebraicConnectivity;
+use lib_dachshund::dachshund::algorithms::betweenness::{Betweenness, BetweennessOptions};
 use lib_dachshund::dachshund::algorithms::coreness::{Coreness, FractionalCoreness};
+use lib_dachshund::dachshund::algorithms::laplacian::Laplacian;
 use lib_dachshund::dachshund::error::{CLQError, CLQResult};
 use lib_dachshund::dachshund::graph_base::GraphBase;
 use lib_dachshund::dachshund::graph_builder_base::GraphBuilderBase;
 use lib_dachshund::dachshund::id_types::NodeId;
-use lib_dachshund::dachshund::node::WeightedNodeBase;
+use lib_dachshund::dachshund::node::{NodeBase, WeightedNodeBase};
 use lib_dachshund::dachshund::weighted_undirected_graph::WeightedUndirectedGraph;
 use lib_dachshund::dachshund::weighted_undirected_graph_builder::WeightedUndirectedGraphBuilder;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
 fn get_graph(idx: usize) -> CLQResult<WeightedUndirectedGraph> {
     let v = match idx {
@@ -133,3 +139,198 @@ fn test_fractional_coreness() {
         );
     }
 }
+
+#[test]
+fn test_weighted_graph_mutation() -> CLQResult<()> {
+    let mut graph = get_graph(0)?;
+    assert!(graph.add_edge(NodeId::from(1), NodeId::from(2), 0.5));
+    assert!(!graph.add_edge(NodeId::from(2), NodeId::from(1), 1.5));
+    assert_eq!(graph.get_node_weight(NodeId::from(1)), 2.5);
+    assert_eq!(graph.get_node_weight(NodeId::from(2)), 3.5);
+    assert_eq!(graph.count_edges(), 4);
+
+    assert!(graph.remove_node(NodeId::from(0)));
+    assert_eq!(graph.ids.len(), 3);
+    assert_eq!(graph.count_edges(), 1);
+    assert_eq!(graph.get_node_weight(NodeId::from(3)), 0.0);
+    assert!(graph.remove_edge(NodeId::from(1), NodeId::from(2)));
+    assert!(!graph.remove_edge(NodeId::from(1), NodeId::from(2)));
+    assert_eq!(graph.get_node(NodeId::from(2)).degree(), 0);
+    assert!(graph.add_node(NodeId::from(7)));
+    assert_eq!(graph.count_nodes(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_weighted_laplacians() -> CLQResult<()> {
+    let graph = get_graph(0)?;
+    let (adjacency, ids) = graph.get_adjacency_matrix();
+    assert_eq!(ids, (0..4).map(NodeId::from).collect::<Vec<NodeId>>());
+    assert_eq!(adjacency.row(0).iter().sum::<f64>(), 6.0);
+    assert_eq!(adjacency[(3, 0)], 3.0);
+
+    let (laplacian, _ids) = graph.get_laplacian_matrix();
+    assert_eq!(laplacian.diagonal().as_slice(), &[6.0, 1.0, 2.0, 3.0]);
+    assert_eq!(laplacian[(0, 2)], -2.0);
+    assert_eq!(graph.get_sparse_laplacian_matrix().0.to_dense(), laplacian);
+
+    let (normalized, _ids) = graph.get_normalized_laplacian_matrix();
+    assert!(normalized
+        .diagonal()
+        .iter()
+        .all(|x| (x - 1.0).abs() < 1e-12));
+    assert!((normalized[(0, 3)] + 3.0 / (6.0_f64 * 3.0).sqrt()).abs() < 1e-12);
+
+    // each row of the random walk Laplacian is 1 on the diagonal and minus
+    // the transition probabilities elsewhere, so rows sum to 0.
+    let (random_walk, _ids) = graph.get_random_walk_laplacian_matrix();
+    assert!((random_walk[(0, 3)] + 0.5).abs() < 1e-12);
+    assert_eq!(random_walk[(3, 0)], -1.0);
+    for row in random_walk.row_iter() {
+        assert!(row.iter().sum::<f64>().abs() < 1e-12);
+    }
+    // the symmetric and random walk versions are similar matrices.
+    let mut values: Vec<f64> = normalized
+        .symmetric_eigen()
+        .eigenvalues
+        .iter()
+        .cloned()
+        .collect();
+    let mut rw_values: Vec<f64> = random_walk
+        .complex_eigenvalues()
+        .iter()
+        .map(|x| x.re)
+        .collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    rw_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    for (x, y) in values.iter().zip(rw_values) {
+        assert!((x - y).abs() < 1e-9);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_weighted_algebraic_connectivity() -> CLQResult<()> {
+    // doubling every weight doubles the Laplacian, hence its eigenvalues.
+    let path = get_graph(5)?;
+    let unit_path = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (3, 4, 1.0),
+    ])?;
+    let connectivity = path.get_algebraic_connectivity()?.value;
+    assert!((connectivity - 2.0 * unit_path.get_algebraic_connectivity()?.value).abs() < 1e-9);
+    // a weak link between two strongly tied pairs splits the graph there.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 10.0),
+        (1, 2, 0.1),
+        (2, 3, 10.0),
+    ])?;
+    let fiedler = graph.get_fiedler_vector()?.value;
+    let sign = |i: i64| fiedler[&NodeId::from(i)].signum();
+    assert_eq!(sign(0), sign(1));
+    assert_eq!(sign(2), sign(3));
+    assert_ne!(sign(0), sign(2));
+    assert!(graph.get_algebraic_connectivity()?.value < 0.2);
+    Ok(())
+}
+
+#[test]
+fn test_weighted_betweenness() -> CLQResult<()> {
+    let weighted = BetweennessOptions {
+        weighted: true,
+        ..BetweennessOptions::default()
+    };
+    // a square whose heavy edge is on no weighted shortest path.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 2, 1.0),
+        (2, 3, 1.0),
+        (3, 0, 10.0),
+    ])?;
+    let hops = graph.get_node_betweenness_brandes()?;
+    let bet = graph.get_node_betweenness_brandes_with_options(weighted)?;
+    let expected = [0.0, 2.0, 2.0, 0.0];
+    for (i, value) in expected.iter().enumerate() {
+        assert_eq!(hops[&NodeId::from(i as i64)], 0.5);
+        assert_eq!(bet[&NodeId::from(i as i64)], *value);
+    }
+    // 0 - 1 - 3 and 0 - 2 - 3 are both of length 2.
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![
+        (0, 1, 1.0),
+        (1, 3, 1.0),
+        (0, 2, 0.5),
+        (2, 3, 1.5),
+    ])?;
+    let bet = graph.get_node_betweenness_brandes_with_options(weighted)?;
+    let expected = [1.0, 0.5, 0.5, 0.0];
+    for (i, value) in expected.iter().enumerate() {
+        assert_eq!(bet[&NodeId::from(i as i64)], *value);
+    }
+
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(vec![(0, 1, 1.0), (1, 2, -1.0)])?;
+    assert!(graph.get_node_betweenness_brandes().is_ok());
+    assert!(graph
+        .get_node_betweenness_brandes_with_options(weighted)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_weighted_approximate_betweenness() -> CLQResult<()> {
+    // without weights, path lengths count edges: 0 is only on the paths from 3 to
+    // the four other nodes.
+    let graph = get_graph(4)?;
+    let exact = graph.get_node_betweenness_brandes()?;
+    assert_eq!(exact[&NodeId::from(0)], 4.0);
+    let approx = graph.get_approximate_node_betweenness(
+        0.1,
+        0.1,
+        BetweennessOptions::default(),
+        &mut StdRng::seed_from_u64(0),
+    )?;
+    assert_eq!(approx.num_samples, 0);
+    assert_eq!(approx.values, exact);
+
+    // with few samples, estimates are coarse, but within the error bound.
+    let approx = graph.get_approximate_node_betweenness(
+        0.9,
+        0.1,
+        BetweennessOptions::default(),
+        &mut StdRng::seed_from_u64(0),
+    )?;
+    assert!(approx.num_samples > 0 && approx.num_samples < graph.count_nodes());
+    for (id, value) in &exact {
+        assert!((approx.values[id] - value).abs() <= approx.max_error);
+    }
+
+    // a 20 x 20 grid, with vertical edges twice as long as horizontal ones.
+    let side: i64 = 20;
+    let mut edges: Vec<(i64, i64, f64)> = Vec::new();
+    for i in 0..side {
+        for j in 0..side {
+            if i + 1 < side {
+                edges.push((i * side + j, (i + 1) * side + j, 2.0));
+            }
+            if j + 1 < side {
+                edges.push((i * side + j, i * side + j + 1, 1.0));
+            }
+        }
+    }
+    let graph = WeightedUndirectedGraphBuilder {}.from_vector(edges)?;
+    let options = BetweennessOptions {
+        weighted: true,
+        ..BetweennessOptions::default()
+    };
+    let exact = graph.get_node_betweenness_brandes_with_options(options)?;
+    let approx =
+        graph.get_approximate_node_betweenness(0.2, 0.1, options, &mut StdRng::seed_from_u64(0))?;
+    // from a corner, the furthest nodes are at 57 and 56, so shortest paths have
+    // at most 57 + 56 + 1 nodes: 0.5 / 0.2^2 * (floor(log2(112)) + 1 + ln(10)).
+    assert_eq!(approx.num_samples, 117);
+    for (id, value) in &exact {
+        assert!((approx.values[id] - value).abs() <= approx.max_error);
+    }
+    Ok(())
+}